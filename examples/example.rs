@@ -114,8 +114,12 @@ fn main() -> Result<(), AStarError> {
     match result {
         Ok(path) => {
             // Print the grid with the found path.
-            Node::print_grid(&grid, &path);
-            println!("Path found: {:?}", path);
+            Node::print_grid(&grid, &Some(path.positions().to_vec()));
+            println!(
+                "Path found: {:?} (cost {})",
+                path.positions(),
+                path.total_cost()
+            );
         }
         Err(e) => {
             // Print an error message using the Display implementation of AStarError.
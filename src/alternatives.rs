@@ -0,0 +1,271 @@
+use crate::astar::{AStar, AStarError, Restrictions};
+use crate::path::Path;
+use std::collections::HashSet;
+
+/// One of several paths between the same start and goal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alternative {
+    /// The path from the start to the goal.
+    pub path: Path,
+    /// The total cost of the path, without any penalties used to find it.
+    pub cost: usize,
+    /// The share of the best path that this path also follows, from `0.0` to `1.0`.
+    ///
+    /// Shared steps are weighted by their cost, counting steps without cost as `1`.
+    /// The best path has an overlap of `1.0`.
+    pub overlap: f64,
+}
+
+/// Options for `AStar::find_alternative_paths`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlternativeOptions {
+    /// The maximum number of paths to return, including the best one.
+    pub count: usize,
+    /// The share of a step's cost added to it every time a found path takes it.
+    pub penalty: f64,
+    /// Paths sharing more than this share with an already accepted path are rejected.
+    pub max_overlap: f64,
+    /// Paths costing more than this multiple of the best path are rejected.
+    pub max_cost_ratio: f64,
+    /// The maximum number of searches, including the first one.
+    pub max_searches: usize,
+}
+
+impl Default for AlternativeOptions {
+    fn default() -> Self {
+        AlternativeOptions {
+            count: 3,
+            penalty: 0.5,
+            max_overlap: 0.8,
+            max_cost_ratio: 1.5,
+            max_searches: 10,
+        }
+    }
+}
+
+impl AStar {
+    /// Finds the `k` cheapest loopless paths from the start to the goal with Yen's algorithm.
+    ///
+    /// Paths are returned from the cheapest to the most expensive and never visit
+    /// a position twice. Consecutive paths often differ in a single detour; use
+    /// `AStar::find_alternative_paths` for paths that are clearly different.
+    ///
+    /// # Parameters
+    /// - `start`: The starting point as a tuple (x, y).
+    /// - `goal`: The goal point as a tuple (x, y).
+    /// - `k`: The maximum number of paths to return.
+    ///
+    /// # Returns
+    /// Up to `k` paths, fewer if the map has no more loopless paths, or an `AStarError`
+    /// if no path is found at all.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, Node};
+    ///
+    /// let grid = vec![
+    ///     vec![0, 0, 0],
+    ///     vec![0, 1, 0],
+    ///     vec![0, 0, 0],
+    /// ];
+    /// let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+    /// let paths = astar.find_k_shortest_paths((0, 0), (2, 2), 3).unwrap();
+    /// // Only two ways lead around the blocked centre
+    /// assert_eq!(paths.len(), 2);
+    /// assert_eq!((paths[0].cost, paths[1].cost), (4, 4));
+    /// assert_eq!(paths[1].overlap, 0.0);
+    /// ```
+    pub fn find_k_shortest_paths(
+        &mut self,
+        start: (usize, usize),
+        goal: (usize, usize),
+        k: usize,
+    ) -> Result<Vec<Alternative>, AStarError> {
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut found = vec![self.find_shortest_path(start, goal)?];
+        let mut candidates: Vec<Path> = Vec::new();
+
+        while found.len() < k {
+            let previous = found[found.len() - 1].clone();
+            let positions = previous.positions();
+
+            for spur_index in 0..previous.len() {
+                let spur = positions[spur_index];
+                let root = &positions[..=spur_index];
+
+                // Leave the root through a step no found path takes from there
+                let mut restrictions = Restrictions::default();
+                for path in &found {
+                    let other = path.positions();
+                    if other.len() > spur_index + 1 && &other[..=spur_index] == root {
+                        restrictions
+                            .removed_edges
+                            .insert((key(other[spur_index]), key(other[spur_index + 1])));
+                    }
+                }
+                // Keep the path loopless
+                restrictions
+                    .blocked
+                    .extend(root[..spur_index].iter().map(|&position| key(position)));
+
+                let spur_path = match self.with_restrictions(&mut restrictions, |astar| {
+                    astar.find_shortest_path(spur, goal)
+                }) {
+                    Ok(path) => path,
+                    Err(AStarError::PathNotFound(_)) => continue,
+                    Err(error) => return Err(error),
+                };
+
+                let mut candidate = Path::from_parts(
+                    root.to_vec(),
+                    previous.step_costs()[..spur_index].to_vec(),
+                    previous.directions()[..spur_index].to_vec(),
+                );
+                candidate.append(&spur_path);
+                let is_new = |path: &Path| path.positions() != candidate.positions();
+                if found.iter().all(is_new) && candidates.iter().all(is_new) {
+                    candidates.push(candidate);
+                }
+            }
+
+            let Some(cheapest) = candidates
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, path)| (path.total_cost(), path.len()))
+                .map(|(index, _)| index)
+            else {
+                break;
+            };
+            found.push(candidates.swap_remove(cheapest));
+        }
+
+        Ok(alternatives(found))
+    }
+
+    /// Finds paths from the start to the goal that are clearly different from each other.
+    ///
+    /// Uses the penalty method: after every search, the steps of the found path get
+    /// more expensive, so the next search is pushed onto other routes. A path is
+    /// kept if it does not overlap too much with the paths kept before it and does
+    /// not cost too much more than the best path.
+    ///
+    /// # Parameters
+    /// - `start`: The starting point as a tuple (x, y).
+    /// - `goal`: The goal point as a tuple (x, y).
+    /// - `options`: How many paths to find and how different they must be.
+    ///
+    /// # Returns
+    /// The best path followed by up to `options.count - 1` alternatives, in the order
+    /// they were found, or an `AStarError` if no path is found at all.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, AlternativeOptions, Node};
+    ///
+    /// let grid = vec![
+    ///     vec![0, 0, 0, 0, 0],
+    ///     vec![0, 1, 1, 1, 0],
+    ///     vec![0, 0, 0, 0, 0],
+    /// ];
+    /// let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+    /// let paths = astar
+    ///     .find_alternative_paths((0, 1), (4, 1), &AlternativeOptions::default())
+    ///     .unwrap();
+    /// assert_eq!(paths.len(), 2);
+    /// assert!(paths[1].overlap < 0.5);
+    /// ```
+    pub fn find_alternative_paths(
+        &mut self,
+        start: (usize, usize),
+        goal: (usize, usize),
+        options: &AlternativeOptions,
+    ) -> Result<Vec<Alternative>, AStarError> {
+        if options.count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let best = self.find_shortest_path(start, goal)?;
+        let max_cost = best.total_cost() as f64 * options.max_cost_ratio;
+        let mut restrictions = Restrictions::default();
+        penalize(&mut restrictions, &best, options.penalty);
+        let mut found = vec![best];
+
+        for _ in 1..options.max_searches {
+            if found.len() >= options.count {
+                break;
+            }
+
+            let path = self.with_restrictions(&mut restrictions, |astar| {
+                astar.find_shortest_path(start, goal)
+            })?;
+
+            penalize(&mut restrictions, &path, options.penalty);
+            let is_different = found
+                .iter()
+                .all(|other| overlap(other, &path) <= options.max_overlap);
+            if is_different && path.total_cost() as f64 <= max_cost {
+                found.push(path);
+            }
+        }
+
+        Ok(alternatives(found))
+    }
+}
+
+/// Converts a position given as `(x, y)` into a map key.
+fn key(position: (usize, usize)) -> (usize, usize) {
+    (position.1, position.0)
+}
+
+/// Makes every step of a path more expensive for the following searches.
+fn penalize(restrictions: &mut Restrictions, path: &Path, penalty: f64) {
+    for (pair, &cost) in path.positions().windows(2).zip(path.step_costs()) {
+        let extra = (cost.max(1) as f64 * penalty).ceil() as usize;
+        *restrictions
+            .extra_costs
+            .entry((key(pair[0]), key(pair[1])))
+            .or_insert(0) += extra;
+    }
+}
+
+/// Returns the share of `best` that `path` also follows, weighted by step cost.
+fn overlap(best: &Path, path: &Path) -> f64 {
+    let steps: HashSet<_> = path
+        .positions()
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .collect();
+
+    let mut shared = 0;
+    let mut total = 0;
+    for (pair, &cost) in best.positions().windows(2).zip(best.step_costs()) {
+        // Steps without cost still count, so free paths can be compared
+        let weight = cost.max(1);
+        total += weight;
+        if steps.contains(&(pair[0], pair[1])) {
+            shared += weight;
+        }
+    }
+
+    if total == 0 {
+        1.0
+    } else {
+        shared as f64 / total as f64
+    }
+}
+
+/// Pairs found paths with their cost and their overlap with the first path.
+fn alternatives(paths: Vec<Path>) -> Vec<Alternative> {
+    let best = paths[0].clone();
+    paths
+        .into_iter()
+        .map(|path| Alternative {
+            cost: path.total_cost(),
+            overlap: overlap(&best, &path),
+            path,
+        })
+        .collect()
+}
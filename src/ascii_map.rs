@@ -0,0 +1,325 @@
+use crate::node::Node;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The meaning of a character in an ASCII map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tile {
+    /// A free cell with the default cost of `1`.
+    Free,
+    /// A blocked cell.
+    Blocked,
+    /// A free cell with the given cost.
+    Cost(usize),
+    /// The start position. The cell itself is free.
+    Start,
+    /// The goal position. The cell itself is free.
+    Goal,
+}
+
+/// Maps characters of an ASCII map to tiles.
+///
+/// The default legend uses `.` for free cells, `#` for blocked cells, `S` and `G`
+/// for the start and goal, and the digits `1`-`9` for cells with that cost. There
+/// is no digit for a cost of `0`, which would make searches on the map free.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Legend {
+    tiles: BTreeMap<char, Tile>,
+}
+
+impl Legend {
+    /// Creates a legend without any characters.
+    ///
+    /// # Returns
+    /// An empty `Legend`.
+    pub fn empty() -> Self {
+        Legend {
+            tiles: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the tile a character stands for.
+    ///
+    /// # Parameters
+    /// - `character`: The character in the map.
+    /// - `tile`: The tile the character stands for.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{Legend, Tile};
+    ///
+    /// let mut legend = Legend::default();
+    /// legend.set('~', Tile::Cost(4)); // Shallow water
+    /// legend.set('T', Tile::Blocked); // Trees
+    /// assert_eq!(legend.get('~'), Some(Tile::Cost(4)));
+    /// ```
+    pub fn set(&mut self, character: char, tile: Tile) {
+        self.tiles.insert(character, tile);
+    }
+
+    /// Removes a character from the legend.
+    pub fn remove(&mut self, character: char) {
+        self.tiles.remove(&character);
+    }
+
+    /// Returns the tile a character stands for, if any.
+    pub fn get(&self, character: char) -> Option<Tile> {
+        self.tiles.get(&character).copied()
+    }
+
+    /// Returns the character used to write a tile.
+    ///
+    /// If several characters stand for the same tile, the smallest one is used.
+    pub fn character(&self, tile: Tile) -> Option<char> {
+        self.tiles
+            .iter()
+            .find(|(_, &candidate)| candidate == tile)
+            .map(|(&character, _)| character)
+    }
+}
+
+impl Default for Legend {
+    fn default() -> Self {
+        let mut legend = Legend::empty();
+        legend.set('.', Tile::Free);
+        legend.set('#', Tile::Blocked);
+        legend.set('S', Tile::Start);
+        legend.set('G', Tile::Goal);
+        for digit in 1..10 {
+            legend.set(char::from(b'0' + digit as u8), Tile::Cost(digit));
+        }
+        legend
+    }
+}
+
+/// Kinds of errors that can occur while reading or writing an ASCII map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AsciiMapErrorKind {
+    /// The map does not contain any cells.
+    EmptyMap,
+    /// A character that is not in the legend.
+    UnknownCharacter(char),
+    /// A line whose length differs from the first line.
+    RaggedLine { expected: usize, found: usize },
+    /// A second start marker.
+    DuplicateStart,
+    /// A second goal marker.
+    DuplicateGoal,
+    /// A tile that has no character in the legend.
+    UnmappedTile(Tile),
+}
+
+/// An error while reading or writing an ASCII map, with the 1-based line and column it occurred at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsciiMapError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: AsciiMapErrorKind,
+}
+
+impl fmt::Display for AsciiMapError {
+    /// Formats the error for display in user-facing contexts.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::AsciiMap;
+    ///
+    /// let error = "..\n.x".parse::<AsciiMap>().unwrap_err();
+    /// assert_eq!(error.to_string(), "line 2, column 2: unknown character 'x'");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            AsciiMapErrorKind::EmptyMap => write!(f, "the map is empty"),
+            AsciiMapErrorKind::UnknownCharacter(character) => {
+                write!(f, "unknown character {:?}", character)
+            }
+            AsciiMapErrorKind::RaggedLine { expected, found } => {
+                write!(f, "expected {} cells but found {}", expected, found)
+            }
+            AsciiMapErrorKind::DuplicateStart => write!(f, "the map has more than one start"),
+            AsciiMapErrorKind::DuplicateGoal => write!(f, "the map has more than one goal"),
+            AsciiMapErrorKind::UnmappedTile(tile) => {
+                write!(f, "no character in the legend for {:?}", tile)
+            }
+        }
+    }
+}
+
+impl Error for AsciiMapError {}
+
+/// A node map read from an ASCII map, together with its start and goal markers.
+///
+/// Nodes are keyed like in `Node::grid_to_nodes`, with every line of the text
+/// being one row. `start` and `goal` are given as `(column, row)`, ready to be
+/// passed to `AStar::find_shortest_path`.
+#[derive(Debug, Clone)]
+pub struct AsciiMap {
+    pub nodes: HashMap<(usize, usize), Node>,
+    pub width: usize,
+    pub height: usize,
+    pub start: Option<(usize, usize)>,
+    pub goal: Option<(usize, usize)>,
+}
+
+impl AsciiMap {
+    /// Parses an ASCII map.
+    ///
+    /// Every line is a row of cells and all lines must have the same length.
+    /// Trailing empty lines are ignored.
+    ///
+    /// # Parameters
+    /// - `text`: The text of the map.
+    /// - `legend`: The meaning of the characters in the map.
+    ///
+    /// # Returns
+    /// The parsed `AsciiMap`, or an `AsciiMapError` pointing at the offending character.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, AsciiMap, Legend};
+    ///
+    /// let map = AsciiMap::parse("S.#\n.9#\n..G", &Legend::default()).unwrap();
+    /// assert_eq!(map.start, Some((0, 0)));
+    /// assert_eq!(map.goal, Some((2, 2)));
+    ///
+    /// let mut astar = AStar::new(map.nodes);
+    /// let path = astar.find_shortest_path(map.start.unwrap(), map.goal.unwrap());
+    /// ```
+    pub fn parse(text: &str, legend: &Legend) -> Result<Self, AsciiMapError> {
+        let lines: Vec<&str> = text
+            .trim_end_matches(['\n', '\r'])
+            .lines()
+            .map(|line| line.trim_end_matches('\r'))
+            .collect();
+
+        let width = lines.first().map_or(0, |line| line.chars().count());
+        if width == 0 {
+            return Err(AsciiMapError {
+                line: 1,
+                column: 1,
+                kind: AsciiMapErrorKind::EmptyMap,
+            });
+        }
+        let height = lines.len();
+
+        let mut nodes = HashMap::new();
+        let mut start = None;
+        let mut goal = None;
+
+        for (row, line) in lines.iter().enumerate() {
+            let found = line.chars().count();
+            if found != width {
+                return Err(AsciiMapError {
+                    line: row + 1,
+                    column: found.min(width) + 1,
+                    kind: AsciiMapErrorKind::RaggedLine {
+                        expected: width,
+                        found,
+                    },
+                });
+            }
+
+            for (column, character) in line.chars().enumerate() {
+                let error = |kind| AsciiMapError {
+                    line: row + 1,
+                    column: column + 1,
+                    kind,
+                };
+                let tile = legend
+                    .get(character)
+                    .ok_or_else(|| error(AsciiMapErrorKind::UnknownCharacter(character)))?;
+
+                let mut node = Node::new(row, column, tile == Tile::Blocked, height - 1, width - 1);
+                match tile {
+                    Tile::Cost(cost) => node.set_cost(cost),
+                    Tile::Start if start.is_some() => {
+                        return Err(error(AsciiMapErrorKind::DuplicateStart))
+                    }
+                    Tile::Start => start = Some((column, row)),
+                    Tile::Goal if goal.is_some() => {
+                        return Err(error(AsciiMapErrorKind::DuplicateGoal))
+                    }
+                    Tile::Goal => goal = Some((column, row)),
+                    Tile::Free | Tile::Blocked => {}
+                }
+                nodes.insert((row, column), node);
+            }
+        }
+
+        Ok(AsciiMap {
+            nodes,
+            width,
+            height,
+            start,
+            goal,
+        })
+    }
+
+    /// Writes the map back as text, the inverse of `AsciiMap::parse`.
+    ///
+    /// Cells missing from `nodes` are written as blocked cells, and free cells
+    /// with a cost of `1` use the `Tile::Free` character.
+    ///
+    /// # Parameters
+    /// - `legend`: The characters to use for the tiles.
+    ///
+    /// # Returns
+    /// The text of the map with one line per row, or an `AsciiMapError` if the legend
+    /// has no character for a tile in the map.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AsciiMap, Legend};
+    ///
+    /// let legend = Legend::default();
+    /// let map = AsciiMap::parse("S.#\n.9G", &legend).unwrap();
+    /// assert_eq!(map.write(&legend).unwrap(), "S.#\n.9G\n");
+    /// ```
+    pub fn write(&self, legend: &Legend) -> Result<String, AsciiMapError> {
+        let mut text = String::new();
+
+        for row in 0..self.height {
+            for column in 0..self.width {
+                let tile = if self.start == Some((column, row)) {
+                    Tile::Start
+                } else if self.goal == Some((column, row)) {
+                    Tile::Goal
+                } else {
+                    match self.nodes.get(&(row, column)) {
+                        None => Tile::Blocked,
+                        Some(node) if node.is_blocked => Tile::Blocked,
+                        Some(node) if node.cost == 1 => Tile::Free,
+                        Some(node) => Tile::Cost(node.cost),
+                    }
+                };
+
+                // Fall back to the digit for a cost of 1 if there is no free character
+                let character = legend
+                    .character(tile)
+                    .or_else(|| (tile == Tile::Free).then(|| legend.character(Tile::Cost(1)))?)
+                    .ok_or(AsciiMapError {
+                        line: row + 1,
+                        column: column + 1,
+                        kind: AsciiMapErrorKind::UnmappedTile(tile),
+                    })?;
+                text.push(character);
+            }
+            text.push('\n');
+        }
+
+        Ok(text)
+    }
+}
+
+impl FromStr for AsciiMap {
+    type Err = AsciiMapError;
+
+    /// Parses an ASCII map with the default legend.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        AsciiMap::parse(text, &Legend::default())
+    }
+}
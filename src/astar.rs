@@ -1,23 +1,275 @@
+use crate::components::ComponentIndex;
+use crate::heuristic::Heuristic;
+use crate::landmarks::LandmarkTable;
 use crate::node::Node;
+use crate::path::Path;
 use crate::priority_queue::{PriorityQueue, State};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
 use std::fmt;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
 
 /// Error types that can occur during A* pathfinding.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AStarError {
+    /// The start position is blocked.
     StartNodeBlocked((usize, usize)),
+    /// The goal position is blocked.
     GoalNodeBlocked((usize, usize)),
+    /// The start or goal position is not in the map.
     NodeNotFound((usize, usize)),
-    PathNotFound((usize, usize)),
+    /// The goal cannot be reached from the start, with an explanation why.
+    PathNotFound(Unreachable),
+    /// A node links to a neighbor or custom edge target that is not in the map.
+    InvalidNeighbor {
+        node: (usize, usize),
+        neighbor: (usize, usize),
+    },
+    /// The search is configured in a way that cannot give correct results.
+    InvalidConfiguration(String),
+    /// The search expanded more nodes than allowed by `AStar::set_max_expansions`.
+    SearchLimitExceeded {
+        limit: usize,
+        position: (usize, usize),
+    },
+}
+
+/// Why a goal cannot be reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnreachableReason {
+    /// No chain of edges connects the start and goal, even ignoring their direction.
+    DisconnectedComponents,
+    /// The start and goal are connected, but only against the direction of one-way
+    /// neighbors or custom edges.
+    OneWayEdges,
+    /// The search kept too little of the map to tell why, see `AStar::find_path_ida`.
+    Unknown,
+}
+
+impl fmt::Display for UnreachableReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnreachableReason::DisconnectedComponents => {
+                write!(
+                    f,
+                    "the start and goal are in different connected components"
+                )
+            }
+            UnreachableReason::OneWayEdges => {
+                write!(f, "the goal can only be reached against one-way edges")
+            }
+            UnreachableReason::Unknown => write!(f, "the goal cannot be reached from the start"),
+        }
+    }
+}
+
+/// Explains why a search could not reach its goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Unreachable {
+    pub reason: UnreachableReason,
+    /// The reachable position closest to the goal by the heuristic, as `(x, y)`.
+    pub closest: (usize, usize),
+    /// The level of `closest` when searching a `MultiLevelMap`.
+    pub level: Option<usize>,
+    /// The number of positions reachable from the start, all of which were explored.
+    /// `AStar::find_path_ida` counts its expansions instead, see there.
+    pub explored: usize,
+}
+
+/// Options for `AStar::find_anytime_path`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnytimeOptions {
+    /// The heuristic weight of the first, quick search, at least `1.0`.
+    pub initial_weight: f64,
+    /// How much the weight is lowered for every following search.
+    pub weight_step: f64,
+    /// How long to keep improving the path once the first one is found.
+    pub time_budget: Duration,
+}
+
+impl Default for AnytimeOptions {
+    fn default() -> Self {
+        AnytimeOptions {
+            initial_weight: 3.0,
+            weight_step: 0.5,
+            time_budget: Duration::from_millis(50),
+        }
+    }
+}
+
+/// The best path found by `AStar::find_anytime_path` within its time budget.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnytimePath {
+    /// The cheapest path found.
+    pub path: Path,
+    /// The path costs at most this many times as much as the shortest path. `1.0`
+    /// means the path is the shortest one.
+    pub bound: f64,
+    /// The number of searches completed, including the first one.
+    pub searches: usize,
+}
+
+/// Options for `AStar::find_path_ida`.
+#[derive(Debug, Clone, Copy)]
+pub struct IdaOptions<'a> {
+    /// The maximum number of entries in a transposition table, or `None` for no table.
+    ///
+    /// The table remembers the cheapest cost each position was reached at during an
+    /// iteration, so positions reached again at no lower cost are not searched twice.
+    /// Without a table, the search time grows exponentially with the path length on
+    /// maps with many alternative routes, such as open grids.
+    pub transposition_table: Option<usize>,
+    /// The components of the searched map, or `None`.
+    ///
+    /// With an index, unreachable goals are reported before searching. Without one,
+    /// IDA* only notices them after following every route from the start.
+    pub components: Option<&'a ComponentIndex>,
+}
+
+impl Default for IdaOptions<'_> {
+    /// Returns options with a transposition table of 4096 entries and no component index.
+    fn default() -> Self {
+        IdaOptions {
+            transposition_table: Some(4096),
+            components: None,
+        }
+    }
+}
+
+/// How an IDA* iteration ended.
+enum IdaOutcome {
+    /// The goal was found along these positions, given as `(x, y)`.
+    Found(Vec<(usize, usize)>),
+    /// The goal was not found; this is the lowest cost that exceeded the bound,
+    /// `usize::MAX` if none did.
+    Exceeded(usize),
 }
+
+/// The lowest cost each position was reached at during an IDA* iteration, up to a capacity.
+struct TranspositionTable {
+    entries: HashMap<(usize, usize), usize>,
+    capacity: usize,
+}
+
+impl TranspositionTable {
+    /// Records that a position was reached at a cost.
+    ///
+    /// # Returns
+    /// `true` if the position was already reached at a cost at least as low, so
+    /// searching it again cannot find anything new.
+    fn is_known(&mut self, position: (usize, usize), g_score: usize) -> bool {
+        let has_room = self.entries.len() < self.capacity;
+        match self.entries.get_mut(&position) {
+            Some(best) if *best <= g_score => return true,
+            Some(best) => *best = g_score,
+            None if has_room => {
+                self.entries.insert(position, g_score);
+            }
+            None => {}
+        }
+        false
+    }
+}
+
+/// What all IDA* iterations of a search have explored so far.
+struct IdaProgress {
+    /// The number of positions expanded.
+    expansions: usize,
+    /// The expanded position with the lowest estimate to the goal, and that estimate.
+    closest: ((usize, usize), usize),
+}
+
+/// A position on the current branch of an IDA* search, with its steps left to try.
+struct IdaFrame {
+    position: (usize, usize),
+    g_score: usize,
+    steps: Steps,
+    next: usize,
+}
+
+/// Map from a position to its score (`g_score` or `f_score`).
+type ScoreMap = HashMap<(usize, usize), usize>;
+
+/// Positions reachable in one step, each with the cost of the step.
+type Steps = Vec<((usize, usize), usize)>;
+
+/// Edges that cost less than the heuristic estimates between their ends, such as teleporters.
+///
+/// Diagonal steps of a grid that are cheaper than the heuristic assumes lower its
+/// diagonal cost instead, so eight-directional maps keep a useful estimate. Any
+/// path using one of the other shortcuts costs at least the estimate to the nearest
+/// shortcut source, plus the cheapest shortcut, plus the estimate from the shortcut
+/// target nearest to the goal. Taking the minimum of this bound and the heuristic
+/// keeps the estimate admissible.
+#[derive(Debug)]
+struct Shortcuts {
+    /// The configured heuristic, with its diagonal cost lowered to the cheapest diagonal step.
+    heuristic: Heuristic,
+    /// Sources of the other shortcuts.
+    sources: Vec<(usize, usize)>,
+    /// Targets of the other shortcuts.
+    targets: Vec<(usize, usize)>,
+    /// Cost of the cheapest other shortcut.
+    min_cost: usize,
+}
+
+/// A link between two map keys, in this direction.
+type Link = ((usize, usize), (usize, usize));
+
+/// Temporary changes to the map, used while searching for alternative paths.
+///
+/// Positions are given as map keys.
+#[derive(Debug, Default)]
+pub(crate) struct Restrictions {
+    /// Positions treated as blocked.
+    pub(crate) blocked: HashSet<(usize, usize)>,
+    /// Links between two positions that cannot be followed, in this direction.
+    pub(crate) removed_edges: HashSet<Link>,
+    /// Costs added to the links between two positions, in this direction.
+    pub(crate) extra_costs: HashMap<Link, usize>,
+}
+
+/// What a search is looking for, with positions given as map keys.
+enum Target<'a> {
+    /// Any of these positions, estimated by the closest one.
+    Positions(&'a [(usize, usize)]),
+    /// Any position accepted by a predicate. The remaining cost cannot be estimated.
+    Matching(&'a dyn Fn((usize, usize)) -> bool),
+}
+
+impl Target<'_> {
+    /// Checks whether a position is a goal of the search.
+    fn contains(&self, position: (usize, usize)) -> bool {
+        match self {
+            Target::Positions(goals) => goals.contains(&position),
+            Target::Matching(is_goal) => is_goal(position),
+        }
+    }
+}
+
 /// Structure implementing the A* algorithm.
 #[derive(Debug)]
 pub struct AStar {
     nodes: HashMap<(usize, usize), Node>,
     open_set: PriorityQueue,
     came_from: HashMap<(usize, usize), (usize, usize)>,
-    g_score: HashMap<(usize, usize), usize>,
-    f_score: HashMap<(usize, usize), usize>,
+    g_score: ScoreMap,
+    f_score: ScoreMap,
+    closed_set: HashSet<(usize, usize)>,
+    expansions: usize,
+    heuristic: Heuristic,
+    shortcuts: Shortcuts,
+    /// The cheapest shortcut plus the estimate from its target to the goals of the
+    /// current search, `None` if there are no such shortcuts or goals.
+    shortcut_exit: Option<usize>,
+    max_expansions: Option<usize>,
+    heuristic_weight: f64,
+    restrictions: Restrictions,
+    landmarks: Option<LandmarkTable>,
 }
 
 impl AStar {
@@ -31,35 +283,170 @@ impl AStar {
     ///
     /// # Example
     /// ```rust
+    /// use controlled_astar::AStar;
+    /// use std::collections::HashMap;
+    ///
     /// let nodes = HashMap::new();
     /// let astar = AStar::new(nodes);
     /// ```
     pub fn new(nodes: HashMap<(usize, usize), Node>) -> Self {
+        let shortcuts = Self::find_shortcuts(&nodes, Heuristic::default());
         AStar {
             nodes,
             open_set: PriorityQueue::new(),
             came_from: HashMap::new(),
             g_score: HashMap::new(),
             f_score: HashMap::new(),
+            closed_set: HashSet::new(),
+            expansions: 0,
+            heuristic: Heuristic::default(),
+            shortcuts,
+            shortcut_exit: None,
+            max_expansions: None,
+            heuristic_weight: 1.0,
+            restrictions: Restrictions::default(),
+            landmarks: None,
         }
     }
 
-    /// Calculates the Manhattan distance between two points.
+    /// Sets the heuristic used to estimate the remaining cost to the goal.
     ///
-    /// The Manhattan distance is the sum of the absolute differences of their Cartesian coordinates.
+    /// The default is `Heuristic::Manhattan`. Hex grids created with
+    /// `Node::hex_grid_to_nodes` should use `Heuristic::Hex` with the same layout.
+    ///
+    /// Changing the heuristic scans the map once for edges it overestimates, such as
+    /// teleporters, so that searches stay optimal. Diagonal neighbors cheaper than
+    /// the heuristic assumes lower its diagonal cost; `Heuristic::Octile` with the
+    /// real costs of the map gives tighter estimates on eight-directional maps.
     ///
     /// # Parameters
-    /// - `start`: The starting point as a tuple (x, y).
-    /// - `goal`: The goal point as a tuple (x, y).
+    /// - `heuristic`: The heuristic to use for subsequent searches.
     ///
-    /// # Returns
-    /// The Manhattan distance between the start and goal points as a `usize`.
-    fn manhattan_distance(start: (usize, usize), goal: (usize, usize)) -> usize {
-        let (x1, y1) = start;
-        let (x2, y2) = goal;
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, HexLayout, Heuristic, Node};
+    ///
+    /// let grid = vec![vec![0; 4]; 4];
+    /// let mut astar = AStar::new(Node::hex_grid_to_nodes(&grid, HexLayout::OddR));
+    /// astar.set_heuristic(Heuristic::Hex(HexLayout::OddR));
+    /// let path = astar.find_shortest_path((0, 0), (3, 3));
+    /// ```
+    pub fn set_heuristic(&mut self, heuristic: Heuristic) {
+        if heuristic != self.heuristic {
+            self.heuristic = heuristic;
+            self.shortcuts = Self::find_shortcuts(&self.nodes, heuristic);
+        }
+    }
+
+    /// Limits how many nodes a search may expand before giving up.
+    ///
+    /// Searches that would expand more nodes fail with `AStarError::SearchLimitExceeded`.
+    /// Every expansion counts, including nodes expanded more than once, while
+    /// reaching the goal does not. There is no limit by default.
+    ///
+    /// # Parameters
+    /// - `limit`: The maximum number of expanded nodes, or `None` for no limit.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, AStarError, Node};
+    ///
+    /// let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 10]; 10]));
+    /// astar.set_max_expansions(Some(5));
+    /// assert!(matches!(
+    ///     astar.find_shortest_path((0, 0), (9, 9)),
+    ///     Err(AStarError::SearchLimitExceeded { limit: 5, .. })
+    /// ));
+    /// ```
+    pub fn set_max_expansions(&mut self, limit: Option<usize>) {
+        self.max_expansions = limit;
+    }
 
-        // Compute the absolute differences in x and y coordinates and sum them
-        (x1 as isize - x2 as isize).abs() as usize + (y1 as isize - y2 as isize).abs() as usize
+    /// Inflates the heuristic by a weight to trade path quality for speed (weighted A*).
+    ///
+    /// With a weight `w`, searches expand fewer nodes and find paths that cost at
+    /// most `w` times as much as the shortest path. The default weight `1.0` gives
+    /// shortest paths.
+    ///
+    /// # Parameters
+    /// - `weight`: The factor applied to the heuristic, at least `1.0`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, Node};
+    ///
+    /// let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 10]; 10]));
+    /// astar.set_heuristic_weight(1.5);
+    /// let path = astar.find_shortest_path((0, 0), (9, 9)).unwrap();
+    /// assert!(path.total_cost() as f64 <= 18.0 * 1.5);
+    /// ```
+    pub fn set_heuristic_weight(&mut self, weight: f64) {
+        self.heuristic_weight = weight;
+    }
+
+    /// Tightens the heuristic with precomputed landmark distances (ALT).
+    ///
+    /// Every estimate becomes the larger of the configured heuristic and the landmark
+    /// bound, so searches stay optimal and expand fewer nodes on maps where walls
+    /// make the heuristic too optimistic. The table should be built from the same
+    /// node map, see `LandmarkTable` for the changes it survives.
+    ///
+    /// # Parameters
+    /// - `landmarks`: The landmark distances, or `None` to use the heuristic alone.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, LandmarkStrategy, LandmarkTable, Node};
+    ///
+    /// let nodes = Node::grid_to_nodes(&vec![vec![0; 10]; 10]);
+    /// let mut astar = AStar::new(nodes.clone());
+    /// astar.set_landmarks(Some(LandmarkTable::new(&nodes, 4, LandmarkStrategy::Avoid)));
+    /// let path = astar.find_shortest_path((0, 0), (9, 9)).unwrap();
+    /// assert_eq!(path.total_cost(), 18);
+    /// ```
+    pub fn set_landmarks(&mut self, landmarks: Option<LandmarkTable>) {
+        self.landmarks = landmarks;
+    }
+
+    /// Returns the node map the searches run on.
+    pub fn nodes(&self) -> &HashMap<(usize, usize), Node> {
+        &self.nodes
+    }
+
+    /// Returns the positions waiting in the open set after the last search.
+    ///
+    /// Positions are given as `(x, y)`, like the positions of a path.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, Node};
+    ///
+    /// let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 5]; 5]));
+    /// astar.find_shortest_path((0, 0), (2, 0)).unwrap();
+    /// assert!(astar.closed_positions().contains(&(1, 0)));
+    /// assert!(astar.open_positions().contains(&(0, 1)));
+    /// ```
+    pub fn open_positions(&self) -> HashSet<(usize, usize)> {
+        self.open_set
+            .iter()
+            .map(|state| state.position)
+            .filter(|position| !self.closed_set.contains(position))
+            .map(|(x, y)| (y, x))
+            .collect()
+    }
+
+    /// Returns the positions expanded by the last search, as `(x, y)`.
+    pub fn closed_positions(&self) -> HashSet<(usize, usize)> {
+        self.closed_set.iter().map(|&(x, y)| (y, x)).collect()
+    }
+
+    /// Returns the g-scores of the last search, the cheapest known cost from the
+    /// start to each reached position, keyed by `(x, y)`.
+    pub fn g_scores(&self) -> HashMap<(usize, usize), usize> {
+        self.g_score
+            .iter()
+            .map(|(&(x, y), &score)| ((y, x), score))
+            .collect()
     }
 
     /// Initializes the `g_score` and `f_score` maps for the A* algorithm.
@@ -68,84 +455,256 @@ impl AStar {
     /// The `f_score` map estimates the total cost of the shortest path through each node.
     ///
     /// # Parameters
-    /// - `start`: The starting point as a tuple (x, y).
-    /// - `goal`: The goal point as a tuple (x, y).
+    /// - `starts`: The starting points as map keys, each with the cost already spent to be there.
+    /// - `target`: The goals of the search.
     ///
     /// # Returns
     /// A tuple containing the initialized `g_score` and `f_score` maps.
     fn initialize_scores(
-        start: (usize, usize),
-        goal: (usize, usize),
-    ) -> (
-        HashMap<(usize, usize), usize>,
-        HashMap<(usize, usize), usize>,
-    ) {
+        &self,
+        starts: &[((usize, usize), usize)],
+        target: &Target,
+    ) -> (ScoreMap, ScoreMap) {
         let mut g_score = HashMap::new();
         let mut f_score = HashMap::new();
 
-        // Set the starting point's g_score to 0
-        g_score.insert(start, 0);
+        for &(start, initial_cost) in starts {
+            // A start listed twice keeps its cheapest initial cost
+            if initial_cost >= *g_score.get(&start).unwrap_or(&usize::MAX) {
+                continue;
+            }
+
+            // Set the starting point's g_score to the cost already spent
+            g_score.insert(start, initial_cost);
 
-        // Compute the initial f_score as the Manhattan distance from the start to the goal
-        f_score.insert(start, Self::manhattan_distance(start, goal));
+            // Compute the initial f_score from the estimated distance from the start to the goal
+            f_score.insert(
+                start,
+                initial_cost.saturating_add(self.weighted_estimate(
+                    start,
+                    target,
+                    self.heuristic_weight,
+                )),
+            );
+        }
 
         (g_score, f_score)
     }
 
     /// Reconstructs the path from the start node to the goal node using the `came_from` map.
     ///
-    /// The path is reconstructed by tracing back from the goal node to the start node.
+    /// The path is reconstructed by tracing back from the goal node until a start
+    /// node, which has no entry in `came_from`.
     ///
     /// # Parameters
-    /// - `came_from`: A map indicating the parent of each node.
-    /// - `start`: The starting point as a tuple (x, y).
-    /// - `goal`: The goal point as a tuple (x, y).
+    /// - `goal`: The goal point as a map key.
     ///
     /// # Returns
-    /// A vector of tuples representing the path from the start to the goal.
-    fn reconstruct_path(
-        came_from: HashMap<(usize, usize), (usize, usize)>,
-        start: (usize, usize),
-        goal: (usize, usize),
-    ) -> Vec<(usize, usize)> {
+    /// The path from the start to the goal, with the cost and direction of every step.
+    fn reconstruct_path(&self, goal: (usize, usize)) -> Path {
         let mut path = Vec::new();
         let mut current = goal;
 
         // Trace the path from the goal to the start
-        while current != start {
+        while let Some(&previous) = self.came_from.get(&current) {
             path.push((current.1, current.0));
-            current = came_from[&current];
+            current = previous;
         }
 
         // Add the start point and reverse the path to get it from start to goal
-        path.push((start.1, start.0));
+        path.push((current.1, current.0));
         path.reverse();
-        path
+        Path::from_positions(&self.nodes, path).expect("the search only follows existing edges")
+    }
+
+    /// Collects the edges that are cheaper than the heuristic estimate between their ends.
+    ///
+    /// The node map of an `AStar` does not change, so this runs when the heuristic is set.
+    ///
+    /// # Parameters
+    /// - `nodes`: The node map.
+    /// - `heuristic`: The configured heuristic.
+    ///
+    /// # Returns
+    /// The `Shortcuts` of the map, without any if the heuristic never overestimates an edge.
+    fn find_shortcuts(nodes: &HashMap<(usize, usize), Node>, heuristic: Heuristic) -> Shortcuts {
+        let mut sources = HashSet::new();
+        let mut targets = HashSet::new();
+        let mut min_cost = usize::MAX;
+        let mut min_diagonal = usize::MAX;
+
+        for (&position, node) in nodes {
+            if node.is_blocked {
+                continue;
+            }
+            for (target, cost) in node.edges(nodes) {
+                // An edge the heuristic overestimates breaks its distance assumptions
+                if heuristic.estimate(position, target) <= cost {
+                    continue;
+                }
+                let is_diagonal =
+                    position.0.abs_diff(target.0) == 1 && position.1.abs_diff(target.1) == 1;
+                let has_diagonals =
+                    matches!(heuristic, Heuristic::Manhattan | Heuristic::Octile { .. });
+                if is_diagonal && has_diagonals {
+                    min_diagonal = min_diagonal.min(cost);
+                } else {
+                    sources.insert(position);
+                    targets.insert(target);
+                    min_cost = min_cost.min(cost);
+                }
+            }
+        }
+
+        // Diagonal steps cheaper than straight ones are taken in zigzags, leaving
+        // only the number of steps as a bound
+        let heuristic = match heuristic {
+            _ if min_diagonal == usize::MAX => heuristic,
+            Heuristic::Manhattan => Heuristic::Octile {
+                straight: min_diagonal.min(1),
+                diagonal: min_diagonal,
+            },
+            Heuristic::Octile { straight, .. } => Heuristic::Octile {
+                straight: straight.min(min_diagonal),
+                diagonal: min_diagonal,
+            },
+            heuristic => heuristic,
+        };
+        Shortcuts {
+            heuristic,
+            sources: sources.into_iter().collect(),
+            targets: targets.into_iter().collect(),
+            min_cost,
+        }
+    }
+
+    /// Computes the part of the shortcut bound that depends on the goals only.
+    ///
+    /// # Parameters
+    /// - `target`: The goals of the search.
+    ///
+    /// # Returns
+    /// The cheapest shortcut plus the estimate from the shortcut target nearest to a
+    /// goal, or `None` if there are no shortcuts or the goals are not known.
+    fn find_shortcut_exit(&self, target: &Target) -> Option<usize> {
+        // Without known goals there is no estimate to correct
+        let Target::Positions(goals) = target else {
+            return None;
+        };
+        let exit_estimate = self
+            .shortcuts
+            .targets
+            .iter()
+            .flat_map(|&exit| {
+                goals
+                    .iter()
+                    .map(move |&goal| self.shortcuts.heuristic.estimate(exit, goal))
+            })
+            .min()?;
+        Some(exit_estimate.saturating_add(self.shortcuts.min_cost))
+    }
+
+    /// Estimates the remaining cost from a position to the nearest goal.
+    ///
+    /// Uses the configured heuristic, lowered where shortcuts could make the goal cheaper
+    /// to reach, or the landmark bound if it is higher. Goals given by a predicate are estimated as `0`.
+    ///
+    /// # Parameters
+    /// - `position`: The position to estimate from.
+    /// - `target`: The goals of the search.
+    ///
+    /// # Returns
+    /// An estimate that never exceeds the real remaining cost.
+    fn estimate(&self, position: (usize, usize), target: &Target) -> usize {
+        let Target::Positions(goals) = target else {
+            return 0;
+        };
+        let heuristic = self.shortcuts.heuristic;
+        let estimate = goals
+            .iter()
+            .map(|&goal| heuristic.estimate(position, goal))
+            .min()
+            .unwrap_or(usize::MAX);
+        // Landmark bounds follow the real links, shortcuts included
+        let landmark_estimate = self.landmarks.as_ref().map_or(0, |landmarks| {
+            goals
+                .iter()
+                .map(|&goal| landmarks.estimate_keys(position, goal))
+                .min()
+                .unwrap_or(0)
+        });
+
+        let estimate = match self.shortcut_exit {
+            Some(exit) => {
+                let to_shortcut = self
+                    .shortcuts
+                    .sources
+                    .iter()
+                    .map(|&source| heuristic.estimate(position, source))
+                    .min()
+                    .unwrap_or(usize::MAX);
+                estimate.min(to_shortcut.saturating_add(exit))
+            }
+            None => estimate,
+        };
+        estimate.max(landmark_estimate)
+    }
+
+    /// Estimates the remaining cost to the nearest goal, inflated by a weight.
+    ///
+    /// # Parameters
+    /// - `position`: The position to estimate from.
+    /// - `target`: The goals of the search.
+    /// - `weight`: The factor applied to the estimate, at least `1.0`.
+    fn weighted_estimate(&self, position: (usize, usize), target: &Target, weight: f64) -> usize {
+        let estimate = self.estimate(position, target);
+        if weight == 1.0 {
+            estimate
+        } else {
+            (estimate as f64 * weight) as usize
+        }
     }
 
     /// Finds the neighbors of the current node that are not blocked.
     ///
+    /// Both directional neighbors and custom edges are followed, see `Node::edges` for their costs.
+    ///
     /// # Parameters
     /// - `current_node`: The current node from which to find neighbors.
     ///
     /// # Returns
-    /// A vector of positions representing the neighbors of the current node.
-    fn find_neighbors(&self, current_node: &Node) -> Vec<(usize, usize)> {
-        current_node
+    /// A vector of positions representing the neighbors of the current node, each with the cost of moving there.
+    fn find_neighbors(&self, current_node: &Node) -> Result<Steps, AStarError> {
+        // `Node::edges` skips targets missing from the map, report them instead
+        let targets = current_node
             .neighbors
             .values()
-            .filter_map(|&neighbor_pos| {
-                neighbor_pos.and_then(|pos| {
-                    // Check if the neighbor is not blocked and exists in the nodes map
-                    if let Some(neighbor_node) = self.nodes.get(&pos) {
-                        if !neighbor_node.is_blocked {
-                            return Some(pos);
-                        }
-                    }
-                    None
-                })
+            .flatten()
+            .chain(current_node.custom_edges.iter().map(|edge| &edge.target));
+        for &(x, y) in targets {
+            if !self.nodes.contains_key(&(x, y)) {
+                return Err(AStarError::InvalidNeighbor {
+                    node: (current_node.y, current_node.x),
+                    neighbor: (y, x),
+                });
+            }
+        }
+
+        let position = (current_node.x, current_node.y);
+        Ok(current_node
+            .edges(&self.nodes)
+            .into_iter()
+            .filter(|(pos, _)| {
+                // Check if the neighbor is not blocked
+                !self.nodes[pos].is_blocked
+                    && !self.restrictions.blocked.contains(pos)
+                    && !self.restrictions.removed_edges.contains(&(position, *pos))
             })
-            .collect()
+            .map(|(pos, cost)| {
+                let extra = self.restrictions.extra_costs.get(&(position, pos));
+                (pos, cost.saturating_add(extra.copied().unwrap_or(0)))
+            })
+            .collect())
     }
 
     /// Calculates and updates the `g_score` and `f_score` for a neighbor node.
@@ -156,7 +715,8 @@ impl AStar {
     /// # Parameters
     /// - `current_position`: The position of the current node.
     /// - `neighbor_pos`: The position of the neighbor node.
-    /// - `goal`: The goal point as a tuple (x, y).
+    /// - `step_cost`: The cost of moving from the current node to the neighbor node.
+    /// - `target`: The goals of the search.
     ///
     /// # Returns
     /// The calculated `f_score` for the neighbor node.
@@ -164,17 +724,19 @@ impl AStar {
         &mut self,
         current_position: (usize, usize),
         neighbor_pos: (usize, usize),
-        goal: (usize, usize),
+        step_cost: usize,
+        target: &Target,
     ) -> usize {
         // Calculate the tentative g_score for the neighbor
-        let tentative_g_score = self.g_score[&current_position] + 1;
+        let tentative_g_score = self.g_score[&current_position] + step_cost;
 
         // Check if this path to the neighbor is better than any previously recorded path
         if tentative_g_score < *self.g_score.get(&neighbor_pos).unwrap_or(&usize::MAX) {
             // Update the path and scores
             self.came_from.insert(neighbor_pos, current_position);
             self.g_score.insert(neighbor_pos, tentative_g_score);
-            let f_score_value = tentative_g_score + Self::manhattan_distance(neighbor_pos, goal);
+            let f_score_value = tentative_g_score
+                + self.weighted_estimate(neighbor_pos, target, self.heuristic_weight);
             self.f_score.insert(neighbor_pos, f_score_value);
             return f_score_value;
         }
@@ -185,12 +747,12 @@ impl AStar {
     ///
     /// # Parameters
     /// - `current_position`: The position of the current node.
-    /// - `goal`: The goal point as a tuple (x, y).
+    /// - `target`: The goals of the search.
     ///
     /// # Returns
-    /// `true` if the current position is the goal, otherwise `false`.
-    fn is_goal_reached(&self, current_position: (usize, usize), goal: (usize, usize)) -> bool {
-        current_position == goal
+    /// `true` if the current position is a goal, otherwise `false`.
+    fn is_goal_reached(&self, current_position: (usize, usize), target: &Target) -> bool {
+        target.contains(current_position)
     }
 
     /// Processes a neighbor node by calculating its `f_score` and adding it to the open set if necessary.
@@ -198,15 +760,18 @@ impl AStar {
     /// # Parameters
     /// - `current_position`: The position of the current node.
     /// - `neighbor_pos`: The position of the neighbor node.
-    /// - `goal`: The goal point as a tuple (x, y).
+    /// - `step_cost`: The cost of moving from the current node to the neighbor node.
+    /// - `target`: The goals of the search.
     fn process_neighbor(
         &mut self,
         current_position: (usize, usize),
         neighbor_pos: (usize, usize),
-        goal: (usize, usize),
+        step_cost: usize,
+        target: &Target,
     ) {
         // Calculate the f_score for the neighbor
-        let f_score_value = self.calculate_scores(current_position, neighbor_pos, goal);
+        let f_score_value =
+            self.calculate_scores(current_position, neighbor_pos, step_cost, target);
 
         // If the f_score is valid, add the neighbor to the open set
         if f_score_value != usize::MAX {
@@ -217,6 +782,93 @@ impl AStar {
         }
     }
 
+    /// Finds the expanded position closest to any of the goals by the heuristic.
+    ///
+    /// Ties are broken by the lower g-score, so the path to the position is as cheap as possible.
+    /// Without goals, the cheapest expanded position is returned.
+    fn closest_explored(&self, goals: &[(usize, usize)]) -> Option<(usize, usize)> {
+        self.closed_set
+            .iter()
+            .min_by_key(|&&position| {
+                let estimate = goals
+                    .iter()
+                    .map(|&goal| self.heuristic.estimate(position, goal))
+                    .min()
+                    .unwrap_or(0);
+                (estimate, self.g_score[&position], position)
+            })
+            .copied()
+    }
+
+    /// Explains why a finished search did not reach the goal.
+    ///
+    /// Goals given by a predicate are looked up among all passable nodes.
+    ///
+    /// # Parameters
+    /// - `starts`: The starting points as map keys.
+    /// - `target`: The goals of the search.
+    fn unreachable(&self, starts: &[(usize, usize)], target: &Target) -> Unreachable {
+        let goals: Vec<(usize, usize)> = match target {
+            Target::Positions(goals) => goals.to_vec(),
+            Target::Matching(is_goal) => self
+                .nodes
+                .iter()
+                .filter(|&(&position, node)| !node.is_blocked && is_goal(position))
+                .map(|(&position, _)| position)
+                .collect(),
+        };
+
+        let reason = unreachable_reason(&self.nodes, starts, &goals);
+        let (x, y) = self.closest_explored(&goals).unwrap_or(starts[0]);
+
+        Unreachable {
+            reason,
+            closest: (y, x),
+            level: None,
+            explored: self.closed_set.len(),
+        }
+    }
+
+    /// Runs searches with temporary restrictions, which are lifted afterwards.
+    ///
+    /// # Parameters
+    /// - `restrictions`: The changes to apply to the map, handed back unchanged.
+    /// - `searches`: The searches to run with the restrictions.
+    pub(crate) fn with_restrictions<T>(
+        &mut self,
+        restrictions: &mut Restrictions,
+        searches: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        std::mem::swap(&mut self.restrictions, restrictions);
+        let result = searches(self);
+        std::mem::swap(&mut self.restrictions, restrictions);
+        result
+    }
+
+    /// Validates that the configured heuristic can give optimal paths.
+    ///
+    /// # Returns
+    /// A result indicating success or an `AStarError::InvalidConfiguration`.
+    fn validate_configuration(&self) -> Result<(), AStarError> {
+        // NaN fails this check as well
+        if !(self.heuristic_weight >= 1.0 && self.heuristic_weight.is_finite()) {
+            return Err(AStarError::InvalidConfiguration(format!(
+                "the heuristic weight {} must be a finite number of at least 1",
+                self.heuristic_weight
+            )));
+        }
+        if let Heuristic::Octile { straight, diagonal } = self.heuristic {
+            // Outside this range the octile distance overestimates some paths
+            if diagonal < straight || diagonal > straight.saturating_mul(2) {
+                return Err(AStarError::InvalidConfiguration(format!(
+                    "the octile diagonal cost {} must be between the straight cost {} and twice it",
+                    diagonal, straight
+                )));
+            }
+        }
+        Ok(())
+    }
+
     /// Validates that the start and goal nodes exist and are not blocked.
     ///
     /// # Parameters
@@ -230,23 +882,27 @@ impl AStar {
         start: (usize, usize),
         goal: (usize, usize),
     ) -> Result<(), AStarError> {
+        // Nodes are stored with swapped coordinates
+        let start_node = self.nodes.get(&(start.1, start.0));
+        let goal_node = self.nodes.get(&(goal.1, goal.0));
+
         // Check if the start node exists
-        if !self.nodes.contains_key(&start) {
+        if start_node.is_none() {
             return Err(AStarError::NodeNotFound(start));
         }
 
         // Check if the goal node exists
-        if !self.nodes.contains_key(&goal) {
+        if goal_node.is_none() {
             return Err(AStarError::NodeNotFound(goal));
         }
 
         // Check if the start node is blocked
-        if self.nodes.get(&start).map_or(true, |node| node.is_blocked) {
+        if start_node.is_none_or(|node| node.is_blocked) {
             return Err(AStarError::StartNodeBlocked(start));
         }
 
         // Check if the goal node is blocked
-        if self.nodes.get(&goal).map_or(true, |node| node.is_blocked) {
+        if goal_node.is_none_or(|node| node.is_blocked) {
             return Err(AStarError::GoalNodeBlocked(goal));
         }
 
@@ -260,78 +916,904 @@ impl AStar {
     /// - `goal`: The goal point as a tuple (x, y).
     ///
     /// # Returns
-    /// A `Result<Option<Vec<(usize, usize)>>, AStarError>` containing the path from the start to the goal if found,
-    /// or an `AStarError` if no path is found or if an error occurs.
+    /// The `Path` from the start to the goal, or an `AStarError` if no path is found or if an error occurs.
     ///
     /// # Example
     /// ```rust
+    /// use controlled_astar::{AStar, Node};
+    ///
+    /// let nodes = Node::grid_to_nodes(&vec![vec![0; 6]; 6]);
     /// let mut astar = AStar::new(nodes);
-    /// let path = astar.find_shortest_path((0, 0), (5, 5));
+    /// let path = astar.find_shortest_path((0, 0), (5, 5)).unwrap();
+    /// assert_eq!(path.total_cost(), 10);
     /// ```
     pub fn find_shortest_path(
         &mut self,
         start: (usize, usize),
         goal: (usize, usize),
-    ) -> Result<Option<Vec<(usize, usize)>>, AStarError> {
-        // Validate nodes
+    ) -> Result<Path, AStarError> {
+        // Validate the configuration and nodes
+        self.validate_configuration()?;
         self.validate_nodes(start, goal)?;
 
+        let goal = (goal.1, goal.0);
+        let reached = self.search(&[((start.1, start.0), 0)], &Target::Positions(&[goal]))?;
+        Ok(self.reconstruct_path(reached))
+    }
+
+    /// Clears the state of the previous search and prepares the heuristic for the goals.
+    fn reset(&mut self, target: &Target) {
         // Reset the open set and clear previous scores and path information
         self.open_set = PriorityQueue::new(); // Reset the open set
         self.came_from.clear(); // Clear the `came_from` map
         self.g_score.clear(); // Clear the `g_score` map
         self.f_score.clear(); // Clear the `f_score` map
+        self.closed_set.clear(); // Clear the expanded positions
+        self.expansions = 0; // Reset the expansion counter
+        self.shortcut_exit = self.find_shortcut_exit(target); // Bound paths through shortcuts
+    }
 
-        // Initialize scores for the start and goal
-        let (g_score, f_score) = Self::initialize_scores((start.1, start.0), (goal.1, goal.0));
+    /// Runs the A* search from one or more starts until a goal is expanded.
+    ///
+    /// # Parameters
+    /// - `starts`: The starting points as map keys, each with the cost already spent to be there.
+    /// - `target`: The goals of the search.
+    ///
+    /// # Returns
+    /// The goal reached first as a map key, or an `AStarError` if none can be reached.
+    fn search(
+        &mut self,
+        starts: &[((usize, usize), usize)],
+        target: &Target,
+    ) -> Result<(usize, usize), AStarError> {
+        self.start_search(starts, target);
+        match self.next_goal(target)? {
+            Some(goal) => Ok(goal),
+            None => {
+                let starts: Vec<(usize, usize)> = starts.iter().map(|&(start, _)| start).collect();
+                Err(AStarError::PathNotFound(self.unreachable(&starts, target)))
+            }
+        }
+    }
+
+    /// Clears the previous search and puts the starts into the open set.
+    ///
+    /// # Parameters
+    /// - `starts`: The starting points as map keys, each with the cost already spent to be there.
+    /// - `target`: The goals of the search.
+    fn start_search(&mut self, starts: &[((usize, usize), usize)], target: &Target) {
+        self.reset(target);
+
+        // Initialize scores for the starts and goals
+        let (g_score, f_score) = self.initialize_scores(starts, target);
         self.g_score = g_score;
         self.f_score = f_score;
 
-        // Add the start position to the open set
-        self.open_set.push(State {
-            cost: self.f_score[&(start.1, start.0)],
-            position: (start.1, start.0),
-        });
-
-        let mut current_position = (start.1, start.0);
+        // Add the start positions to the open set
+        for &(position, _) in starts {
+            self.open_set.push(State {
+                cost: self.f_score[&position],
+                position,
+            });
+        }
+    }
 
+    /// Expands positions of the open set until a goal is taken from it.
+    ///
+    /// The goal is closed but not expanded, so the search can go on with `expand`.
+    ///
+    /// # Parameters
+    /// - `target`: The goals of the search.
+    ///
+    /// # Returns
+    /// The goal as a map key, `None` once the open set is empty, or an `AStarError`
+    /// if the search limit is exceeded.
+    fn next_goal(&mut self, target: &Target) -> Result<Option<(usize, usize)>, AStarError> {
         // Main loop of the A* algorithm
         while let Some(current_state) = self.open_set.pop() {
-            current_position = current_state.position;
-
-            // Check if the goal has been reached
-            if self.is_goal_reached(current_position, (goal.1, goal.0)) {
-                // Reconstruct and return the path
-                return Ok(Some(Self::reconstruct_path(
-                    self.came_from.clone(),
-                    (start.1, start.0),
-                    (goal.1, goal.0),
-                )));
+            let current_position = current_state.position;
+
+            // Check if a goal has been reached
+            if self.is_goal_reached(current_position, target) {
+                self.closed_set.insert(current_position);
+                return Ok(Some(current_position));
+            }
+            self.expand(current_position, target)?;
+        }
+        Ok(None)
+    }
+
+    /// Closes a position and adds its neighbors to the open set.
+    ///
+    /// # Parameters
+    /// - `position`: The position to expand as a map key.
+    /// - `target`: The goals of the search.
+    ///
+    /// # Returns
+    /// `Ok(())`, or an `AStarError` if the search limit is exceeded.
+    fn expand(&mut self, position: (usize, usize), target: &Target) -> Result<(), AStarError> {
+        // Give up before expanding more nodes than allowed
+        if let Some(limit) = self.max_expansions {
+            if self.expansions >= limit {
+                return Err(AStarError::SearchLimitExceeded {
+                    limit,
+                    position: (position.1, position.0),
+                });
+            }
+        }
+        self.closed_set.insert(position);
+        self.expansions += 1;
+
+        // Process each neighbor of the current node
+        if let Some(current_node) = self.nodes.get(&position) {
+            for (neighbor_pos, step_cost) in self.find_neighbors(current_node)? {
+                self.process_neighbor(position, neighbor_pos, step_cost, target);
+            }
+        }
+        Ok(())
+    }
+
+    /// Finds the shortest path to the goal or, if it cannot be reached, to the closest position.
+    ///
+    /// If the goal is unreachable or the search limit is exceeded, the path leads to the
+    /// explored position closest to the goal by the heuristic, as reported in
+    /// `Unreachable::closest`. Check the last position of the path to tell the cases apart.
+    ///
+    /// # Parameters
+    /// - `start`: The starting point as a tuple (x, y).
+    /// - `goal`: The goal point as a tuple (x, y).
+    ///
+    /// # Returns
+    /// The path, or an `AStarError` for invalid start or goal positions and configurations.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, Node};
+    ///
+    /// let grid = vec![
+    ///     vec![0, 0, 1, 0],
+    ///     vec![0, 0, 1, 0],
+    /// ];
+    /// let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+    /// let path = astar.find_best_effort_path((0, 0), (3, 0)).unwrap();
+    /// assert_eq!(path.positions(), &[(0, 0), (1, 0)]);
+    /// ```
+    pub fn find_best_effort_path(
+        &mut self,
+        start: (usize, usize),
+        goal: (usize, usize),
+    ) -> Result<Path, AStarError> {
+        match self.find_shortest_path(start, goal) {
+            Ok(path) => Ok(path),
+            Err(AStarError::PathNotFound(_)) | Err(AStarError::SearchLimitExceeded { .. }) => {
+                let closest = self
+                    .closest_explored(&[(goal.1, goal.0)])
+                    .unwrap_or((start.1, start.0));
+                Ok(self.reconstruct_path(closest))
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Finds the shortest path from the start to the nearest of several goals in a single search.
+    ///
+    /// The remaining cost is estimated by the closest goal, so the search is as
+    /// focused as for a single goal. Blocked goals are skipped.
+    ///
+    /// # Parameters
+    /// - `start`: The starting point as a tuple (x, y).
+    /// - `goals`: The goal points as tuples (x, y).
+    ///
+    /// # Returns
+    /// The `Path` to the goal that is cheapest to reach, which is `Path::goal`, or an
+    /// `AStarError` if no goal is found. `AStarError::GoalNodeBlocked` is only
+    /// returned if every goal is blocked.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, Node};
+    ///
+    /// let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 8]; 8]));
+    /// let path = astar.find_nearest_goal((3, 3), &[(7, 7), (0, 2), (6, 0)]).unwrap();
+    /// assert_eq!(path.goal(), (0, 2));
+    /// assert_eq!(path.total_cost(), 4);
+    /// ```
+    pub fn find_nearest_goal(
+        &mut self,
+        start: (usize, usize),
+        goals: &[(usize, usize)],
+    ) -> Result<Path, AStarError> {
+        self.validate_configuration()?;
+        let Some(&first) = goals.first() else {
+            return Err(AStarError::InvalidConfiguration(
+                "at least one goal is needed".to_string(),
+            ));
+        };
+        for &goal in goals {
+            self.validate_nodes(start, goal)
+                .or_else(|error| match error {
+                    AStarError::GoalNodeBlocked(_) => Ok(()),
+                    error => Err(error),
+                })?;
+        }
+
+        let goals: Vec<(usize, usize)> = goals
+            .iter()
+            .map(|&(x, y)| (y, x))
+            .filter(|goal| !self.nodes[goal].is_blocked)
+            .collect();
+        if goals.is_empty() {
+            return Err(AStarError::GoalNodeBlocked(first));
+        }
+
+        let reached = self.search(&[((start.1, start.0), 0)], &Target::Positions(&goals))?;
+        Ok(self.reconstruct_path(reached))
+    }
+
+    /// Finds the shortest path from the start to the nearest position accepted by a predicate.
+    ///
+    /// Since the goals are not known in advance, the remaining cost cannot be
+    /// estimated and the search expands positions in order of their cost, like
+    /// Dijkstra's algorithm. Prefer `AStar::find_nearest_goal` when the goals are known.
+    ///
+    /// # Parameters
+    /// - `start`: The starting point as a tuple (x, y).
+    /// - `is_goal`: Tells whether a passable position, given as `(x, y)`, is a goal.
+    ///
+    /// # Returns
+    /// The `Path` to the goal that is cheapest to reach, which is `Path::goal`, or an
+    /// `AStarError` if no goal is found.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, Node};
+    ///
+    /// let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 6]; 6]));
+    ///
+    /// // The nearest position on the border of the map
+    /// let path = astar.find_nearest_matching((1, 4), |(x, y)| x == 5 || y == 5).unwrap();
+    /// assert_eq!(path.goal(), (1, 5));
+    /// assert_eq!(path.total_cost(), 1);
+    /// ```
+    pub fn find_nearest_matching<F>(
+        &mut self,
+        start: (usize, usize),
+        is_goal: F,
+    ) -> Result<Path, AStarError>
+    where
+        F: Fn((usize, usize)) -> bool,
+    {
+        self.validate_configuration()?;
+        self.validate_nodes(start, start)?;
+
+        let is_goal = |(y, x)| is_goal((x, y));
+        let reached = self.search(&[((start.1, start.0), 0)], &Target::Matching(&is_goal))?;
+        Ok(self.reconstruct_path(reached))
+    }
+
+    /// Finds the shortest paths from the start to each of several goals in a single search.
+    ///
+    /// The search goes on past every goal it expands until all goals are expanded,
+    /// nothing is left to expand or the expansion limit is hit.
+    ///
+    /// # Parameters
+    /// - `start`: The starting point as a tuple (x, y).
+    /// - `goals`: The goal points as tuples (x, y).
+    ///
+    /// # Returns
+    /// The path to every goal in the order of `goals`, `None` where the search did not
+    /// reach it, for example because it is blocked or the expansion limit was hit.
+    pub(crate) fn find_paths_to_goals(
+        &mut self,
+        start: (usize, usize),
+        goals: &[(usize, usize)],
+    ) -> Vec<Option<Path>> {
+        let mut paths = vec![None; goals.len()];
+        if self.validate_configuration().is_err() || self.validate_nodes(start, start).is_err() {
+            return paths;
+        }
+
+        let keys: Vec<(usize, usize)> = goals.iter().map(|&(x, y)| (y, x)).collect();
+        let mut remaining: HashSet<(usize, usize)> = keys
+            .iter()
+            .copied()
+            .filter(|key| self.nodes.get(key).is_some_and(|node| !node.is_blocked))
+            .collect();
+
+        // The costs of expanded goals are final, the remaining goals are searched on from them
+        let target = Target::Positions(&keys);
+        self.start_search(&[((start.1, start.0), 0)], &target);
+        while !remaining.is_empty() {
+            let Ok(Some(goal)) = self.next_goal(&target) else {
+                break;
+            };
+            remaining.remove(&goal);
+            if !remaining.is_empty() && self.expand(goal, &target).is_err() {
+                break;
+            }
+        }
+
+        for (path, &key) in paths.iter_mut().zip(&keys) {
+            if self.closed_set.contains(&key) {
+                *path = Some(self.reconstruct_path(key));
+            }
+        }
+        paths
+    }
+
+    /// Finds the shortest path to the goal from whichever of several starts reaches it most cheaply.
+    ///
+    /// All starts are searched at once, so this costs about as much as a single
+    /// search. Blocked starts are skipped.
+    ///
+    /// # Parameters
+    /// - `starts`: The starting points as tuples (x, y).
+    /// - `goal`: The goal point as a tuple (x, y).
+    ///
+    /// # Returns
+    /// The `Path` from the winning start, which is `Path::start`, or an `AStarError`
+    /// if no start reaches the goal. `AStarError::StartNodeBlocked` is only returned
+    /// if every start is blocked.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, Node};
+    ///
+    /// let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 8]; 8]));
+    /// let path = astar.find_nearest_start(&[(0, 0), (7, 7), (5, 1)], (3, 3)).unwrap();
+    /// assert_eq!(path.start(), (5, 1));
+    /// assert_eq!(path.total_cost(), 4);
+    /// ```
+    pub fn find_nearest_start(
+        &mut self,
+        starts: &[(usize, usize)],
+        goal: (usize, usize),
+    ) -> Result<Path, AStarError> {
+        let starts: Vec<((usize, usize), usize)> = starts.iter().map(|&start| (start, 0)).collect();
+        self.find_nearest_start_with_costs(&starts, goal)
+    }
+
+    /// Finds the cheapest path to the goal from several starts that each have a cost already spent.
+    ///
+    /// The initial cost of a start is added before comparing, for example to account
+    /// for a unit that needs time to get ready. A path from a start can also pass
+    /// through another start, if that start's initial cost is higher than the cost
+    /// of walking there.
+    ///
+    /// # Parameters
+    /// - `starts`: The starting points as tuples (x, y), each with its initial cost.
+    /// - `goal`: The goal point as a tuple (x, y).
+    ///
+    /// # Returns
+    /// The `Path` from the winning start, which is `Path::start`, or an `AStarError`
+    /// if no start reaches the goal. The cost of the path does not include the
+    /// initial cost of its start.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, Node};
+    ///
+    /// let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 8]; 8]));
+    /// let path = astar
+    ///     .find_nearest_start_with_costs(&[((5, 1), 3), ((0, 0), 0)], (3, 3))
+    ///     .unwrap();
+    /// assert_eq!(path.start(), (0, 0));
+    /// assert_eq!(path.total_cost(), 6);
+    /// ```
+    pub fn find_nearest_start_with_costs(
+        &mut self,
+        starts: &[((usize, usize), usize)],
+        goal: (usize, usize),
+    ) -> Result<Path, AStarError> {
+        self.validate_configuration()?;
+        let Some(&(first, _)) = starts.first() else {
+            return Err(AStarError::InvalidConfiguration(
+                "at least one start is needed".to_string(),
+            ));
+        };
+
+        let mut seeds = Vec::new();
+        for &(start, initial_cost) in starts {
+            let node = self
+                .nodes
+                .get(&(start.1, start.0))
+                .ok_or(AStarError::NodeNotFound(start))?;
+            if !node.is_blocked {
+                seeds.push(((start.1, start.0), initial_cost));
+            }
+        }
+        match self.nodes.get(&(goal.1, goal.0)) {
+            None => return Err(AStarError::NodeNotFound(goal)),
+            Some(node) if node.is_blocked => return Err(AStarError::GoalNodeBlocked(goal)),
+            Some(_) => {}
+        }
+        if seeds.is_empty() {
+            return Err(AStarError::StartNodeBlocked(first));
+        }
+
+        let reached = self.search(&seeds, &Target::Positions(&[(goal.1, goal.0)]))?;
+        Ok(self.reconstruct_path(reached))
+    }
+
+    /// Finds a path quickly and keeps improving it within a time budget (ARA*).
+    ///
+    /// The first search uses a heavily weighted heuristic and returns a path
+    /// quickly. Every following search lowers the weight and reuses the work of the
+    /// previous ones, until the path is known to be the shortest or the time budget
+    /// runs out. The first search always runs to completion, only the improvements
+    /// are limited by the budget. The weight set by `AStar::set_heuristic_weight`
+    /// is not used.
+    ///
+    /// # Parameters
+    /// - `start`: The starting point as a tuple (x, y).
+    /// - `goal`: The goal point as a tuple (x, y).
+    /// - `options`: The weights to use and the time budget.
+    ///
+    /// # Returns
+    /// The best path found with its suboptimality bound, or an `AStarError` if no path is found.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, AnytimeOptions, Node};
+    ///
+    /// let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 20]; 20]));
+    /// let result = astar
+    ///     .find_anytime_path((0, 0), (19, 19), &AnytimeOptions::default())
+    ///     .unwrap();
+    /// assert!(result.path.total_cost() as f64 <= 38.0 * result.bound);
+    /// ```
+    pub fn find_anytime_path(
+        &mut self,
+        start: (usize, usize),
+        goal: (usize, usize),
+        options: &AnytimeOptions,
+    ) -> Result<AnytimePath, AStarError> {
+        self.validate_configuration()?;
+        self.validate_nodes(start, goal)?;
+        let valid_weights = options.initial_weight >= 1.0
+            && options.initial_weight.is_finite()
+            && options.weight_step > 0.0;
+        if !valid_weights {
+            return Err(AStarError::InvalidConfiguration(format!(
+                "the initial weight {} must be finite and at least 1, and the weight step {} positive",
+                options.initial_weight, options.weight_step
+            )));
+        }
+
+        let (start, goal) = ((start.1, start.0), (goal.1, goal.0));
+        let goals = [goal];
+        let target = Target::Positions(&goals);
+        self.reset(&target);
+
+        let mut weight = options.initial_weight;
+        let mut inconsistent = HashSet::new();
+        self.g_score.insert(start, 0);
+        self.push_weighted(start, weight, &target);
+
+        let mut best: Option<(Path, f64)> = None;
+        let mut deadline = None;
+        let mut searches = 0;
+        loop {
+            match self.improve_path(goal, weight, &target, &mut inconsistent, deadline) {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(AStarError::SearchLimitExceeded { .. }) if best.is_some() => break,
+                Err(error) => return Err(error),
+            }
+            searches += 1;
+
+            let Some(&goal_cost) = self.g_score.get(&goal) else {
+                return Err(AStarError::PathNotFound(
+                    self.unreachable(&[start], &target),
+                ));
+            };
+            let lowest = self
+                .open_set
+                .iter()
+                .map(|state| state.position)
+                .filter(|position| !self.closed_set.contains(position))
+                .chain(inconsistent.iter().copied())
+                .map(|position| self.g_score[&position] + self.estimate(position, &target))
+                .min();
+            let bound = match lowest {
+                Some(lowest) if lowest < goal_cost => weight.min(goal_cost as f64 / lowest as f64),
+                _ => 1.0,
+            };
+            best = Some((self.reconstruct_path(goal), bound));
+
+            // The budget starts once the first path is found
+            let ends_at = *deadline.get_or_insert_with(|| Instant::now() + options.time_budget);
+            if bound <= 1.0 || Instant::now() >= ends_at {
+                break;
+            }
+
+            // Search again with a lower weight, starting from the inconsistent positions
+            weight = (weight - options.weight_step).max(1.0);
+            let open: HashSet<(usize, usize)> = self
+                .open_set
+                .iter()
+                .map(|state| state.position)
+                .filter(|position| !self.closed_set.contains(position))
+                .chain(inconsistent.drain())
+                .collect();
+            self.open_set = PriorityQueue::new();
+            self.closed_set.clear();
+            for position in open {
+                self.push_weighted(position, weight, &target);
+            }
+        }
+
+        let (path, bound) = best.expect("the first search either finds a path or fails");
+        Ok(AnytimePath {
+            path,
+            bound,
+            searches,
+        })
+    }
+
+    /// Adds a position to the open set, scored with an inflated heuristic.
+    fn push_weighted(&mut self, position: (usize, usize), weight: f64, target: &Target) {
+        let cost = self.g_score[&position] + self.weighted_estimate(position, target, weight);
+        self.f_score.insert(position, cost);
+        self.open_set.push(State { cost, position });
+    }
+
+    /// Expands positions until no open position can lead to a cheaper path to the goal.
+    ///
+    /// Positions improved after being expanded are collected in `inconsistent`
+    /// instead of being expanded again, as in ARA*.
+    ///
+    /// # Returns
+    /// `true` if the search completed, `false` if it stopped at the deadline.
+    fn improve_path(
+        &mut self,
+        goal: (usize, usize),
+        weight: f64,
+        target: &Target,
+        inconsistent: &mut HashSet<(usize, usize)>,
+        deadline: Option<Instant>,
+    ) -> Result<bool, AStarError> {
+        while let Some(state) = self.open_set.pop() {
+            let position = state.position;
+            // Skip entries replaced by a better score or already expanded
+            if self.closed_set.contains(&position) || state.cost != self.f_score[&position] {
+                continue;
+            }
+            if self
+                .g_score
+                .get(&goal)
+                .is_some_and(|&cost| cost <= state.cost)
+            {
+                self.open_set.push(state);
+                return Ok(true);
+            }
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                self.open_set.push(state);
+                return Ok(false);
+            }
+
+            if let Some(limit) = self.max_expansions {
+                if self.expansions >= limit {
+                    return Err(AStarError::SearchLimitExceeded {
+                        limit,
+                        position: (position.1, position.0),
+                    });
+                }
+            }
+            self.closed_set.insert(position);
+            self.expansions += 1;
+
+            let current_node = &self.nodes[&position];
+            for (neighbor, step_cost) in self.find_neighbors(current_node)? {
+                let tentative = self.g_score[&position] + step_cost;
+                if tentative < *self.g_score.get(&neighbor).unwrap_or(&usize::MAX) {
+                    self.came_from.insert(neighbor, position);
+                    self.g_score.insert(neighbor, tentative);
+                    if self.closed_set.contains(&neighbor) {
+                        inconsistent.insert(neighbor);
+                    } else {
+                        self.push_weighted(neighbor, weight, target);
+                    }
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Finds the shortest path with iterative-deepening A* (IDA*), using little memory.
+    ///
+    /// Instead of score tables for every reached position, IDA* runs depth-first
+    /// searches bounded by increasing cost limits, so memory grows with the length of
+    /// the path only. Positions may be searched many times; an optional
+    /// transposition table of bounded size avoids part of this. The configured
+    /// heuristic, heuristic weight and expansion limit are used as for
+    /// `AStar::find_shortest_path`, and the introspection methods such as
+    /// `AStar::g_scores` return nothing after the search.
+    ///
+    /// In the worst case the search time is exponential in the path length: every
+    /// route within the cost bound is followed, and the table only prunes the
+    /// positions it has room for. Keep a transposition table at least as large as the
+    /// area the path crosses, or use `AStar::find_shortest_path` on large open maps.
+    ///
+    /// No structure the size of the map is built, so an unreachable goal is only
+    /// noticed once every route from the start has been followed. Pass a
+    /// `ComponentIndex` in `IdaOptions::components` to report it before searching, or
+    /// bound the search with `AStar::set_max_expansions`. Without an index, the
+    /// report has the reason `UnreachableReason::Unknown` and `Unreachable::explored`
+    /// counts every expansion of every iteration.
+    ///
+    /// # Parameters
+    /// - `start`: The starting point as a tuple (x, y).
+    /// - `goal`: The goal point as a tuple (x, y).
+    /// - `options`: The size of the transposition table and the optional component index.
+    ///
+    /// # Returns
+    /// The `Path` from the start to the goal, or an `AStarError` if no path is found.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, IdaOptions, Node};
+    ///
+    /// let grid = vec![
+    ///     vec![0, 0, 0, 0],
+    ///     vec![1, 1, 1, 0],
+    ///     vec![0, 0, 0, 0],
+    /// ];
+    /// let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+    /// let options = IdaOptions {
+    ///     transposition_table: Some(64),
+    ///     ..IdaOptions::default()
+    /// };
+    /// let path = astar.find_path_ida((0, 0), (0, 2), &options).unwrap();
+    /// assert_eq!(path.total_cost(), 8);
+    /// ```
+    pub fn find_path_ida(
+        &mut self,
+        start: (usize, usize),
+        goal: (usize, usize),
+        options: &IdaOptions<'_>,
+    ) -> Result<Path, AStarError> {
+        self.validate_configuration()?;
+        self.validate_nodes(start, goal)?;
+
+        let (start_key, goal_key) = ((start.1, start.0), (goal.1, goal.0));
+        let goals = [goal_key];
+        let target = Target::Positions(&goals);
+        self.reset(&target);
+
+        if let Some(components) = options.components {
+            if !components.is_reachable(start, goal) {
+                let reason = if components.is_weakly_connected(start, goal) {
+                    UnreachableReason::OneWayEdges
+                } else {
+                    UnreachableReason::DisconnectedComponents
+                };
+                return Err(AStarError::PathNotFound(Unreachable {
+                    reason,
+                    closest: start,
+                    level: None,
+                    explored: 0,
+                }));
             }
+        }
 
-            // Process each neighbor of the current node
-            if let Some(current_node) = self.nodes.get(&current_position) {
-                for neighbor_pos in self.find_neighbors(current_node) {
-                    self.process_neighbor(current_position, neighbor_pos, (goal.1, goal.0));
+        let mut table = TranspositionTable {
+            entries: HashMap::new(),
+            capacity: options.transposition_table.unwrap_or(0),
+        };
+        let mut bound = self.weighted_estimate(start_key, &target, self.heuristic_weight);
+        let mut progress = IdaProgress {
+            expansions: 0,
+            closest: (start_key, bound),
+        };
+        loop {
+            table.entries.clear();
+            let outcome = self.ida_iteration(
+                (start_key, goal_key),
+                bound,
+                &target,
+                &mut table,
+                &mut progress,
+            )?;
+            match outcome {
+                IdaOutcome::Found(positions) => {
+                    return Ok(Path::from_positions(&self.nodes, positions)
+                        .expect("the search only follows existing edges"));
                 }
+                IdaOutcome::Exceeded(usize::MAX) => break,
+                IdaOutcome::Exceeded(next_bound) => bound = next_bound,
             }
         }
 
-        Err(AStarError::PathNotFound(current_position))
+        // Every route from the start was followed without reaching the goal
+        let (closest, _) = progress.closest;
+        Err(AStarError::PathNotFound(Unreachable {
+            reason: UnreachableReason::Unknown,
+            closest: (closest.1, closest.0),
+            level: None,
+            explored: progress.expansions,
+        }))
+    }
+
+    /// Runs one depth-first iteration of IDA* within a cost bound.
+    ///
+    /// # Parameters
+    /// - `(start, goal)`: The starting and goal points as map keys.
+    /// - `bound`: The highest estimated path cost to search.
+    /// - `target`: The goals of the search.
+    /// - `table`: The transposition table of this iteration.
+    /// - `progress`: What the previous iterations explored, updated by this one.
+    fn ida_iteration(
+        &self,
+        (start, goal): ((usize, usize), (usize, usize)),
+        bound: usize,
+        target: &Target,
+        table: &mut TranspositionTable,
+        progress: &mut IdaProgress,
+    ) -> Result<IdaOutcome, AStarError> {
+        if start == goal {
+            return Ok(IdaOutcome::Found(vec![(start.1, start.0)]));
+        }
+
+        let mut next_bound = usize::MAX;
+        let mut on_branch = HashSet::from([start]);
+        let mut branch = vec![IdaFrame {
+            position: start,
+            g_score: 0,
+            steps: self.find_neighbors(&self.nodes[&start])?,
+            next: 0,
+        }];
+
+        while let Some(frame) = branch.last_mut() {
+            let Some(&(position, step_cost)) = frame.steps.get(frame.next) else {
+                on_branch.remove(&frame.position);
+                branch.pop();
+                continue;
+            };
+            frame.next += 1;
+            if on_branch.contains(&position) {
+                continue;
+            }
+
+            let g_score = frame.g_score + step_cost;
+            let estimate = self.weighted_estimate(position, target, self.heuristic_weight);
+            let f_score = g_score + estimate;
+            if f_score > bound {
+                next_bound = next_bound.min(f_score);
+                continue;
+            }
+            if position == goal {
+                let mut positions: Vec<(usize, usize)> = branch
+                    .iter()
+                    .map(|frame| (frame.position.1, frame.position.0))
+                    .collect();
+                positions.push((goal.1, goal.0));
+                return Ok(IdaOutcome::Found(positions));
+            }
+
+            // Skip positions already searched from a cost at least as low
+            if table.is_known(position, g_score) {
+                continue;
+            }
+
+            progress.expansions += 1;
+            if let Some(limit) = self.max_expansions {
+                if progress.expansions > limit {
+                    return Err(AStarError::SearchLimitExceeded {
+                        limit,
+                        position: (position.1, position.0),
+                    });
+                }
+            }
+            if estimate < progress.closest.1 {
+                progress.closest = (position, estimate);
+            }
+
+            on_branch.insert(position);
+            branch.push(IdaFrame {
+                position,
+                g_score,
+                steps: self.find_neighbors(&self.nodes[&position])?,
+                next: 0,
+            });
+        }
+
+        Ok(IdaOutcome::Exceeded(next_bound))
     }
 }
 
+/// Explains why no path leads from the starts to the goals of a node map.
+///
+/// # Parameters
+/// - `nodes`: The node map.
+/// - `starts`: The starting points as map keys.
+/// - `goals`: The goals as map keys.
+///
+/// # Returns
+/// `UnreachableReason::OneWayEdges` if the goals could be reached with every edge
+/// followed in both directions, `UnreachableReason::DisconnectedComponents` if not.
+pub(crate) fn unreachable_reason(
+    nodes: &HashMap<(usize, usize), Node>,
+    starts: &[(usize, usize)],
+    goals: &[(usize, usize)],
+) -> UnreachableReason {
+    let passable = nodes
+        .iter()
+        .filter(|(_, node)| !node.is_blocked)
+        .map(|(&position, _)| position);
+    let successors = |position| {
+        nodes[&position]
+            .edges(nodes)
+            .into_iter()
+            .filter(|(target, _)| !nodes[target].is_blocked)
+            .map(|(target, _)| target)
+            .collect()
+    };
+
+    if weakly_connected(starts, goals, passable, successors) {
+        UnreachableReason::OneWayEdges
+    } else {
+        UnreachableReason::DisconnectedComponents
+    }
+}
+
+/// Checks whether positions are connected when edges may be followed in both directions.
+///
+/// # Parameters
+/// - `starts`: The positions to start from.
+/// - `goals`: The positions to reach.
+/// - `positions`: All positions that may be visited.
+/// - `successors`: The positions reachable in one step from a position.
+///
+/// # Returns
+/// `true` if any goal lies in the same weakly connected component as any start.
+pub(crate) fn weakly_connected<P, I, F>(
+    starts: &[P],
+    goals: &[P],
+    positions: I,
+    successors: F,
+) -> bool
+where
+    P: Copy + Eq + Hash,
+    I: IntoIterator<Item = P>,
+    F: Fn(P) -> Vec<P>,
+{
+    let mut adjacent: HashMap<P, Vec<P>> = HashMap::new();
+    for position in positions {
+        for target in successors(position) {
+            adjacent.entry(position).or_default().push(target);
+            adjacent.entry(target).or_default().push(position);
+        }
+    }
+
+    let mut visited: HashSet<P> = starts.iter().copied().collect();
+    let mut queue: VecDeque<P> = starts.iter().copied().collect();
+    while let Some(position) = queue.pop_front() {
+        if goals.contains(&position) {
+            return true;
+        }
+        for &next in adjacent.get(&position).into_iter().flatten() {
+            if visited.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    false
+}
+
 impl fmt::Display for AStarError {
     /// Formats the error for display in user-facing contexts.
     ///
     /// # Example
     /// ```rust
+    /// use controlled_astar::AStarError;
+    ///
     /// let error = AStarError::StartNodeBlocked((0, 0));
     /// println!("{}", error);
     /// // Output: The start node at position (0, 0) is blocked!
     /// ```
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match *self {
+        match self {
             AStarError::StartNodeBlocked(coord) => {
                 write!(f, "The start node at position {:?} is blocked!", coord)
             }
@@ -341,22 +1823,26 @@ impl fmt::Display for AStarError {
             AStarError::NodeNotFound(coord) => {
                 write!(f, "The node at position {:?} was not found!", coord)
             }
-            AStarError::PathNotFound(coord) => {
-                write!(f, "Path not found! Last checked position was {:?}.", coord)
+            AStarError::PathNotFound(unreachable) => write!(
+                f,
+                "Path not found! {}; {} positions were explored and the closest to the goal is {:?}.",
+                unreachable.reason, unreachable.explored, unreachable.closest
+            ),
+            AStarError::InvalidNeighbor { node, neighbor } => write!(
+                f,
+                "The node at position {:?} links to {:?}, which is not in the map!",
+                node, neighbor
+            ),
+            AStarError::InvalidConfiguration(reason) => {
+                write!(f, "Invalid configuration: {}!", reason)
             }
+            AStarError::SearchLimitExceeded { limit, position } => write!(
+                f,
+                "Search limit of {} expanded nodes exceeded! Last checked position was {:?}.",
+                limit, position
+            ),
         }
     }
 }
-impl fmt::Debug for AStarError {
-    /// Formats the error for debugging purposes.
-    ///
-    /// # Example
-    /// ```rust
-    /// let error = AStarError::GoalNodeBlocked((5, 5));
-    /// println!("{:?}", error);
-    /// // Output: GoalNodeBlocked((5, 5))
-    /// ```
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self)
-    }
-}
+
+impl Error for AStarError {}
@@ -0,0 +1,450 @@
+use crate::node::Node;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+/// Strongly connected components of a node map, for reachability queries without a search.
+///
+/// Two positions are in the same component if each can be reached from the
+/// other. Because neighbors set with `Node::set_neighbor` and custom edges are
+/// directed, a position can also reach positions of other components; the index
+/// keeps the reachability between components so `ComponentIndex::is_reachable`
+/// answers in constant time.
+///
+/// Blocked nodes belong to no component. Positions are given as `(x, y)`, like
+/// for `AStar::find_shortest_path`. The index does not follow later changes to
+/// `Node::neighbors` or custom edges; create a new index after changing them.
+///
+/// The reachability between components is kept as one bit per pair of components,
+/// so the index needs memory quadratic in the number of components: about 32 MB for
+/// 16 000 components. Maps split into many small regions, such as mazes of one-way
+/// links, are better served by searching.
+#[derive(Debug, Clone)]
+pub struct ComponentIndex {
+    /// Component of every passable node, by map key.
+    component_of: HashMap<(usize, usize), usize>,
+    /// Map keys of the members of every component. Ids of split or merged
+    /// components are reused, so some entries may be empty.
+    members: Vec<Vec<(usize, usize)>>,
+    /// Targets of the edges of every node, blocked or not.
+    successors: HashMap<(usize, usize), Vec<(usize, usize)>>,
+    /// Sources of the edges into every node, blocked or not.
+    predecessors: HashMap<(usize, usize), Vec<(usize, usize)>>,
+    /// Blocked state of every node, by map key.
+    blocked: HashMap<(usize, usize), bool>,
+    /// For every component, a bit set of the components it can reach, itself included.
+    reachable: Vec<Vec<u64>>,
+}
+
+impl ComponentIndex {
+    /// Computes the components of a node map.
+    ///
+    /// # Parameters
+    /// - `nodes`: The node map.
+    ///
+    /// # Returns
+    /// A new `ComponentIndex`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{ComponentIndex, Node};
+    ///
+    /// let grid = vec![
+    ///     vec![0, 1, 0],
+    ///     vec![0, 1, 0],
+    /// ];
+    /// let index = ComponentIndex::new(&Node::grid_to_nodes(&grid));
+    /// assert!(index.is_reachable((0, 0), (0, 1)));
+    /// assert!(!index.is_reachable((0, 0), (2, 0)));
+    /// assert_eq!(index.region_count(), 2);
+    /// ```
+    pub fn new(nodes: &HashMap<(usize, usize), Node>) -> Self {
+        let mut successors: HashMap<(usize, usize), Vec<(usize, usize)>> = HashMap::new();
+        let mut predecessors: HashMap<(usize, usize), Vec<(usize, usize)>> = HashMap::new();
+        for (&position, node) in nodes {
+            for (target, _) in node.edges(nodes) {
+                successors.entry(position).or_default().push(target);
+                predecessors.entry(target).or_default().push(position);
+            }
+        }
+
+        let mut index = ComponentIndex {
+            component_of: HashMap::new(),
+            members: Vec::new(),
+            successors,
+            predecessors,
+            blocked: nodes
+                .iter()
+                .map(|(&position, node)| (position, node.is_blocked))
+                .collect(),
+            reachable: Vec::new(),
+        };
+
+        let mut positions: Vec<_> = nodes.keys().copied().collect();
+        positions.sort();
+        let components: HashSet<usize> = index
+            .strongly_connected(&positions)
+            .into_iter()
+            .map(|component| index.add_component(component))
+            .collect();
+        index.update_reachability(&components);
+        index
+    }
+
+    /// Checks whether `to` can be reached from `from`.
+    ///
+    /// # Parameters
+    /// - `from`: The starting position as `(x, y)`.
+    /// - `to`: The goal position as `(x, y)`.
+    ///
+    /// # Returns
+    /// `true` if a path exists, `false` if not or if either position is blocked or not in the map.
+    pub fn is_reachable(&self, from: (usize, usize), to: (usize, usize)) -> bool {
+        match (self.region(from), self.region(to)) {
+            (Some(a), Some(b)) => self.reachable[a][b / 64] & (1 << (b % 64)) != 0,
+            _ => false,
+        }
+    }
+
+    /// Checks whether two positions are connected if edges are followed in both directions.
+    ///
+    /// # Parameters
+    /// - `from`: The first position as `(x, y)`.
+    /// - `to`: The second position as `(x, y)`.
+    ///
+    /// # Returns
+    /// `true` if a chain of edges connects the positions, ignoring their direction.
+    pub(crate) fn is_weakly_connected(&self, from: (usize, usize), to: (usize, usize)) -> bool {
+        let (Some(start), Some(goal)) = (self.region(from), self.region(to)) else {
+            return false;
+        };
+
+        // Reachability is transitive, so components one reaches are its neighbors
+        let mut visited = vec![false; self.members.len()];
+        visited[start] = true;
+        let mut pending = vec![start];
+        while let Some(component) = pending.pop() {
+            if component == goal {
+                return true;
+            }
+            let neighbors = self
+                .components_reaching(component)
+                .into_iter()
+                .chain(self.components_reached(component));
+            for neighbor in neighbors {
+                if !visited[neighbor] && !self.members[neighbor].is_empty() {
+                    visited[neighbor] = true;
+                    pending.push(neighbor);
+                }
+            }
+        }
+        false
+    }
+
+    /// Returns the id of the component containing a position.
+    ///
+    /// Ids are small integers that can be used to color regions. They stay the same
+    /// for components not touched by `ComponentIndex::set_blocked`.
+    ///
+    /// # Parameters
+    /// - `position`: The position as `(x, y)`.
+    ///
+    /// # Returns
+    /// The component id, or `None` if the position is blocked or not in the map.
+    pub fn region(&self, position: (usize, usize)) -> Option<usize> {
+        self.component_of.get(&(position.1, position.0)).copied()
+    }
+
+    /// Returns the component id of every passable position, keyed by `(x, y)`.
+    ///
+    /// The map can be drawn with `TextRenderer::set_scores`.
+    pub fn regions(&self) -> HashMap<(usize, usize), usize> {
+        self.component_of
+            .iter()
+            .map(|(&(row, col), &component)| ((col, row), component))
+            .collect()
+    }
+
+    /// Returns the number of components.
+    pub fn region_count(&self) -> usize {
+        self.members
+            .iter()
+            .filter(|members| !members.is_empty())
+            .count()
+    }
+
+    /// Blocks or unblocks a node and updates the components around it.
+    ///
+    /// Only the component of the node is recomputed when it is blocked, and only
+    /// the components merged through it when it is unblocked. The reachability is
+    /// then updated for these components and the components that can reach them;
+    /// on maps whose edges all go both ways, no other component reaches them.
+    ///
+    /// # Parameters
+    /// - `nodes`: The node map the index was created from.
+    /// - `position`: The position of the node as `(x, y)`.
+    /// - `blocked`: The new blocked state.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{ComponentIndex, Node};
+    ///
+    /// let mut nodes = Node::grid_to_nodes(&vec![vec![0, 0, 0]]);
+    /// let mut index = ComponentIndex::new(&nodes);
+    ///
+    /// index.set_blocked(&mut nodes, (1, 0), true);
+    /// assert!(!index.is_reachable((0, 0), (2, 0)));
+    /// assert!(nodes[&(0, 1)].is_blocked);
+    ///
+    /// index.set_blocked(&mut nodes, (1, 0), false);
+    /// assert!(index.is_reachable((0, 0), (2, 0)));
+    /// ```
+    pub fn set_blocked(
+        &mut self,
+        nodes: &mut HashMap<(usize, usize), Node>,
+        position: (usize, usize),
+        blocked: bool,
+    ) {
+        let key = (position.1, position.0);
+        let Some(node) = nodes.get_mut(&key) else {
+            return;
+        };
+        node.set_blocked(blocked);
+        if self.blocked.insert(key, blocked) == Some(blocked) {
+            return;
+        }
+
+        let mut changed: HashSet<usize>;
+        if blocked {
+            // Removing a node can only split its own component
+            let Some(component) = self.component_of.remove(&key) else {
+                return;
+            };
+            changed = self.components_reaching(component).into_iter().collect();
+            changed.remove(&component);
+            self.reachable[component].fill(0);
+
+            let mut remaining = std::mem::take(&mut self.members[component]);
+            remaining.retain(|&member| member != key);
+            for &member in &remaining {
+                self.component_of.remove(&member);
+            }
+            for part in self.strongly_connected(&remaining) {
+                changed.insert(self.add_component(part));
+            }
+        } else {
+            // The node joins every component that can both reach it and be reached from it
+            let reaching: HashSet<usize> = self
+                .neighbor_components(self.predecessors.get(&key))
+                .flat_map(|component| self.components_reaching(component))
+                .collect();
+            let reached: HashSet<usize> = self
+                .neighbor_components(self.successors.get(&key))
+                .flat_map(|component| self.components_reached(component))
+                .collect();
+
+            let mut merged = vec![key];
+            for &component in reaching.intersection(&reached) {
+                merged.append(&mut self.members[component]);
+                self.reachable[component].fill(0);
+            }
+            changed = reaching.difference(&reached).copied().collect();
+            changed.insert(self.add_component(merged));
+        }
+
+        // Only the changed components and those reaching them can have set the bits
+        // of the removed ids, so every other row stays valid
+        self.update_reachability(&changed);
+    }
+
+    /// Returns the components of the passable nodes among `positions`.
+    fn strongly_connected(&self, positions: &[(usize, usize)]) -> Vec<Vec<(usize, usize)>> {
+        let allowed: HashSet<_> = positions
+            .iter()
+            .copied()
+            .filter(|position| !self.is_blocked(*position))
+            .collect();
+        let successors = |position: (usize, usize)| -> Vec<(usize, usize)> {
+            self.successors
+                .get(&position)
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|target| allowed.contains(target))
+                .collect()
+        };
+
+        // Iterative Tarjan: every frame holds a node, its successors and the next one to visit
+        let mut counter = 0;
+        let mut indices: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut low_links: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut stack = Vec::new();
+        let mut on_stack = HashSet::new();
+        let mut components = Vec::new();
+
+        for &root in positions
+            .iter()
+            .filter(|position| allowed.contains(position))
+        {
+            if indices.contains_key(&root) {
+                continue;
+            }
+
+            let mut frames = vec![(root, successors(root), 0)];
+            indices.insert(root, counter);
+            low_links.insert(root, counter);
+            counter += 1;
+            stack.push(root);
+            on_stack.insert(root);
+
+            while let Some((node, targets, next)) = frames.last_mut() {
+                let node = *node;
+                if let Some(&target) = targets.get(*next) {
+                    *next += 1;
+                    if let Entry::Vacant(entry) = indices.entry(target) {
+                        entry.insert(counter);
+                        low_links.insert(target, counter);
+                        counter += 1;
+                        stack.push(target);
+                        on_stack.insert(target);
+                        frames.push((target, successors(target), 0));
+                    } else if on_stack.contains(&target) {
+                        let low = low_links[&node].min(indices[&target]);
+                        low_links.insert(node, low);
+                    }
+                    continue;
+                }
+
+                frames.pop();
+                if let Some((parent, _, _)) = frames.last() {
+                    let low = low_links[parent].min(low_links[&node]);
+                    low_links.insert(*parent, low);
+                }
+                if low_links[&node] == indices[&node] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack.remove(&member);
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+
+        components
+    }
+
+    /// Stores a new component under a free id and returns the id.
+    fn add_component(&mut self, members: Vec<(usize, usize)>) -> usize {
+        let id = self
+            .members
+            .iter()
+            .position(|members| members.is_empty())
+            .unwrap_or_else(|| {
+                self.members.push(Vec::new());
+                self.members.len() - 1
+            });
+        for &member in &members {
+            self.component_of.insert(member, id);
+        }
+        self.members[id] = members;
+        id
+    }
+
+    /// Returns whether the node at a map key is blocked or missing.
+    fn is_blocked(&self, position: (usize, usize)) -> bool {
+        self.blocked.get(&position).copied().unwrap_or(true)
+    }
+
+    /// Returns the components of the given neighbors.
+    fn neighbor_components<'a>(
+        &'a self,
+        neighbors: Option<&'a Vec<(usize, usize)>>,
+    ) -> impl Iterator<Item = usize> + 'a {
+        neighbors
+            .into_iter()
+            .flatten()
+            .filter_map(|neighbor| self.component_of.get(neighbor).copied())
+    }
+
+    /// Returns the components that can reach `component`, itself included.
+    fn components_reaching(&self, component: usize) -> Vec<usize> {
+        (0..self.members.len())
+            .filter(|&other| self.reachable[other][component / 64] & (1 << (component % 64)) != 0)
+            .collect()
+    }
+
+    /// Returns the components `component` can reach, itself included.
+    fn components_reached(&self, component: usize) -> Vec<usize> {
+        (0..self.members.len())
+            .filter(|&other| self.reachable[component][other / 64] & (1 << (other % 64)) != 0)
+            .collect()
+    }
+
+    /// Recomputes which components the given components can reach.
+    ///
+    /// The rows of all other components must be up to date; the given components
+    /// are processed in topological order of the condensed graph among them.
+    ///
+    /// # Parameters
+    /// - `components`: The ids of the components to update.
+    fn update_reachability(&mut self, components: &HashSet<usize>) {
+        let count = self.members.len();
+        let words = count.div_ceil(64).max(1);
+        for row in &mut self.reachable {
+            row.resize(words, 0);
+        }
+        self.reachable.resize(count, vec![0; words]);
+
+        // Edges from the given components to different components
+        let mut edges: HashMap<usize, HashSet<usize>> = HashMap::new();
+        let mut in_degree: HashMap<usize, usize> = components.iter().map(|&c| (c, 0)).collect();
+        for &from in components {
+            let targets: HashSet<usize> = self.members[from]
+                .iter()
+                .flat_map(|member| self.successors.get(member).into_iter().flatten())
+                .filter_map(|target| self.component_of.get(target).copied())
+                .filter(|&to| to != from)
+                .collect();
+            for to in &targets {
+                if let Some(degree) = in_degree.get_mut(to) {
+                    *degree += 1;
+                }
+            }
+            edges.insert(from, targets);
+        }
+
+        // The condensed graph has no cycles, so a topological order exists
+        let mut order: Vec<usize> = components
+            .iter()
+            .copied()
+            .filter(|component| in_degree[component] == 0)
+            .collect();
+        let mut next = 0;
+        while next < order.len() {
+            let component = order[next];
+            next += 1;
+            for to in &edges[&component] {
+                if let Some(degree) = in_degree.get_mut(to) {
+                    *degree -= 1;
+                    if *degree == 0 {
+                        order.push(*to);
+                    }
+                }
+            }
+        }
+
+        for &component in order.iter().rev() {
+            let mut bits = vec![0u64; words];
+            bits[component / 64] |= 1 << (component % 64);
+            for &to in &edges[&component] {
+                for (word, other) in bits.iter_mut().zip(&self.reachable[to]) {
+                    *word |= other;
+                }
+            }
+            self.reachable[component] = bits;
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use crate::node::Node;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// Version of the map document format written by this crate.
+pub const MAP_DOCUMENT_VERSION: u32 = 1;
+
+/// Bytes every binary map document starts with.
+const BINARY_MAGIC: &[u8; 4] = b"CAMD";
+
+/// Errors that can occur while reading or writing a map document.
+#[derive(Debug)]
+pub enum DocumentError {
+    /// The JSON form could not be read or written.
+    Json(serde_json::Error),
+    /// The binary form could not be read or written.
+    Binary(bincode::Error),
+    /// The binary form does not start with the expected magic bytes.
+    InvalidMagic,
+    /// The document was written by a version of the format this crate cannot read.
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for DocumentError {
+    /// Formats the error for display in user-facing contexts.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DocumentError::Json(error) => write!(f, "Invalid JSON map document: {}", error),
+            DocumentError::Binary(error) => write!(f, "Invalid binary map document: {}", error),
+            DocumentError::InvalidMagic => write!(f, "The data is not a binary map document!"),
+            DocumentError::UnsupportedVersion(version) => write!(
+                f,
+                "Map document version {} is not supported, expected {}!",
+                version, MAP_DOCUMENT_VERSION
+            ),
+        }
+    }
+}
+
+impl Error for DocumentError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DocumentError::Json(error) => Some(error),
+            DocumentError::Binary(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// A versioned, serializable form of a whole node map.
+///
+/// Every node is stored with its map key, neighbors, custom edges and cost, so
+/// manual `set_neighbor` and `remove_neighbor` changes survive a round trip, as do
+/// nodes whose `x` and `y` differ from their key. Nodes are sorted by key to keep
+/// the output stable.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MapDocument {
+    pub version: u32,
+    pub nodes: Vec<((usize, usize), Node)>,
+}
+
+impl MapDocument {
+    /// Creates a document from a node map.
+    ///
+    /// # Parameters
+    /// - `nodes`: The node map to store.
+    ///
+    /// # Returns
+    /// A `MapDocument` of the current version.
+    pub fn from_nodes(nodes: &HashMap<(usize, usize), Node>) -> Self {
+        let mut sorted: Vec<((usize, usize), Node)> = nodes
+            .iter()
+            .map(|(&key, node)| (key, node.clone()))
+            .collect();
+        sorted.sort_by_key(|&(key, _)| key);
+
+        MapDocument {
+            version: MAP_DOCUMENT_VERSION,
+            nodes: sorted,
+        }
+    }
+
+    /// Turns the document back into a node map, under the keys it was stored with.
+    pub fn into_nodes(self) -> HashMap<(usize, usize), Node> {
+        self.nodes.into_iter().collect()
+    }
+
+    /// Writes the document as JSON.
+    ///
+    /// # Returns
+    /// The JSON text, or a `DocumentError` if serialization fails.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{MapDocument, Node};
+    ///
+    /// let nodes = Node::grid_to_nodes(&vec![vec![0, 1], vec![0, 0]]);
+    /// let json = MapDocument::from_nodes(&nodes).to_json().unwrap();
+    /// let restored = MapDocument::from_json(&json).unwrap().into_nodes();
+    /// assert_eq!(restored, nodes);
+    /// ```
+    pub fn to_json(&self) -> Result<String, DocumentError> {
+        serde_json::to_string(self).map_err(DocumentError::Json)
+    }
+
+    /// Reads a document from JSON.
+    ///
+    /// # Parameters
+    /// - `json`: The JSON text.
+    ///
+    /// # Returns
+    /// The `MapDocument`, or a `DocumentError` if the text is invalid or of an unsupported version.
+    pub fn from_json(json: &str) -> Result<Self, DocumentError> {
+        // Check the version first, later versions may not match this structure
+        let probe: VersionProbe = serde_json::from_str(json).map_err(DocumentError::Json)?;
+        check_version(probe.version)?;
+        serde_json::from_str(json).map_err(DocumentError::Json)
+    }
+
+    /// Writes the document in a compact binary form.
+    ///
+    /// # Returns
+    /// The encoded bytes, or a `DocumentError` if serialization fails.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{MapDocument, Node};
+    ///
+    /// let nodes = Node::grid_to_nodes(&vec![vec![0, 1], vec![0, 0]]);
+    /// let bytes = MapDocument::from_nodes(&nodes).to_bytes().unwrap();
+    /// let restored = MapDocument::from_bytes(&bytes).unwrap().into_nodes();
+    /// assert_eq!(restored, nodes);
+    /// ```
+    pub fn to_bytes(&self) -> Result<Vec<u8>, DocumentError> {
+        let mut bytes = BINARY_MAGIC.to_vec();
+        bincode::serialize_into(&mut bytes, self).map_err(DocumentError::Binary)?;
+        Ok(bytes)
+    }
+
+    /// Reads a document from its compact binary form.
+    ///
+    /// # Parameters
+    /// - `bytes`: The encoded bytes.
+    ///
+    /// # Returns
+    /// The `MapDocument`, or a `DocumentError` if the bytes are invalid or of an unsupported version.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DocumentError> {
+        let payload = bytes
+            .strip_prefix(BINARY_MAGIC)
+            .ok_or(DocumentError::InvalidMagic)?;
+
+        // The version is the first field, check it before decoding the rest
+        let version: u32 = bincode::deserialize(payload).map_err(DocumentError::Binary)?;
+        check_version(version)?;
+        bincode::deserialize(payload).map_err(DocumentError::Binary)
+    }
+}
+
+/// The part of a document needed to check its version.
+#[derive(Deserialize)]
+struct VersionProbe {
+    version: u32,
+}
+
+/// Rejects documents of versions this crate cannot read.
+fn check_version(version: u32) -> Result<(), DocumentError> {
+    if version != MAP_DOCUMENT_VERSION {
+        return Err(DocumentError::UnsupportedVersion(version));
+    }
+    Ok(())
+}
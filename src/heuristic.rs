@@ -0,0 +1,77 @@
+use crate::hex::{HexCoord, HexLayout};
+
+/// Heuristics available to the A* algorithm.
+///
+/// Every heuristic works on map positions `(x, y)` as used for the keys of the node map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Heuristic {
+    /// Sum of the absolute coordinate differences. Suited to four-directional grids.
+    #[default]
+    Manhattan,
+    /// Number of hex steps between two cells of an offset hex grid with the given layout.
+    Hex(HexLayout),
+    /// Cost of the cheapest eight-directional route, with the given costs for
+    /// straight and diagonal steps.
+    Octile { straight: usize, diagonal: usize },
+    /// Always estimates `0`, turning A* into Dijkstra's algorithm.
+    Zero,
+}
+
+impl Heuristic {
+    /// Estimates the cost of reaching `goal` from `from`.
+    ///
+    /// # Parameters
+    /// - `from`: The position to estimate from as a tuple (x, y).
+    /// - `goal`: The goal position as a tuple (x, y).
+    ///
+    /// # Returns
+    /// The estimated cost as a `usize`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::Heuristic;
+    ///
+    /// assert_eq!(Heuristic::Manhattan.estimate((0, 0), (2, 3)), 5);
+    /// assert_eq!(Heuristic::Octile { straight: 2, diagonal: 3 }.estimate((0, 0), (2, 3)), 8);
+    /// assert_eq!(Heuristic::Zero.estimate((0, 0), (2, 3)), 0);
+    /// ```
+    pub fn estimate(&self, from: (usize, usize), goal: (usize, usize)) -> usize {
+        match self {
+            Heuristic::Manhattan => manhattan_distance(from, goal),
+            Heuristic::Hex(layout) => {
+                // Map keys are (row, column), offset coordinates are (column, row)
+                let a = HexCoord::from_offset(from.1 as i32, from.0 as i32, *layout);
+                let b = HexCoord::from_offset(goal.1 as i32, goal.0 as i32, *layout);
+                a.distance(b) as usize
+            }
+            Heuristic::Octile { straight, diagonal } => {
+                let dx = from.0.abs_diff(goal.0);
+                let dy = from.1.abs_diff(goal.1);
+
+                // Move diagonally as long as possible, then straight
+                let diagonal_steps = dx.min(dy);
+                diagonal_steps * diagonal + (dx.max(dy) - diagonal_steps) * straight
+            }
+            Heuristic::Zero => 0,
+        }
+    }
+}
+
+/// Calculates the Manhattan distance between two points.
+///
+/// The Manhattan distance is the sum of the absolute differences of their Cartesian coordinates.
+///
+/// # Parameters
+/// - `start`: The starting point as a tuple (x, y).
+/// - `goal`: The goal point as a tuple (x, y).
+///
+/// # Returns
+/// The Manhattan distance between the start and goal points as a `usize`.
+pub(crate) fn manhattan_distance(start: (usize, usize), goal: (usize, usize)) -> usize {
+    let (x1, y1) = start;
+    let (x2, y2) = goal;
+
+    // Compute the absolute differences in x and y coordinates and sum them
+    x1.abs_diff(x2) + y1.abs_diff(y2)
+}
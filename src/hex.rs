@@ -0,0 +1,307 @@
+use crate::node::{Direction, Node};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Offset layouts for pointy-top hex grids stored as rows of cells.
+///
+/// In both layouts every other row is shoved half a cell to the right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HexLayout {
+    /// Odd rows are shoved right.
+    OddR,
+    /// Even rows are shoved right.
+    EvenR,
+}
+
+/// The six directions of a pointy-top hex cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HexDirection {
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+impl HexDirection {
+    /// All six hex directions, clockwise from east.
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::SouthEast,
+        HexDirection::SouthWest,
+        HexDirection::West,
+        HexDirection::NorthWest,
+        HexDirection::NorthEast,
+    ];
+
+    /// Returns the axial `(q, r)` offset of a single step in this direction.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::HexDirection;
+    ///
+    /// assert_eq!(HexDirection::NorthEast.axial_offset(), (1, -1));
+    /// ```
+    pub fn axial_offset(&self) -> (i32, i32) {
+        match self {
+            HexDirection::East => (1, 0),
+            HexDirection::NorthEast => (1, -1),
+            HexDirection::NorthWest => (0, -1),
+            HexDirection::West => (-1, 0),
+            HexDirection::SouthWest => (-1, 1),
+            HexDirection::SouthEast => (0, 1),
+        }
+    }
+
+    /// Returns the `Direction` used to store this hex direction in `Node::neighbors`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{Direction, HexDirection};
+    ///
+    /// assert_eq!(HexDirection::SouthWest.to_direction(), Direction::SouthWest);
+    /// ```
+    pub fn to_direction(&self) -> Direction {
+        match self {
+            HexDirection::East => Direction::East,
+            HexDirection::NorthEast => Direction::NorthEast,
+            HexDirection::NorthWest => Direction::NorthWest,
+            HexDirection::West => Direction::West,
+            HexDirection::SouthWest => Direction::SouthWest,
+            HexDirection::SouthEast => Direction::SouthEast,
+        }
+    }
+}
+
+/// A hex cell in axial coordinates.
+///
+/// The third cube coordinate is implied by `s = -q - r`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HexCoord {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl HexCoord {
+    /// Creates a new `HexCoord` from axial coordinates.
+    ///
+    /// # Parameters
+    /// - `q`: The axial column.
+    /// - `r`: The axial row.
+    ///
+    /// # Returns
+    /// A newly created `HexCoord`.
+    pub fn new(q: i32, r: i32) -> Self {
+        HexCoord { q, r }
+    }
+
+    /// Creates a `HexCoord` from cube coordinates.
+    ///
+    /// # Parameters
+    /// - `x`, `y`, `z`: The cube coordinates. They must sum to `0`.
+    ///
+    /// # Returns
+    /// `Some(HexCoord)` if the coordinates are valid, otherwise `None`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::HexCoord;
+    ///
+    /// assert_eq!(HexCoord::from_cube(1, -3, 2), Some(HexCoord::new(1, -3)));
+    /// assert_eq!(HexCoord::from_cube(1, 1, 1), None);
+    /// ```
+    pub fn from_cube(x: i32, y: i32, z: i32) -> Option<Self> {
+        if x + y + z != 0 {
+            return None;
+        }
+        Some(HexCoord { q: x, r: y })
+    }
+
+    /// Returns the cube coordinates `(q, r, s)` of this cell.
+    pub fn to_cube(&self) -> (i32, i32, i32) {
+        (self.q, self.r, -self.q - self.r)
+    }
+
+    /// Converts offset coordinates into axial coordinates.
+    ///
+    /// # Parameters
+    /// - `col`: The column of the cell in the offset grid.
+    /// - `row`: The row of the cell in the offset grid.
+    /// - `layout`: The offset layout of the grid.
+    ///
+    /// # Returns
+    /// The axial `HexCoord` of the cell.
+    pub fn from_offset(col: i32, row: i32, layout: HexLayout) -> Self {
+        let q = match layout {
+            HexLayout::OddR => col - (row - (row & 1)) / 2,
+            HexLayout::EvenR => col - (row + (row & 1)) / 2,
+        };
+        HexCoord { q, r: row }
+    }
+
+    /// Converts this cell into offset coordinates.
+    ///
+    /// # Parameters
+    /// - `layout`: The offset layout of the grid.
+    ///
+    /// # Returns
+    /// The `(col, row)` of the cell in the offset grid.
+    pub fn to_offset(&self, layout: HexLayout) -> (i32, i32) {
+        let col = match layout {
+            HexLayout::OddR => self.q + (self.r - (self.r & 1)) / 2,
+            HexLayout::EvenR => self.q + (self.r + (self.r & 1)) / 2,
+        };
+        (col, self.r)
+    }
+
+    /// Returns the adjacent cell in the given direction.
+    pub fn neighbor(&self, direction: HexDirection) -> Self {
+        let (dq, dr) = direction.axial_offset();
+        HexCoord {
+            q: self.q + dq,
+            r: self.r + dr,
+        }
+    }
+
+    /// Calculates the number of hex steps between two cells.
+    ///
+    /// # Parameters
+    /// - `other`: The cell to measure the distance to.
+    ///
+    /// # Returns
+    /// The hex distance as a `u32`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::HexCoord;
+    ///
+    /// let a = HexCoord::new(0, 0);
+    /// let b = HexCoord::new(2, -1);
+    /// assert_eq!(a.distance(b), 2);
+    /// ```
+    pub fn distance(&self, other: HexCoord) -> u32 {
+        let (x1, y1, z1) = self.to_cube();
+        let (x2, y2, z2) = other.to_cube();
+
+        // Half the Manhattan distance in cube space
+        (x1.abs_diff(x2) + y1.abs_diff(y2) + z1.abs_diff(z2)) / 2
+    }
+}
+
+impl Node {
+    /// Converts a 2D grid laid out as an offset hex grid into `Node` objects.
+    ///
+    /// Nodes are keyed like in `Node::grid_to_nodes`, and their neighbors are the
+    /// six adjacent hex cells stored under the matching `Direction`
+    /// (see `HexDirection::to_direction`).
+    ///
+    /// # Parameters
+    /// - `grid`: The 2D grid where `1` represents a blocked node and `0` represents a free node.
+    /// - `layout`: The offset layout of the grid.
+    ///
+    /// # Returns
+    /// A `HashMap` containing `Node` objects mapped by their positions.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{HexLayout, Node};
+    ///
+    /// let grid = vec![
+    ///     vec![0, 0, 0],
+    ///     vec![0, 1, 0],
+    /// ];
+    /// let nodes = Node::hex_grid_to_nodes(&grid, HexLayout::OddR);
+    /// assert_eq!(nodes[&(0, 1)].neighbors.len(), 4);
+    /// ```
+    pub fn hex_grid_to_nodes(
+        grid: &[Vec<i32>],
+        layout: HexLayout,
+    ) -> HashMap<(usize, usize), Node> {
+        let mut hash_map = HashMap::new();
+        let rows = grid.len() as i32;
+
+        for (x, row) in grid.iter().enumerate() {
+            let cols = row.len() as i32;
+            for (y, &cell) in row.iter().enumerate() {
+                let coord = HexCoord::from_offset(y as i32, x as i32, layout);
+                let mut neighbors = BTreeMap::new();
+
+                // Link every adjacent hex cell that lies inside the grid
+                for direction in HexDirection::ALL {
+                    let (col, row) = coord.neighbor(direction).to_offset(layout);
+                    if row >= 0 && row < rows && col >= 0 && col < cols {
+                        neighbors
+                            .insert(direction.to_direction(), Some((row as usize, col as usize)));
+                    }
+                }
+
+                hash_map.insert(
+                    (x, y),
+                    Node {
+                        x,
+                        y,
+                        is_blocked: cell == 1,
+                        neighbors,
+                        custom_edges: Vec::new(),
+                        cost: 1,
+                    },
+                );
+            }
+        }
+
+        hash_map
+    }
+
+    /// Prints an offset hex grid and an optional path to the screen.
+    ///
+    /// Shoved rows are indented by one character so that adjacent cells line up.
+    ///
+    /// # Parameters
+    /// - `grid`: The 2D grid.
+    /// - `path`: An optional vector representing the path to be printed on the grid.
+    /// - `layout`: The offset layout of the grid.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{HexLayout, Node};
+    ///
+    /// let grid = vec![
+    ///     vec![0, 0, 0],
+    ///     vec![0, 1, 0],
+    /// ];
+    /// let path = Some(vec![(0, 0), (0, 1)]);
+    /// Node::print_hex_grid(&grid, &path, HexLayout::OddR);
+    /// ```
+    pub fn print_hex_grid(
+        grid: &[Vec<i32>],
+        path: &Option<Vec<(usize, usize)>>,
+        layout: HexLayout,
+    ) {
+        let on_path: HashSet<(usize, usize)> = path.iter().flatten().copied().collect();
+
+        for (y, row) in grid.iter().enumerate() {
+            // Indent the rows that are shoved right
+            let shoved = match layout {
+                HexLayout::OddR => y % 2 == 1,
+                HexLayout::EvenR => y % 2 == 0,
+            };
+            if shoved {
+                print!(" ");
+            }
+
+            for (x, &cell) in row.iter().enumerate() {
+                if on_path.contains(&(x, y)) {
+                    print!("o ");
+                } else if cell == 1 {
+                    print!("# ");
+                } else {
+                    print!(". ");
+                }
+            }
+            println!();
+        }
+    }
+}
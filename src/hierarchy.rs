@@ -0,0 +1,607 @@
+use crate::astar::{unreachable_reason, AStar, AStarError, Unreachable};
+use crate::heuristic::Heuristic;
+use crate::node::Node;
+use crate::path::Path;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap};
+
+/// Entrances at least this wide get a transition at both ends instead of one in the middle.
+const WIDE_ENTRANCE: usize = 6;
+
+/// The position of a cluster, as the map key of its first node divided by the cluster size.
+type Cluster = (usize, usize);
+
+/// A link between two clusters, as the map keys of its ends.
+type Transition = ((usize, usize), (usize, usize));
+
+/// Edges of the abstract graph from a map key, each with its cost.
+type AbstractEdges = HashMap<(usize, usize), Vec<((usize, usize), usize)>>;
+
+/// A two-level abstraction of a node map for hierarchical pathfinding (HPA*).
+///
+/// The map is split into square clusters. Where passable nodes of two clusters
+/// are linked, the links are grouped into entrances, and one or two links of every
+/// entrance become transitions. The ends of the transitions are the nodes of an
+/// abstract graph, linked by the transitions and by the cheapest paths between
+/// the entrances of each cluster. Searches run on this small graph first, and
+/// the result is refined by `AStar` inside one cluster at a time.
+///
+/// Paths are usually close to the shortest, but not always the shortest, since
+/// they can only cross between clusters at transitions. Positions are given as
+/// `(x, y)`, like for `AStar::find_shortest_path`. The abstraction follows
+/// `HierarchicalMap::set_blocked`, but not later changes to `Node::neighbors`,
+/// custom edges or costs; create a new one after changing them.
+#[derive(Debug, Clone)]
+pub struct HierarchicalMap {
+    cluster_size: usize,
+    heuristic: Heuristic,
+    /// Clusters linked to each cluster by at least one edge, blocked or not.
+    linked_clusters: HashMap<Cluster, BTreeSet<Cluster>>,
+    /// Transitions between two clusters, the lower cluster first, as pairs of map
+    /// keys with the first key in the lower cluster.
+    transitions: HashMap<(Cluster, Cluster), Vec<Transition>>,
+    /// Ends of the transitions of every cluster, by map key.
+    entrances: HashMap<Cluster, Vec<(usize, usize)>>,
+    /// Abstract edges along transitions, between clusters.
+    inter_edges: AbstractEdges,
+    /// Abstract edges between the entrances of the same cluster.
+    intra_edges: AbstractEdges,
+}
+
+impl HierarchicalMap {
+    /// Builds the abstraction of a node map.
+    ///
+    /// # Parameters
+    /// - `nodes`: The node map.
+    /// - `cluster_size`: The width and height of the clusters in nodes, at least `1`.
+    ///
+    /// # Returns
+    /// A new `HierarchicalMap`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{HierarchicalMap, Node};
+    ///
+    /// let nodes = Node::grid_to_nodes(&vec![vec![0; 32]; 32]);
+    /// let map = HierarchicalMap::new(&nodes, 8);
+    /// let path = map.find_path(&nodes, (0, 0), (31, 31)).unwrap();
+    /// assert_eq!(path.total_cost(), 62);
+    /// ```
+    pub fn new(nodes: &HashMap<(usize, usize), Node>, cluster_size: usize) -> Self {
+        let mut map = HierarchicalMap {
+            cluster_size: cluster_size.max(1),
+            heuristic: Heuristic::default(),
+            linked_clusters: HashMap::new(),
+            transitions: HashMap::new(),
+            entrances: HashMap::new(),
+            inter_edges: HashMap::new(),
+            intra_edges: HashMap::new(),
+        };
+
+        for (&position, node) in nodes {
+            for (target, _) in node.edges(nodes) {
+                let (from, to) = (map.cluster_of(position), map.cluster_of(target));
+                if from != to {
+                    map.linked_clusters.entry(from).or_default().insert(to);
+                    map.linked_clusters.entry(to).or_default().insert(from);
+                }
+            }
+        }
+
+        let mut pairs = Vec::new();
+        for (&cluster, linked) in &map.linked_clusters {
+            pairs.extend(
+                linked
+                    .iter()
+                    .filter(|&&other| cluster < other)
+                    .map(|&other| (cluster, other)),
+            );
+        }
+        for (lower, upper) in pairs {
+            map.update_transitions(nodes, lower, upper);
+        }
+        let clusters: Vec<Cluster> = map.linked_clusters.keys().copied().collect();
+        for cluster in clusters {
+            map.update_entrances(nodes, cluster);
+        }
+
+        map
+    }
+
+    /// Sets the heuristic used when refining paths inside clusters.
+    ///
+    /// The default is `Heuristic::Manhattan`, see `AStar::set_heuristic`.
+    ///
+    /// # Parameters
+    /// - `heuristic`: The heuristic to use for subsequent searches.
+    pub fn set_heuristic(&mut self, heuristic: Heuristic) {
+        self.heuristic = heuristic;
+    }
+
+    /// Returns the width and height of the clusters in nodes.
+    pub fn cluster_size(&self) -> usize {
+        self.cluster_size
+    }
+
+    /// Returns the nodes of the abstract graph as `(x, y)`, sorted.
+    pub fn entrances(&self) -> Vec<(usize, usize)> {
+        let mut entrances: Vec<(usize, usize)> = self
+            .entrances
+            .values()
+            .flatten()
+            .map(|&(y, x)| (x, y))
+            .collect();
+        entrances.sort_unstable();
+        entrances
+    }
+
+    /// Finds a path by searching the abstract graph and refining it inside the clusters.
+    ///
+    /// # Parameters
+    /// - `nodes`: The node map the abstraction was built from.
+    /// - `start`: The starting point as a tuple (x, y).
+    /// - `goal`: The goal point as a tuple (x, y).
+    ///
+    /// # Returns
+    /// The `Path` from the start to the goal, or an `AStarError` if no path is found.
+    /// When the goal cannot be reached, `Unreachable::explored` counts the nodes of
+    /// the abstract graph that were explored.
+    pub fn find_path(
+        &self,
+        nodes: &HashMap<(usize, usize), Node>,
+        start: (usize, usize),
+        goal: (usize, usize),
+    ) -> Result<Path, AStarError> {
+        let (start_key, goal_key) = ((start.1, start.0), (goal.1, goal.0));
+        match (nodes.get(&start_key), nodes.get(&goal_key)) {
+            (None, _) => return Err(AStarError::NodeNotFound(start)),
+            (_, None) => return Err(AStarError::NodeNotFound(goal)),
+            (Some(node), _) if node.is_blocked => return Err(AStarError::StartNodeBlocked(start)),
+            (_, Some(node)) if node.is_blocked => return Err(AStarError::GoalNodeBlocked(goal)),
+            _ => {}
+        }
+
+        let waypoints = self.find_abstract_path(nodes, start_key, goal_key)?;
+
+        // Consecutive waypoints in different clusters are linked by a transition
+        let mut positions = vec![start];
+        for pair in waypoints.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            let cluster = self.cluster_of(from);
+            if cluster != self.cluster_of(to) {
+                positions.push((to.1, to.0));
+                continue;
+            }
+            let mut astar = AStar::new(self.cluster_nodes(nodes, cluster));
+            astar.set_heuristic(self.heuristic);
+            let leg = astar.find_shortest_path((from.1, from.0), (to.1, to.0))?;
+            positions.extend_from_slice(&leg.positions()[1..]);
+        }
+
+        Ok(Path::from_positions(nodes, positions).expect("the refined legs follow existing edges"))
+    }
+
+    /// Blocks or unblocks a node and updates the abstraction around it.
+    ///
+    /// Only the transitions of the node's cluster and the entrances of the
+    /// clusters linked to it are recomputed.
+    ///
+    /// # Parameters
+    /// - `nodes`: The node map the abstraction was built from.
+    /// - `position`: The position of the node as `(x, y)`.
+    /// - `blocked`: The new blocked state.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStarError, HierarchicalMap, Node};
+    ///
+    /// let mut nodes = Node::grid_to_nodes(&vec![vec![0; 8]; 2]);
+    /// let mut map = HierarchicalMap::new(&nodes, 4);
+    ///
+    /// map.set_blocked(&mut nodes, (4, 0), true);
+    /// map.set_blocked(&mut nodes, (4, 1), true);
+    /// assert!(matches!(
+    ///     map.find_path(&nodes, (0, 0), (7, 0)),
+    ///     Err(AStarError::PathNotFound(_))
+    /// ));
+    ///
+    /// map.set_blocked(&mut nodes, (4, 1), false);
+    /// assert_eq!(map.find_path(&nodes, (0, 0), (7, 0)).unwrap().total_cost(), 9);
+    /// ```
+    pub fn set_blocked(
+        &mut self,
+        nodes: &mut HashMap<(usize, usize), Node>,
+        position: (usize, usize),
+        blocked: bool,
+    ) {
+        let key = (position.1, position.0);
+        let Some(node) = nodes.get_mut(&key) else {
+            return;
+        };
+        if node.is_blocked == blocked {
+            return;
+        }
+        node.set_blocked(blocked);
+
+        let cluster = self.cluster_of(key);
+        let linked: Vec<Cluster> = self
+            .linked_clusters
+            .get(&cluster)
+            .into_iter()
+            .flatten()
+            .copied()
+            .collect();
+        for &other in &linked {
+            self.update_transitions(nodes, cluster.min(other), cluster.max(other));
+        }
+
+        // Paths inside the node's cluster may have changed, the other clusters
+        // only need new costs if their entrances moved
+        self.update_entrances(nodes, cluster);
+        for other in linked {
+            if self.entrance_keys(other) != self.entrances.get(&other).cloned().unwrap_or_default()
+            {
+                self.update_entrances(nodes, other);
+            }
+        }
+    }
+
+    /// Returns the cluster of a map key.
+    fn cluster_of(&self, position: (usize, usize)) -> Cluster {
+        (
+            position.0 / self.cluster_size,
+            position.1 / self.cluster_size,
+        )
+    }
+
+    /// Returns the map keys of the nodes of a cluster.
+    fn cluster_keys(
+        &self,
+        nodes: &HashMap<(usize, usize), Node>,
+        cluster: Cluster,
+    ) -> Vec<(usize, usize)> {
+        let size = self.cluster_size;
+        let mut keys = Vec::new();
+        for a in cluster.0 * size..(cluster.0 + 1) * size {
+            for b in cluster.1 * size..(cluster.1 + 1) * size {
+                if nodes.contains_key(&(a, b)) {
+                    keys.push((a, b));
+                }
+            }
+        }
+        keys
+    }
+
+    /// Copies the nodes of a cluster, without their links to other clusters.
+    fn cluster_nodes(
+        &self,
+        nodes: &HashMap<(usize, usize), Node>,
+        cluster: Cluster,
+    ) -> HashMap<(usize, usize), Node> {
+        self.cluster_keys(nodes, cluster)
+            .into_iter()
+            .map(|key| {
+                let mut node = nodes[&key].clone();
+                node.neighbors.retain(|_, target| {
+                    target.is_some_and(|target| self.cluster_of(target) == cluster)
+                });
+                node.custom_edges
+                    .retain(|edge| self.cluster_of(edge.target) == cluster);
+                (key, node)
+            })
+            .collect()
+    }
+
+    /// Returns the links between the passable nodes of a cluster, each with its cost.
+    ///
+    /// # Parameters
+    /// - `reverse`: Whether to list the links into every node instead of out of it.
+    fn cluster_edges(
+        &self,
+        nodes: &HashMap<(usize, usize), Node>,
+        cluster: Cluster,
+        reverse: bool,
+    ) -> AbstractEdges {
+        let mut edges: AbstractEdges = HashMap::new();
+        for key in self.cluster_keys(nodes, cluster) {
+            let node = &nodes[&key];
+            if node.is_blocked {
+                continue;
+            }
+            for (target, cost) in node.edges(nodes) {
+                if self.cluster_of(target) != cluster || nodes[&target].is_blocked {
+                    continue;
+                }
+                if reverse {
+                    edges.entry(target).or_default().push((key, cost));
+                } else {
+                    edges.entry(key).or_default().push((target, cost));
+                }
+            }
+        }
+        edges
+    }
+
+    /// Recomputes the transitions between two clusters and their abstract edges.
+    fn update_transitions(
+        &mut self,
+        nodes: &HashMap<(usize, usize), Node>,
+        lower: Cluster,
+        upper: Cluster,
+    ) {
+        for (from, to) in self
+            .transitions
+            .remove(&(lower, upper))
+            .into_iter()
+            .flatten()
+        {
+            if let Some(edges) = self.inter_edges.get_mut(&from) {
+                edges.retain(|&(target, _)| target != to);
+            }
+            if let Some(edges) = self.inter_edges.get_mut(&to) {
+                edges.retain(|&(target, _)| target != from);
+            }
+        }
+
+        // Links between passable nodes of the two clusters, in either direction
+        let mut links = BTreeSet::new();
+        for (cluster, other) in [(lower, upper), (upper, lower)] {
+            for key in self.cluster_keys(nodes, cluster) {
+                if nodes[&key].is_blocked {
+                    continue;
+                }
+                for (target, _) in nodes[&key].edges(nodes) {
+                    if self.cluster_of(target) == other && !nodes[&target].is_blocked {
+                        links.insert(if cluster == lower {
+                            (key, target)
+                        } else {
+                            (target, key)
+                        });
+                    }
+                }
+            }
+        }
+        let links: Vec<_> = links.into_iter().collect();
+
+        // Links side by side on both ends form one entrance
+        let mut group: Vec<usize> = (0..links.len()).collect();
+        for i in 0..links.len() {
+            for j in i + 1..links.len() {
+                let (a, b) = (links[i], links[j]);
+                if are_adjacent(nodes, a.0, b.0) && are_adjacent(nodes, a.1, b.1) {
+                    let (root_i, root_j) = (find_root(&mut group, i), find_root(&mut group, j));
+                    group[root_i.max(root_j)] = root_i.min(root_j);
+                }
+            }
+        }
+        let mut entrances: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for index in 0..links.len() {
+            entrances
+                .entry(find_root(&mut group, index))
+                .or_default()
+                .push(index);
+        }
+
+        let mut transitions = Vec::new();
+        for members in entrances.into_values() {
+            if members.len() < WIDE_ENTRANCE {
+                transitions.push(links[members[members.len() / 2]]);
+            } else {
+                transitions.push(links[members[0]]);
+                transitions.push(links[members[members.len() - 1]]);
+            }
+        }
+
+        for &(from, to) in &transitions {
+            if let Some(cost) = link_cost(nodes, from, to) {
+                self.inter_edges.entry(from).or_default().push((to, cost));
+            }
+            if let Some(cost) = link_cost(nodes, to, from) {
+                self.inter_edges.entry(to).or_default().push((from, cost));
+            }
+        }
+        if !transitions.is_empty() {
+            self.transitions.insert((lower, upper), transitions);
+        }
+    }
+
+    /// Returns the ends of the transitions inside a cluster, sorted.
+    fn entrance_keys(&self, cluster: Cluster) -> Vec<(usize, usize)> {
+        let mut keys = BTreeSet::new();
+        for &other in self.linked_clusters.get(&cluster).into_iter().flatten() {
+            let pair = (cluster.min(other), cluster.max(other));
+            for &(lower_key, upper_key) in self.transitions.get(&pair).into_iter().flatten() {
+                keys.insert(if cluster < other {
+                    lower_key
+                } else {
+                    upper_key
+                });
+            }
+        }
+        keys.into_iter().collect()
+    }
+
+    /// Recomputes the entrances of a cluster and the costs between them.
+    fn update_entrances(&mut self, nodes: &HashMap<(usize, usize), Node>, cluster: Cluster) {
+        for entrance in self.entrances.remove(&cluster).into_iter().flatten() {
+            self.intra_edges.remove(&entrance);
+        }
+
+        let entrances = self.entrance_keys(cluster);
+        let edges = self.cluster_edges(nodes, cluster, false);
+        for &entrance in &entrances {
+            let costs = shortest_costs(&edges, entrance);
+            let reached = entrances
+                .iter()
+                .filter(|&&other| other != entrance)
+                .filter_map(|&other| costs.get(&other).map(|&cost| (other, cost)))
+                .collect();
+            self.intra_edges.insert(entrance, reached);
+        }
+        if !entrances.is_empty() {
+            self.entrances.insert(cluster, entrances);
+        }
+    }
+
+    /// Searches the abstract graph, with the start and goal linked to the entrances of their clusters.
+    ///
+    /// # Returns
+    /// The map keys of the abstract path, from the start to the goal.
+    fn find_abstract_path(
+        &self,
+        nodes: &HashMap<(usize, usize), Node>,
+        start: (usize, usize),
+        goal: (usize, usize),
+    ) -> Result<Vec<(usize, usize)>, AStarError> {
+        let (start_cluster, goal_cluster) = (self.cluster_of(start), self.cluster_of(goal));
+        let from_start = shortest_costs(&self.cluster_edges(nodes, start_cluster, false), start);
+        let to_goal = shortest_costs(&self.cluster_edges(nodes, goal_cluster, true), goal);
+        let no_entrances = Vec::new();
+
+        let mut costs = HashMap::from([(start, 0)]);
+        let mut came_from = HashMap::new();
+        let mut settled = Vec::new();
+        let mut open = BinaryHeap::from([Reverse((0, start))]);
+        while let Some(Reverse((cost, position))) = open.pop() {
+            if cost > costs[&position] {
+                continue;
+            }
+            if position == goal {
+                let mut path = vec![goal];
+                let mut current = goal;
+                while let Some(&previous) = came_from.get(&current) {
+                    path.push(previous);
+                    current = previous;
+                }
+                path.reverse();
+                return Ok(path);
+            }
+            settled.push(position);
+
+            let mut steps: Vec<((usize, usize), usize)> = Vec::new();
+            if position == start {
+                let entrances = self.entrances.get(&start_cluster).unwrap_or(&no_entrances);
+                steps.extend(
+                    entrances
+                        .iter()
+                        .filter_map(|entrance| from_start.get(entrance).map(|&c| (*entrance, c))),
+                );
+            }
+            if self.cluster_of(position) == goal_cluster {
+                steps.extend(to_goal.get(&position).map(|&c| (goal, c)));
+            }
+            for edges in [&self.inter_edges, &self.intra_edges] {
+                steps.extend(edges.get(&position).into_iter().flatten().copied());
+            }
+
+            for (next, step_cost) in steps {
+                let next_cost = cost + step_cost;
+                if next_cost < *costs.get(&next).unwrap_or(&usize::MAX) {
+                    costs.insert(next, next_cost);
+                    came_from.insert(next, position);
+                    open.push(Reverse((next_cost, next)));
+                }
+            }
+        }
+
+        Err(AStarError::PathNotFound(
+            self.unreachable(nodes, start, goal, &settled, &costs),
+        ))
+    }
+
+    /// Explains why the abstract search did not reach the goal.
+    fn unreachable(
+        &self,
+        nodes: &HashMap<(usize, usize), Node>,
+        start: (usize, usize),
+        goal: (usize, usize),
+        settled: &[(usize, usize)],
+        costs: &HashMap<(usize, usize), usize>,
+    ) -> Unreachable {
+        let reason = unreachable_reason(nodes, &[start], &[goal]);
+        let closest = settled
+            .iter()
+            .min_by_key(|&&position| {
+                (
+                    self.heuristic.estimate(position, goal),
+                    costs[&position],
+                    position,
+                )
+            })
+            .copied()
+            .unwrap_or(start);
+
+        Unreachable {
+            reason,
+            closest: (closest.1, closest.0),
+            level: None,
+            explored: settled.len(),
+        }
+    }
+}
+
+/// Checks whether two map keys are the same node or directional neighbors, in either direction.
+fn are_adjacent(
+    nodes: &HashMap<(usize, usize), Node>,
+    first: (usize, usize),
+    second: (usize, usize),
+) -> bool {
+    let links = |from: (usize, usize), to: (usize, usize)| {
+        nodes[&from]
+            .neighbors
+            .values()
+            .any(|&target| target == Some(to))
+    };
+    first == second || links(first, second) || links(second, first)
+}
+
+/// Returns the cost of the cheapest link from one map key to another, if any.
+fn link_cost(
+    nodes: &HashMap<(usize, usize), Node>,
+    from: (usize, usize),
+    to: (usize, usize),
+) -> Option<usize> {
+    nodes[&from]
+        .edges(nodes)
+        .into_iter()
+        .filter(|&(target, _)| target == to)
+        .map(|(_, cost)| cost)
+        .min()
+}
+
+/// Finds the representative of a group in a union-find forest, compressing the path to it.
+fn find_root(group: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while group[root] != root {
+        root = group[root];
+    }
+    let mut current = index;
+    while group[current] != root {
+        let next = group[current];
+        group[current] = root;
+        current = next;
+    }
+    root
+}
+
+/// Runs Dijkstra's algorithm over a set of edges.
+///
+/// # Returns
+/// The cost of the cheapest path from `source` to every reached map key.
+fn shortest_costs(edges: &AbstractEdges, source: (usize, usize)) -> HashMap<(usize, usize), usize> {
+    let mut costs = HashMap::from([(source, 0)]);
+    let mut open = BinaryHeap::from([Reverse((0, source))]);
+    while let Some(Reverse((cost, position))) = open.pop() {
+        if cost > costs[&position] {
+            continue;
+        }
+        for &(next, step_cost) in edges.get(&position).into_iter().flatten() {
+            let next_cost = cost + step_cost;
+            if next_cost < *costs.get(&next).unwrap_or(&usize::MAX) {
+                costs.insert(next, next_cost);
+                open.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+    costs
+}
@@ -0,0 +1,403 @@
+use crate::node::Node;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Distance stored for positions that cannot be reached.
+const UNREACHABLE: usize = usize::MAX;
+
+/// How `LandmarkTable::new` picks its landmarks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LandmarkStrategy {
+    /// Every landmark is the position farthest from the landmarks picked before it.
+    #[default]
+    Farthest,
+    /// Every landmark is placed in the part of the map the landmarks picked before
+    /// it estimate worst, found by growing a shortest path tree from the position
+    /// farthest from them (the "avoid" strategy of Goldberg and Werneck).
+    Avoid,
+}
+
+/// Precomputed distances to and from a few landmarks, for the ALT heuristic.
+///
+/// For a landmark `L`, the triangle inequality gives two lower bounds on the cost
+/// of the cheapest path from `v` to `t`: `d(L, t) - d(L, v)` and `d(v, L) - d(t, L)`.
+/// The largest bound over all landmarks never overestimates, and follows walls,
+/// one-way edges and terrain costs where geometric heuristics cannot.
+///
+/// The bounds stay admissible when nodes are blocked or get more expensive later,
+/// but not when nodes are unblocked, get cheaper or gain links; build a new table
+/// after such changes. Positions are given as `(x, y)`, like for
+/// `AStar::find_shortest_path`. With the `serde` feature, tables can be stored and
+/// loaded to skip the precomputation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "LandmarkParts", into = "LandmarkParts")
+)]
+pub struct LandmarkTable {
+    /// The landmarks, by map key.
+    landmarks: Vec<(usize, usize)>,
+    /// The map keys of the table, sorted.
+    positions: Vec<(usize, usize)>,
+    /// The index of every map key in `positions`.
+    indices: HashMap<(usize, usize), usize>,
+    /// The cost from every landmark to every position, by landmark and position index.
+    from_landmarks: Vec<Vec<usize>>,
+    /// The cost from every position to every landmark, by landmark and position index.
+    to_landmarks: Vec<Vec<usize>>,
+}
+
+/// The serialized form of a `LandmarkTable`, without the index rebuilt on loading.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct LandmarkParts {
+    landmarks: Vec<(usize, usize)>,
+    positions: Vec<(usize, usize)>,
+    from_landmarks: Vec<Vec<usize>>,
+    to_landmarks: Vec<Vec<usize>>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<LandmarkParts> for LandmarkTable {
+    type Error = String;
+
+    fn try_from(parts: LandmarkParts) -> Result<Self, Self::Error> {
+        let count = parts.landmarks.len();
+        if parts.from_landmarks.len() != count || parts.to_landmarks.len() != count {
+            return Err(format!(
+                "a table with {} landmarks needs as many distance lists, found {} and {}",
+                count,
+                parts.from_landmarks.len(),
+                parts.to_landmarks.len()
+            ));
+        }
+        let size = parts.positions.len();
+        if let Some(distances) = parts
+            .from_landmarks
+            .iter()
+            .chain(&parts.to_landmarks)
+            .find(|distances| distances.len() != size)
+        {
+            return Err(format!(
+                "a table with {} positions needs as many distances per landmark, found {}",
+                size,
+                distances.len()
+            ));
+        }
+        Ok(LandmarkTable {
+            indices: index_positions(&parts.positions),
+            landmarks: parts.landmarks,
+            positions: parts.positions,
+            from_landmarks: parts.from_landmarks,
+            to_landmarks: parts.to_landmarks,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<LandmarkTable> for LandmarkParts {
+    fn from(table: LandmarkTable) -> Self {
+        LandmarkParts {
+            landmarks: table.landmarks,
+            positions: table.positions,
+            from_landmarks: table.from_landmarks,
+            to_landmarks: table.to_landmarks,
+        }
+    }
+}
+
+impl LandmarkTable {
+    /// Picks landmarks and computes the distances to and from them.
+    ///
+    /// Runs two passes of Dijkstra's algorithm over the directed node map per
+    /// landmark, following directional neighbors and custom edges between
+    /// passable nodes.
+    ///
+    /// # Parameters
+    /// - `nodes`: The node map.
+    /// - `count`: The number of landmarks, at most the number of passable nodes.
+    /// - `strategy`: How the landmarks are picked.
+    ///
+    /// # Returns
+    /// A new `LandmarkTable`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{LandmarkStrategy, LandmarkTable, Node};
+    ///
+    /// let grid = vec![
+    ///     vec![0, 0, 0, 0, 0],
+    ///     vec![1, 1, 1, 1, 0],
+    ///     vec![0, 0, 0, 0, 0],
+    /// ];
+    /// let nodes = Node::grid_to_nodes(&grid);
+    /// let table = LandmarkTable::new(&nodes, 2, LandmarkStrategy::Farthest);
+    /// assert_eq!(table.landmarks().len(), 2);
+    /// // Manhattan distance estimates 2, the wall makes it 10
+    /// assert_eq!(table.estimate((0, 0), (0, 2)), 10);
+    /// ```
+    pub fn new(
+        nodes: &HashMap<(usize, usize), Node>,
+        count: usize,
+        strategy: LandmarkStrategy,
+    ) -> Self {
+        let mut positions: Vec<(usize, usize)> = nodes.keys().copied().collect();
+        positions.sort_unstable();
+        let mut table = LandmarkTable {
+            landmarks: Vec::new(),
+            indices: index_positions(&positions),
+            positions,
+            from_landmarks: Vec::new(),
+            to_landmarks: Vec::new(),
+        };
+
+        let passable = table
+            .positions
+            .iter()
+            .filter(|position| !nodes[position].is_blocked)
+            .count();
+        for _ in 0..count.min(passable) {
+            let landmark = match strategy {
+                LandmarkStrategy::Farthest => table.farthest(nodes),
+                LandmarkStrategy::Avoid => table.avoid(nodes),
+            };
+            table.add_landmark(nodes, landmark);
+        }
+        table
+    }
+
+    /// Returns the landmarks as `(x, y)`, in the order they were picked.
+    pub fn landmarks(&self) -> Vec<(usize, usize)> {
+        self.landmarks.iter().map(|&(y, x)| (x, y)).collect()
+    }
+
+    /// Returns a lower bound on the cost of the cheapest path between two positions.
+    ///
+    /// # Parameters
+    /// - `from`: The position to estimate from as a tuple (x, y).
+    /// - `goal`: The goal position as a tuple (x, y).
+    ///
+    /// # Returns
+    /// The largest landmark bound, or `0` if either position is not in the table.
+    pub fn estimate(&self, from: (usize, usize), goal: (usize, usize)) -> usize {
+        self.estimate_keys((from.1, from.0), (goal.1, goal.0))
+    }
+
+    /// Returns the landmark bound between two map keys, see `LandmarkTable::estimate`.
+    pub(crate) fn estimate_keys(&self, from: (usize, usize), goal: (usize, usize)) -> usize {
+        let (Some(&v), Some(&t)) = (self.indices.get(&from), self.indices.get(&goal)) else {
+            return 0;
+        };
+
+        let mut bound = 0;
+        for (from_landmark, to_landmark) in self.from_landmarks.iter().zip(&self.to_landmarks) {
+            // Unreachable distances give no bound
+            if from_landmark[t] != UNREACHABLE && from_landmark[v] != UNREACHABLE {
+                bound = bound.max(from_landmark[t].saturating_sub(from_landmark[v]));
+            }
+            if to_landmark[v] != UNREACHABLE && to_landmark[t] != UNREACHABLE {
+                bound = bound.max(to_landmark[v].saturating_sub(to_landmark[t]));
+            }
+        }
+        bound
+    }
+
+    /// Computes the distances of a new landmark and adds it to the table.
+    fn add_landmark(&mut self, nodes: &HashMap<(usize, usize), Node>, landmark: (usize, usize)) {
+        let forward = self.distances(nodes, landmark, false);
+        let backward = self.distances(nodes, landmark, true);
+        self.landmarks.push(landmark);
+        self.from_landmarks.push(forward);
+        self.to_landmarks.push(backward);
+    }
+
+    /// Returns the cost of every position by index from a map key, or to it if `reverse`.
+    fn distances(
+        &self,
+        nodes: &HashMap<(usize, usize), Node>,
+        source: (usize, usize),
+        reverse: bool,
+    ) -> Vec<usize> {
+        self.shortest_path_tree(nodes, source, reverse).costs
+    }
+
+    /// Runs Dijkstra's algorithm from a map key over the passable nodes.
+    ///
+    /// # Parameters
+    /// - `reverse`: Whether to compute the costs to `source` instead of from it.
+    fn shortest_path_tree(
+        &self,
+        nodes: &HashMap<(usize, usize), Node>,
+        source: (usize, usize),
+        reverse: bool,
+    ) -> ShortestPathTree {
+        let mut edges: Vec<Vec<(usize, usize)>> = vec![Vec::new(); self.positions.len()];
+        for (index, position) in self.positions.iter().enumerate() {
+            let node = &nodes[position];
+            if node.is_blocked {
+                continue;
+            }
+            for (target, cost) in node.edges(nodes) {
+                let Some(&target_index) = self.indices.get(&target) else {
+                    continue;
+                };
+                if nodes[&target].is_blocked {
+                    continue;
+                }
+                if reverse {
+                    edges[target_index].push((index, cost));
+                } else {
+                    edges[index].push((target_index, cost));
+                }
+            }
+        }
+
+        let source = self.indices[&source];
+        let mut tree = ShortestPathTree {
+            costs: vec![UNREACHABLE; self.positions.len()],
+            parents: vec![None; self.positions.len()],
+            order: Vec::new(),
+        };
+        tree.costs[source] = 0;
+        let mut open = BinaryHeap::from([Reverse((0, source))]);
+        while let Some(Reverse((cost, index))) = open.pop() {
+            if cost > tree.costs[index] {
+                continue;
+            }
+            tree.order.push(index);
+            for &(next, step_cost) in &edges[index] {
+                let next_cost = cost.saturating_add(step_cost);
+                if next_cost < tree.costs[next] {
+                    tree.costs[next] = next_cost;
+                    tree.parents[next] = Some(index);
+                    open.push(Reverse((next_cost, next)));
+                }
+            }
+        }
+        tree
+    }
+
+    /// Returns how far every position is from the closest landmark, in either direction.
+    ///
+    /// Without landmarks, distances are measured from the first passable position instead.
+    fn coverage(&self, nodes: &HashMap<(usize, usize), Node>) -> Vec<usize> {
+        if self.landmarks.is_empty() {
+            let first = self
+                .positions
+                .iter()
+                .copied()
+                .find(|position| !nodes[position].is_blocked)
+                .expect("landmarks are only picked among passable nodes");
+            return self.distances(nodes, first, false);
+        }
+
+        (0..self.positions.len())
+            .map(|index| {
+                self.from_landmarks
+                    .iter()
+                    .zip(&self.to_landmarks)
+                    .map(|(from, to)| from[index].min(to[index]))
+                    .min()
+                    .unwrap_or(UNREACHABLE)
+            })
+            .collect()
+    }
+
+    /// Returns the passable position farthest from the landmarks picked so far.
+    ///
+    /// Positions no landmark reaches come first, so every part of a divided map gets a landmark.
+    fn farthest(&self, nodes: &HashMap<(usize, usize), Node>) -> (usize, usize) {
+        let coverage = self.coverage(nodes);
+        let index = (0..self.positions.len())
+            .filter(|&index| {
+                !nodes[&self.positions[index]].is_blocked
+                    && !self.landmarks.contains(&self.positions[index])
+            })
+            .max_by_key(|&index| (coverage[index], Reverse(index)))
+            .expect("fewer landmarks than passable nodes");
+        self.positions[index]
+    }
+
+    /// Picks a landmark with the "avoid" strategy.
+    ///
+    /// Grows a shortest path tree from the position farthest from the landmarks and
+    /// weighs every position by how much the current bound underestimates its cost
+    /// from the root. The landmark is the leaf reached by descending from the
+    /// heaviest subtree without landmarks into its heaviest child.
+    fn avoid(&self, nodes: &HashMap<(usize, usize), Node>) -> (usize, usize) {
+        let root = self.farthest(nodes);
+        if self.landmarks.is_empty() {
+            return root;
+        }
+        let tree = self.shortest_path_tree(nodes, root, false);
+
+        // Every position weighs how much the bound underestimates its cost from the
+        // root, subtrees add up from the leaves and drop out if they hold a landmark
+        let mut weights = vec![0; self.positions.len()];
+        let mut has_landmark = vec![false; self.positions.len()];
+        for &index in &tree.order {
+            let position = self.positions[index];
+            weights[index] = tree.costs[index].saturating_sub(self.estimate_keys(root, position));
+            has_landmark[index] = self.landmarks.contains(&position);
+        }
+        for &index in tree.order.iter().rev() {
+            if let Some(parent) = tree.parents[index] {
+                weights[parent] = weights[parent].saturating_add(weights[index]);
+                has_landmark[parent] |= has_landmark[index];
+            }
+        }
+        let weight = |index: usize| {
+            if has_landmark[index] {
+                0
+            } else {
+                weights[index]
+            }
+        };
+
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); self.positions.len()];
+        for &index in &tree.order {
+            if let Some(parent) = tree.parents[index] {
+                children[parent].push(index);
+            }
+        }
+        let Some(mut current) = tree
+            .order
+            .iter()
+            .copied()
+            .filter(|&index| weight(index) > 0)
+            .max_by_key(|&index| (weight(index), Reverse(index)))
+        else {
+            return root;
+        };
+        while let Some(child) = children[current]
+            .iter()
+            .copied()
+            .filter(|&index| weight(index) > 0)
+            .max_by_key(|&index| (weight(index), Reverse(index)))
+        {
+            current = child;
+        }
+        self.positions[current]
+    }
+}
+
+/// The result of Dijkstra's algorithm over the positions of a `LandmarkTable`, by index.
+struct ShortestPathTree {
+    /// The cost of every position, `UNREACHABLE` where there is no path.
+    costs: Vec<usize>,
+    /// The position every position was reached from.
+    parents: Vec<Option<usize>>,
+    /// The reached positions, in the order they were settled.
+    order: Vec<usize>,
+}
+
+/// Maps every map key to its index in a list of positions.
+pub(crate) fn index_positions(positions: &[(usize, usize)]) -> HashMap<(usize, usize), usize> {
+    positions
+        .iter()
+        .enumerate()
+        .map(|(index, &position)| (position, index))
+        .collect()
+}
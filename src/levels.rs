@@ -0,0 +1,421 @@
+use crate::astar::{weakly_connected, AStarError, Unreachable, UnreachableReason};
+use crate::heuristic::Heuristic;
+use crate::node::Node;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::OnceLock;
+
+/// A position on a multi-level map as `(level, x, y)`.
+///
+/// `x` and `y` are given in the same order as for `AStar::find_shortest_path`.
+pub type LevelPosition = (usize, usize, usize);
+
+/// Kinds of links that connect positions across levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LinkKind {
+    Stairs,
+    Elevator,
+    Portal,
+}
+
+/// A directed link from one position of a `MultiLevelMap` to another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LevelLink {
+    pub to: LevelPosition,
+    pub cost: usize,
+    pub kind: LinkKind,
+}
+
+/// A map made of several 2D levels connected by stairs, elevators and portals.
+///
+/// Each level is a regular node map as produced by `Node::grid_to_nodes`. Moving
+/// to an adjacent node of a level costs that node's `Node::cost`, moving along a
+/// custom edge or a link costs the edge's or link's cost.
+#[derive(Debug, Clone, Default)]
+pub struct MultiLevelMap {
+    levels: Vec<HashMap<(usize, usize), Node>>,
+    links: HashMap<LevelPosition, Vec<LevelLink>>,
+    /// Bounds of `estimate`, derived on first use and dropped whenever the map changes.
+    heuristic: OnceLock<LevelHeuristic>,
+}
+
+impl MultiLevelMap {
+    /// Creates a new, empty `MultiLevelMap`.
+    ///
+    /// # Returns
+    /// A new `MultiLevelMap` without levels or links.
+    pub fn new() -> Self {
+        MultiLevelMap {
+            levels: Vec::new(),
+            links: HashMap::new(),
+            heuristic: OnceLock::new(),
+        }
+    }
+
+    /// Adds a level on top of the existing ones.
+    ///
+    /// # Parameters
+    /// - `nodes`: The node map of the new level.
+    ///
+    /// # Returns
+    /// The index of the new level.
+    pub fn add_level(&mut self, nodes: HashMap<(usize, usize), Node>) -> usize {
+        self.heuristic.take();
+        self.levels.push(nodes);
+        self.levels.len() - 1
+    }
+
+    /// Returns the number of levels.
+    pub fn level_count(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Returns the node map of a level, if it exists.
+    pub fn level(&self, level: usize) -> Option<&HashMap<(usize, usize), Node>> {
+        self.levels.get(level)
+    }
+
+    /// Returns the mutable node map of a level, if it exists.
+    ///
+    /// Use this to block nodes or adjust neighbors of a single level.
+    pub fn level_mut(&mut self, level: usize) -> Option<&mut HashMap<(usize, usize), Node>> {
+        self.heuristic.take();
+        self.levels.get_mut(level)
+    }
+
+    /// Adds a one-way link between two positions.
+    ///
+    /// # Parameters
+    /// - `from`: The position the link starts at.
+    /// - `to`: The position the link leads to.
+    /// - `cost`: The cost of moving along the link.
+    /// - `kind`: The kind of the link.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{LinkKind, MultiLevelMap, Node};
+    ///
+    /// let mut map = MultiLevelMap::new();
+    /// map.add_level(Node::grid_to_nodes(&vec![vec![0; 3]; 3]));
+    /// map.add_level(Node::grid_to_nodes(&vec![vec![0; 3]; 3]));
+    ///
+    /// // A chute that only leads down
+    /// map.add_link((1, 2, 2), (0, 2, 2), 1, LinkKind::Portal);
+    /// ```
+    pub fn add_link(
+        &mut self,
+        from: LevelPosition,
+        to: LevelPosition,
+        cost: usize,
+        kind: LinkKind,
+    ) {
+        self.heuristic.take();
+        self.links
+            .entry(from)
+            .or_default()
+            .push(LevelLink { to, cost, kind });
+    }
+
+    /// Adds a link in both directions between two positions, such as a staircase.
+    ///
+    /// # Parameters
+    /// - `a`: The first position.
+    /// - `b`: The second position.
+    /// - `cost`: The cost of moving along the link in either direction.
+    /// - `kind`: The kind of the link.
+    pub fn add_two_way_link(
+        &mut self,
+        a: LevelPosition,
+        b: LevelPosition,
+        cost: usize,
+        kind: LinkKind,
+    ) {
+        self.add_link(a, b, cost, kind);
+        self.add_link(b, a, cost, kind);
+    }
+
+    /// Adds an elevator shaft at `(x, y)` serving the given levels.
+    ///
+    /// Every pair of served levels is linked in both directions. Riding the
+    /// elevator costs `cost_per_level` for every level travelled.
+    ///
+    /// # Parameters
+    /// - `x`, `y`: The position of the shaft on every served level.
+    /// - `levels`: The levels the elevator stops at.
+    /// - `cost_per_level`: The cost of travelling one level.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{MultiLevelMap, Node};
+    ///
+    /// let mut map = MultiLevelMap::new();
+    /// for _ in 0..3 {
+    ///     map.add_level(Node::grid_to_nodes(&vec![vec![0; 3]; 3]));
+    /// }
+    /// map.add_elevator(1, 1, &[0, 1, 2], 2);
+    /// assert_eq!(map.links((0, 1, 1)).len(), 2);
+    /// ```
+    pub fn add_elevator(&mut self, x: usize, y: usize, levels: &[usize], cost_per_level: usize) {
+        for &from in levels {
+            for &to in levels {
+                if from != to {
+                    self.add_link(
+                        (from, x, y),
+                        (to, x, y),
+                        from.abs_diff(to) * cost_per_level,
+                        LinkKind::Elevator,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Returns the links leaving a position.
+    pub fn links(&self, from: LevelPosition) -> &[LevelLink] {
+        self.links.get(&from).map_or(&[], |links| links.as_slice())
+    }
+
+    /// Returns the node at a position, if it exists.
+    pub fn node(&self, position: LevelPosition) -> Option<&Node> {
+        let (level, x, y) = position;
+        self.levels.get(level)?.get(&(y, x))
+    }
+
+    /// Estimates the remaining cost from `from` to `goal`.
+    ///
+    /// The estimate is the larger of two lower bounds, so it never overestimates:
+    /// - the number of levels to travel times the cheapest cost per level of any link;
+    /// - the planar Manhattan distance, or the octile distance if a level has
+    ///   diagonal edges cheaper than two straight steps, but only if no other edge
+    ///   or link covers more planar distance than its cost (otherwise a portal
+    ///   could be a shortcut).
+    ///
+    /// The bounds are derived from the whole map once and reused until the map changes.
+    ///
+    /// # Parameters
+    /// - `from`: The position to estimate from.
+    /// - `goal`: The goal position.
+    ///
+    /// # Returns
+    /// An admissible estimate of the remaining cost.
+    pub fn estimate(&self, from: LevelPosition, goal: LevelPosition) -> usize {
+        self.heuristic().estimate(from, goal)
+    }
+
+    /// Returns the bounds of `estimate`, deriving them from the map on first use.
+    fn heuristic(&self) -> &LevelHeuristic {
+        self.heuristic.get_or_init(|| LevelHeuristic::new(self))
+    }
+
+    /// Validates that the start and goal nodes exist and are not blocked.
+    fn validate_nodes(&self, start: LevelPosition, goal: LevelPosition) -> Result<(), AStarError> {
+        match self.node(start) {
+            None => return Err(AStarError::NodeNotFound((start.1, start.2))),
+            Some(node) if node.is_blocked => {
+                return Err(AStarError::StartNodeBlocked((start.1, start.2)))
+            }
+            _ => {}
+        }
+        match self.node(goal) {
+            None => Err(AStarError::NodeNotFound((goal.1, goal.2))),
+            Some(node) if node.is_blocked => Err(AStarError::GoalNodeBlocked((goal.1, goal.2))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Finds the positions reachable in one move from `position` and their costs.
+    fn successors(&self, position: LevelPosition) -> Vec<(LevelPosition, usize)> {
+        let (level, x, y) = position;
+        let mut successors = Vec::new();
+
+        // Neighbors and custom edges on the same level, stored with map keys (y, x)
+        if let (Some(node), Some(nodes)) = (self.node(position), self.levels.get(level)) {
+            for ((ny, nx), cost) in node.edges(nodes) {
+                successors.push(((level, nx, ny), cost));
+            }
+        }
+
+        // Links to other positions
+        for link in self.links((level, x, y)) {
+            successors.push((link.to, link.cost));
+        }
+
+        successors.retain(|&(pos, _)| self.node(pos).is_some_and(|node| !node.is_blocked));
+        successors
+    }
+
+    /// Finds the cheapest path from start to goal across all levels.
+    ///
+    /// # Parameters
+    /// - `start`: The starting position as `(level, x, y)`.
+    /// - `goal`: The goal position as `(level, x, y)`.
+    ///
+    /// # Returns
+    /// The positions of the path from the start to the goal, or an `AStarError` if no path
+    /// is found or if an error occurs.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{LinkKind, MultiLevelMap, Node};
+    ///
+    /// let mut map = MultiLevelMap::new();
+    /// map.add_level(Node::grid_to_nodes(&vec![vec![0; 3]; 3]));
+    /// map.add_level(Node::grid_to_nodes(&vec![vec![0; 3]; 3]));
+    /// map.add_two_way_link((0, 2, 0), (1, 2, 0), 3, LinkKind::Stairs);
+    ///
+    /// let path = map.find_shortest_path((0, 0, 0), (1, 0, 0)).unwrap();
+    /// assert_eq!(path.first(), Some(&(0, 0, 0)));
+    /// assert_eq!(path.last(), Some(&(1, 0, 0)));
+    /// ```
+    pub fn find_shortest_path(
+        &self,
+        start: LevelPosition,
+        goal: LevelPosition,
+    ) -> Result<Vec<LevelPosition>, AStarError> {
+        self.validate_nodes(start, goal)?;
+
+        let mut open_set = BinaryHeap::new();
+        let mut came_from: HashMap<LevelPosition, LevelPosition> = HashMap::new();
+        let mut g_score: HashMap<LevelPosition, usize> = HashMap::new();
+
+        let heuristic = self.heuristic();
+        g_score.insert(start, 0);
+        open_set.push(Reverse((heuristic.estimate(start, goal), start)));
+
+        while let Some(Reverse((_, position))) = open_set.pop() {
+            let mut current = position;
+
+            if current == goal {
+                // Trace the path back to the start
+                let mut path = vec![goal];
+                while let Some(&previous) = came_from.get(&current) {
+                    path.push(previous);
+                    current = previous;
+                }
+                path.reverse();
+                return Ok(path);
+            }
+
+            let current_g = g_score[&current];
+            for (neighbor, cost) in self.successors(current) {
+                let tentative_g = current_g + cost;
+                if tentative_g < *g_score.get(&neighbor).unwrap_or(&usize::MAX) {
+                    came_from.insert(neighbor, current);
+                    g_score.insert(neighbor, tentative_g);
+                    open_set.push(Reverse((
+                        tentative_g + heuristic.estimate(neighbor, goal),
+                        neighbor,
+                    )));
+                }
+            }
+        }
+
+        // Every reachable position has a g-score now, explain why the goal is not among them
+        let passable = self.levels.iter().enumerate().flat_map(|(level, nodes)| {
+            nodes
+                .iter()
+                .filter(|(_, node)| !node.is_blocked)
+                .map(move |(&(y, x), _)| (level, x, y))
+        });
+        let successors = |position| {
+            self.successors(position)
+                .into_iter()
+                .map(|(neighbor, _)| neighbor)
+                .collect()
+        };
+        let reason = if weakly_connected(&[start], &[goal], passable, successors) {
+            UnreachableReason::OneWayEdges
+        } else {
+            UnreachableReason::DisconnectedComponents
+        };
+        let closest = g_score
+            .iter()
+            .min_by_key(|&(&position, &g)| (heuristic.estimate(position, goal), g, position))
+            .map_or(start, |(&position, _)| position);
+
+        Err(AStarError::PathNotFound(Unreachable {
+            reason,
+            closest: (closest.1, closest.2),
+            level: Some(closest.0),
+            explored: g_score.len(),
+        }))
+    }
+}
+
+/// Lower bounds derived from the links of a `MultiLevelMap`.
+#[derive(Debug, Clone)]
+struct LevelHeuristic {
+    min_cost_per_level: Option<usize>,
+    planar: Option<Heuristic>,
+}
+
+impl LevelHeuristic {
+    /// Derives the bounds from all links and node edges of a map.
+    fn new(map: &MultiLevelMap) -> Self {
+        let mut min_cost_per_level = None;
+        for (&source, links) in &map.links {
+            for link in links {
+                // Cheapest cost of travelling a single level
+                let levels = source.0.abs_diff(link.to.0);
+                if let Some(per_level) = link.cost.checked_div(levels) {
+                    min_cost_per_level =
+                        Some(min_cost_per_level.map_or(per_level, |min: usize| min.min(per_level)));
+                }
+            }
+        }
+
+        // Diagonal steps cheaper than two straight ones turn the planar bound into
+        // an octile one instead of disabling it
+        let edges = || {
+            map.levels.iter().flat_map(|nodes| {
+                nodes.iter().flat_map(move |(&position, node)| {
+                    node.edges(nodes)
+                        .into_iter()
+                        .map(move |(target, cost)| (position, target, cost))
+                })
+            })
+        };
+        let min_diagonal = edges()
+            .filter(|&(position, target, cost)| {
+                position.0.abs_diff(target.0) == 1 && position.1.abs_diff(target.1) == 1 && cost < 2
+            })
+            .map(|(_, _, cost)| cost)
+            .min();
+        let planar = match min_diagonal {
+            Some(diagonal) => Heuristic::Octile {
+                straight: diagonal.min(1),
+                diagonal,
+            },
+            None => Heuristic::Manhattan,
+        };
+
+        // Any other edge or link covering more planar distance than it costs is a
+        // shortcut, such as a teleporter or a portal
+        let link_shortcut = map.links.iter().any(|(&source, links)| {
+            links.iter().any(|link| {
+                planar.estimate((source.1, source.2), (link.to.1, link.to.2)) > link.cost
+            })
+        });
+        let edge_shortcut =
+            edges().any(|(position, target, cost)| planar.estimate(position, target) > cost);
+
+        LevelHeuristic {
+            min_cost_per_level,
+            planar: (!link_shortcut && !edge_shortcut).then_some(planar),
+        }
+    }
+
+    /// Estimates the remaining cost from `from` to `goal`.
+    fn estimate(&self, from: LevelPosition, goal: LevelPosition) -> usize {
+        let level_bound = self
+            .min_cost_per_level
+            .map_or(0, |cost| from.0.abs_diff(goal.0) * cost);
+        let planar_bound = self.planar.map_or(0, |planar| {
+            planar.estimate((from.1, from.2), (goal.1, goal.2))
+        });
+
+        level_bound.max(planar_bound)
+    }
+}
@@ -1,7 +1,51 @@
+pub mod alternatives;
+pub mod ascii_map;
 pub mod astar;
+pub mod components;
+#[cfg(feature = "serde")]
+pub mod document;
+pub mod heuristic;
+pub mod hex;
+pub mod hierarchy;
+pub mod landmarks;
+pub mod levels;
+pub mod movingai;
 pub mod node;
+pub mod occupancy;
+pub mod path;
+pub mod path_database;
 pub mod priority_queue;
+pub mod route;
+pub mod smoothing;
+pub mod svg;
+pub mod text_renderer;
+pub mod tiled;
+pub mod validate;
 
-pub use astar::{AStar, AStarError};
-pub use node::{Direction, Node};
+pub use alternatives::{Alternative, AlternativeOptions};
+pub use ascii_map::{AsciiMap, AsciiMapError, AsciiMapErrorKind, Legend, Tile};
+pub use astar::{
+    AStar, AStarError, AnytimeOptions, AnytimePath, IdaOptions, Unreachable, UnreachableReason,
+};
+pub use components::ComponentIndex;
+#[cfg(feature = "serde")]
+pub use document::{DocumentError, MapDocument, MAP_DOCUMENT_VERSION};
+pub use heuristic::Heuristic;
+pub use hex::{HexCoord, HexDirection, HexLayout};
+pub use hierarchy::HierarchicalMap;
+pub use landmarks::{LandmarkStrategy, LandmarkTable};
+pub use levels::{LevelLink, LevelPosition, LinkKind, MultiLevelMap};
+pub use movingai::{
+    octile_length, MovingAiError, MovingAiErrorKind, MovingAiMap, Scenario, ScenarioResult,
+};
+pub use node::{CustomEdge, Direction, Node};
+pub use occupancy::{MapMetadata, Occupancy, OccupancyError, OccupancyGrid, OccupancyOptions};
+pub use path::{Path, Segment};
+pub use path_database::PathDatabase;
 pub use priority_queue::{PriorityQueue, State};
+pub use route::{LegError, Route, WaypointOrder};
+pub use smoothing::PathSmoother;
+pub use svg::{EdgeDisplay, SvgOptions, SvgRenderer};
+pub use text_renderer::{Glyphs, TextOptions, TextRenderer};
+pub use tiled::{TiledError, TiledMap, TiledOptions};
+pub use validate::{validate, DirectionConvention, ValidationIssue, ValidationOptions};
@@ -0,0 +1,416 @@
+use crate::astar::AStar;
+use crate::heuristic::Heuristic;
+use crate::node::Node;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// Cost of a straight step on a MovingAI map.
+///
+/// Costs are integers, so octile distances are scaled: a straight step costs
+/// `OCTILE_STRAIGHT_COST` and a diagonal step `OCTILE_DIAGONAL_COST`.
+pub const OCTILE_STRAIGHT_COST: usize = 1_000_000;
+
+/// Cost of a diagonal step on a MovingAI map, `√2` scaled like `OCTILE_STRAIGHT_COST`.
+pub const OCTILE_DIAGONAL_COST: usize = 1_414_214;
+
+/// Label of the custom edges used for diagonal steps on MovingAI maps.
+pub const DIAGONAL_EDGE_LABEL: &str = "diagonal";
+
+/// Heuristic matching the costs of MovingAI maps.
+pub const OCTILE_HEURISTIC: Heuristic = Heuristic::Octile {
+    straight: OCTILE_STRAIGHT_COST,
+    diagonal: OCTILE_DIAGONAL_COST,
+};
+
+/// Default tolerance when comparing path lengths with the reference lengths of a scenario.
+pub const LENGTH_TOLERANCE: f64 = 1e-3;
+
+/// Kinds of errors that can occur while reading MovingAI files.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MovingAiErrorKind {
+    /// A header line (`type`, `height`, `width` or `map`) is missing or malformed.
+    InvalidHeader(String),
+    /// The map uses a type other than `octile`.
+    UnsupportedType(String),
+    /// A terrain character that is not part of the format.
+    UnknownTerrain { column: usize, character: char },
+    /// A map line whose length differs from the `width` header.
+    RaggedLine { expected: usize, found: usize },
+    /// The map has fewer lines than the `height` header.
+    MissingLines { expected: usize, found: usize },
+    /// The scenario file does not start with a `version` line.
+    InvalidVersion(String),
+    /// A scenario line with a missing or malformed field.
+    InvalidScenario(String),
+}
+
+/// An error while reading a MovingAI file, with the 1-based line it occurred at.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MovingAiError {
+    pub line: usize,
+    pub kind: MovingAiErrorKind,
+}
+
+impl fmt::Display for MovingAiError {
+    /// Formats the error for display in user-facing contexts.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            MovingAiErrorKind::InvalidHeader(line) => write!(f, "invalid header {:?}", line),
+            MovingAiErrorKind::UnsupportedType(kind) => {
+                write!(f, "unsupported map type {:?}", kind)
+            }
+            MovingAiErrorKind::UnknownTerrain { column, character } => {
+                write!(f, "unknown terrain {:?} at column {}", character, column)
+            }
+            MovingAiErrorKind::RaggedLine { expected, found } => {
+                write!(f, "expected {} cells but found {}", expected, found)
+            }
+            MovingAiErrorKind::MissingLines { expected, found } => {
+                write!(f, "expected {} map lines but found {}", expected, found)
+            }
+            MovingAiErrorKind::InvalidVersion(line) => write!(f, "invalid version {:?}", line),
+            MovingAiErrorKind::InvalidScenario(line) => {
+                write!(f, "invalid scenario {:?}", line)
+            }
+        }
+    }
+}
+
+impl Error for MovingAiError {}
+
+/// A map in the MovingAI benchmark `.map` format.
+///
+/// The terrain characters are read as follows:
+/// - `.`, `G` and `S` (swamp) are passable;
+/// - `@`, `O`, `T` (trees) and `W` (water) are blocked.
+///
+/// Nodes are keyed like in `Node::grid_to_nodes`. They are connected in all
+/// eight directions: straight steps cost `OCTILE_STRAIGHT_COST` (stored as the
+/// nodes' cost), diagonal steps cost `OCTILE_DIAGONAL_COST` (stored as custom
+/// edges labelled `DIAGONAL_EDGE_LABEL`). Diagonal steps must not cut corners,
+/// so both adjacent straight cells have to be passable.
+#[derive(Debug, Clone)]
+pub struct MovingAiMap {
+    pub width: usize,
+    pub height: usize,
+    pub nodes: HashMap<(usize, usize), Node>,
+}
+
+impl MovingAiMap {
+    /// Parses a MovingAI `.map` file.
+    ///
+    /// # Parameters
+    /// - `text`: The content of the file.
+    ///
+    /// # Returns
+    /// The parsed `MovingAiMap`, or a `MovingAiError` pointing at the offending line.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::MovingAiMap;
+    ///
+    /// let text = "type octile\nheight 2\nwidth 3\nmap\n..@\n.T.\n";
+    /// let map = MovingAiMap::parse(text).unwrap();
+    /// assert_eq!((map.width, map.height), (3, 2));
+    /// assert!(map.nodes[&(0, 2)].is_blocked);
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, MovingAiError> {
+        let mut lines = text.lines().map(|line| line.trim_end_matches('\r'));
+
+        let kind = header_value(lines.next(), 1, "type")?;
+        if kind != "octile" {
+            return Err(MovingAiError {
+                line: 1,
+                kind: MovingAiErrorKind::UnsupportedType(kind.to_string()),
+            });
+        }
+        let height = parse_dimension(header_value(lines.next(), 2, "height")?, 2)?;
+        let width = parse_dimension(header_value(lines.next(), 3, "width")?, 3)?;
+        match lines.next() {
+            Some("map") => {}
+            other => {
+                return Err(MovingAiError {
+                    line: 4,
+                    kind: MovingAiErrorKind::InvalidHeader(other.unwrap_or_default().to_string()),
+                })
+            }
+        }
+
+        // Read the terrain as a grid of passable cells
+        let mut passable = Vec::with_capacity(height);
+        for (row, line) in lines.take(height).enumerate() {
+            let line_number = row + 5;
+            let mut cells = Vec::with_capacity(width);
+            for (column, character) in line.chars().enumerate() {
+                cells.push(match character {
+                    '.' | 'G' | 'S' => true,
+                    '@' | 'O' | 'T' | 'W' => false,
+                    _ => {
+                        return Err(MovingAiError {
+                            line: line_number,
+                            kind: MovingAiErrorKind::UnknownTerrain {
+                                column: column + 1,
+                                character,
+                            },
+                        })
+                    }
+                });
+            }
+            if cells.len() != width {
+                return Err(MovingAiError {
+                    line: line_number,
+                    kind: MovingAiErrorKind::RaggedLine {
+                        expected: width,
+                        found: cells.len(),
+                    },
+                });
+            }
+            passable.push(cells);
+        }
+        if passable.len() != height {
+            return Err(MovingAiError {
+                line: passable.len() + 5,
+                kind: MovingAiErrorKind::MissingLines {
+                    expected: height,
+                    found: passable.len(),
+                },
+            });
+        }
+
+        Ok(MovingAiMap {
+            width,
+            height,
+            nodes: octile_nodes(&passable),
+        })
+    }
+
+    /// Runs every scenario on this map with `AStar` and compares the path lengths
+    /// with the reference lengths.
+    ///
+    /// # Parameters
+    /// - `scenarios`: The scenarios to run.
+    ///
+    /// # Returns
+    /// One `ScenarioResult` per scenario, in the same order.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{MovingAiMap, Scenario};
+    ///
+    /// let map = MovingAiMap::parse("type octile\nheight 2\nwidth 2\nmap\n..\n..\n").unwrap();
+    /// let scenarios = Scenario::parse_all("version 1\n0\tm.map\t2\t2\t0\t0\t1\t1\t1.41421356\n").unwrap();
+    /// let results = map.run_scenarios(&scenarios);
+    /// assert!(results.iter().all(|result| result.is_optimal()));
+    /// ```
+    pub fn run_scenarios(&self, scenarios: &[Scenario]) -> Vec<ScenarioResult> {
+        let mut astar = AStar::new(self.nodes.clone());
+        astar.set_heuristic(OCTILE_HEURISTIC);
+
+        scenarios
+            .iter()
+            .map(|scenario| {
+                let length = match astar.find_shortest_path(scenario.start, scenario.goal) {
+                    Ok(path) => Some(octile_length(path.positions())),
+                    _ => None,
+                };
+                ScenarioResult {
+                    scenario: scenario.clone(),
+                    length,
+                }
+            })
+            .collect()
+    }
+}
+
+/// A single query from a MovingAI `.scen` file.
+///
+/// `start` and `goal` are given as `(x, y)`, ready to be passed to `AStar::find_shortest_path`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scenario {
+    pub bucket: usize,
+    pub map: String,
+    pub map_width: usize,
+    pub map_height: usize,
+    pub start: (usize, usize),
+    pub goal: (usize, usize),
+    pub optimal_length: f64,
+}
+
+impl Scenario {
+    /// Parses all scenarios of a MovingAI `.scen` file.
+    ///
+    /// # Parameters
+    /// - `text`: The content of the file, starting with a `version` line.
+    ///
+    /// # Returns
+    /// The scenarios in file order, or a `MovingAiError` pointing at the offending line.
+    pub fn parse_all(text: &str) -> Result<Vec<Scenario>, MovingAiError> {
+        let mut lines = text.lines().map(|line| line.trim_end_matches('\r'));
+
+        match lines
+            .next()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>())
+        {
+            Some(fields) if fields.len() == 2 && fields[0] == "version" => {}
+            _ => {
+                return Err(MovingAiError {
+                    line: 1,
+                    kind: MovingAiErrorKind::InvalidVersion(
+                        text.lines().next().unwrap_or_default().to_string(),
+                    ),
+                })
+            }
+        }
+
+        lines
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                Scenario::parse_line(line).ok_or_else(|| MovingAiError {
+                    line: index + 2,
+                    kind: MovingAiErrorKind::InvalidScenario(line.to_string()),
+                })
+            })
+            .collect()
+    }
+
+    /// Parses a single tab-separated scenario line.
+    fn parse_line(line: &str) -> Option<Scenario> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 9 {
+            return None;
+        }
+        let number = |index: usize| fields[index].trim().parse::<usize>().ok();
+
+        Some(Scenario {
+            bucket: number(0)?,
+            map: fields[1].to_string(),
+            map_width: number(2)?,
+            map_height: number(3)?,
+            start: (number(4)?, number(5)?),
+            goal: (number(6)?, number(7)?),
+            optimal_length: fields[8].trim().parse().ok()?,
+        })
+    }
+}
+
+/// The outcome of running a single scenario.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioResult {
+    pub scenario: Scenario,
+    /// Octile length of the path found, or `None` if no path was found.
+    pub length: Option<f64>,
+}
+
+impl ScenarioResult {
+    /// Checks whether the path found matches the reference length within `LENGTH_TOLERANCE`.
+    pub fn is_optimal(&self) -> bool {
+        self.is_within(LENGTH_TOLERANCE)
+    }
+
+    /// Checks whether the path found matches the reference length within a tolerance.
+    ///
+    /// # Parameters
+    /// - `tolerance`: The largest accepted absolute difference.
+    pub fn is_within(&self, tolerance: f64) -> bool {
+        self.length
+            .is_some_and(|length| (length - self.scenario.optimal_length).abs() <= tolerance)
+    }
+}
+
+/// Calculates the octile length of a path, counting diagonal steps as `√2`.
+///
+/// # Parameters
+/// - `path`: The positions of the path.
+///
+/// # Returns
+/// The length of the path.
+///
+/// # Example
+/// ```rust
+/// use controlled_astar::octile_length;
+///
+/// let length = octile_length(&[(0, 0), (1, 0), (2, 1)]);
+/// assert!((length - (1.0 + 2f64.sqrt())).abs() < 1e-9);
+/// ```
+pub fn octile_length(path: &[(usize, usize)]) -> f64 {
+    path.windows(2)
+        .map(|step| {
+            let diagonal = step[0].0 != step[1].0 && step[0].1 != step[1].1;
+            if diagonal {
+                std::f64::consts::SQRT_2
+            } else {
+                1.0
+            }
+        })
+        .sum()
+}
+
+/// Reads the value of a `key value` header line.
+fn header_value<'a>(
+    line: Option<&'a str>,
+    number: usize,
+    key: &str,
+) -> Result<&'a str, MovingAiError> {
+    let error = || MovingAiError {
+        line: number,
+        kind: MovingAiErrorKind::InvalidHeader(line.unwrap_or_default().to_string()),
+    };
+    let (found, value) = line
+        .and_then(|line| line.split_once(' '))
+        .ok_or_else(error)?;
+    if found != key {
+        return Err(error());
+    }
+    Ok(value.trim())
+}
+
+/// Parses the value of a `height` or `width` header.
+fn parse_dimension(value: &str, line: usize) -> Result<usize, MovingAiError> {
+    value.parse().map_err(|_| MovingAiError {
+        line,
+        kind: MovingAiErrorKind::InvalidHeader(value.to_string()),
+    })
+}
+
+/// Builds eight-connected nodes with octile costs from a grid of passable cells.
+fn octile_nodes(passable: &[Vec<bool>]) -> HashMap<(usize, usize), Node> {
+    let height = passable.len();
+    let width = passable.first().map_or(0, |row| row.len());
+    let is_passable = |row: isize, column: isize| {
+        row >= 0
+            && column >= 0
+            && passable
+                .get(row as usize)
+                .and_then(|cells| cells.get(column as usize))
+                .copied()
+                .unwrap_or(false)
+    };
+
+    let mut nodes = HashMap::new();
+    for (x, cells) in passable.iter().enumerate() {
+        for (y, &open) in cells.iter().enumerate() {
+            let mut node = Node::new(x, y, !open, height - 1, width - 1);
+            node.set_cost(OCTILE_STRAIGHT_COST);
+
+            // Diagonal steps, allowed only if they do not cut a corner
+            for (dx, dy) in [(-1, -1), (-1, 1), (1, -1), (1, 1)] {
+                let (row, column) = (x as isize + dx, y as isize + dy);
+                if is_passable(row, column)
+                    && is_passable(row, y as isize)
+                    && is_passable(x as isize, column)
+                {
+                    node.add_custom_edge(
+                        (row as usize, column as usize),
+                        OCTILE_DIAGONAL_COST,
+                        DIAGONAL_EDGE_LABEL,
+                    );
+                }
+            }
+            nodes.insert((x, y), node);
+        }
+    }
+    nodes
+}
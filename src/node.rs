@@ -1,9 +1,11 @@
+use crate::text_renderer::TextRenderer;
 use std::cmp::{Ord, PartialOrd};
 use std::collections::{BTreeMap, HashMap};
 use std::hash::{Hash, Hasher};
 
 /// Directions used in nodes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     North,
     South,
@@ -15,13 +17,51 @@ pub enum Direction {
     SouthWest,
 }
 
+impl Direction {
+    /// Returns the direction pointing the other way.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::Direction;
+    ///
+    /// assert_eq!(Direction::NorthEast.opposite(), Direction::SouthWest);
+    /// ```
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+            Direction::East => Direction::West,
+            Direction::NorthEast => Direction::SouthWest,
+            Direction::NorthWest => Direction::SouthEast,
+            Direction::SouthEast => Direction::NorthWest,
+            Direction::SouthWest => Direction::NorthEast,
+        }
+    }
+}
+
+/// An extra link from a node to any other node, such as a teleporter or a jump pad.
+///
+/// Unlike the entries of `Node::neighbors`, custom edges are not tied to a direction,
+/// can lead to distant nodes and carry their own cost.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CustomEdge {
+    pub target: (usize, usize),
+    pub cost: usize,
+    pub label: String,
+}
+
 /// Represents a node on a map.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Node {
     pub x: usize,
     pub y: usize,
     pub is_blocked: bool,
     pub neighbors: BTreeMap<Direction, Option<(usize, usize)>>,
+    pub custom_edges: Vec<CustomEdge>,
+    pub cost: usize,
 }
 
 impl Node {
@@ -40,7 +80,7 @@ impl Node {
     /// # Examples
     ///
     /// ```
-    /// use my_crate::Node;
+    /// use controlled_astar::Node;
     ///
     /// // Create a new `Node` at position (2, 3) which is not blocked
     /// let node = Node::new(2, 3, false, 10, 10);
@@ -76,6 +116,8 @@ impl Node {
             y,
             is_blocked,
             neighbors,
+            custom_edges: Vec::new(),
+            cost: 1,
         }
     }
 
@@ -87,6 +129,8 @@ impl Node {
     ///
     /// # Example
     /// ```rust
+    /// use controlled_astar::{Direction, Node};
+    ///
     /// let mut node = Node::new(0, 0, false, 10, 10);
     /// node.set_neighbor(Direction::North, Some((0, 1)));
     /// ```
@@ -102,6 +146,8 @@ impl Node {
     ///
     /// # Example
     /// ```rust
+    /// use controlled_astar::{Direction, Node};
+    ///
     /// let mut node = Node::new(0, 0, false, 10, 10);
     /// node.remove_neighbor(Direction::North);
     /// ```
@@ -110,6 +156,111 @@ impl Node {
         self.neighbors.remove(&direction);
     }
 
+    /// Adds a custom edge from this node to any other node.
+    ///
+    /// Custom edges are followed by the A* algorithm in addition to the directional neighbors.
+    ///
+    /// # Parameters
+    /// - `target`: The position of the node the edge leads to.
+    /// - `cost`: The cost of moving along the edge.
+    /// - `label`: A label describing the edge, such as `"teleporter"`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::Node;
+    ///
+    /// let mut node = Node::new(0, 0, false, 10, 10);
+    /// node.add_custom_edge((9, 9), 2, "teleporter");
+    /// assert_eq!(node.custom_edges.len(), 1);
+    /// ```
+    pub fn add_custom_edge(
+        &mut self,
+        target: (usize, usize),
+        cost: usize,
+        label: impl Into<String>,
+    ) {
+        self.custom_edges.push(CustomEdge {
+            target,
+            cost,
+            label: label.into(),
+        });
+    }
+
+    /// Removes all custom edges with the given label.
+    ///
+    /// # Parameters
+    /// - `label`: The label of the edges to remove.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::Node;
+    ///
+    /// let mut node = Node::new(0, 0, false, 10, 10);
+    /// node.add_custom_edge((9, 9), 2, "teleporter");
+    /// node.remove_custom_edges("teleporter");
+    /// assert!(node.custom_edges.is_empty());
+    /// ```
+    pub fn remove_custom_edges(&mut self, label: &str) {
+        self.custom_edges.retain(|edge| edge.label != label);
+    }
+
+    /// Returns the positions this node links to together with the cost of each move.
+    ///
+    /// Moving to a directional neighbor costs the neighbor's `cost`, moving along a
+    /// custom edge costs the edge's cost. Targets missing from `nodes` are skipped,
+    /// blocked targets are not filtered out.
+    ///
+    /// # Parameters
+    /// - `nodes`: The map this node belongs to.
+    ///
+    /// # Returns
+    /// A vector of `(position, cost)` pairs.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::Node;
+    ///
+    /// let mut nodes = Node::grid_to_nodes(&vec![vec![0; 10]; 10]);
+    /// let mut node = nodes[&(0, 0)].clone();
+    /// node.add_custom_edge((9, 9), 5, "jump pad");
+    /// assert!(node.edges(&nodes).contains(&((9, 9), 5)));
+    /// ```
+    pub fn edges(&self, nodes: &HashMap<(usize, usize), Node>) -> Vec<((usize, usize), usize)> {
+        let neighbors = self
+            .neighbors
+            .values()
+            .flatten()
+            .filter_map(|pos| nodes.get(pos).map(|node| (*pos, node.cost)));
+        let custom_edges = self
+            .custom_edges
+            .iter()
+            .filter(|edge| nodes.contains_key(&edge.target))
+            .map(|edge| (edge.target, edge.cost));
+
+        neighbors.chain(custom_edges).collect()
+    }
+
+    /// Sets the cost of moving onto this node from one of its directional neighbors.
+    ///
+    /// The default cost is `1`. Higher costs model rough terrain that is passable but
+    /// should be avoided.
+    ///
+    /// # Parameters
+    /// - `cost`: The cost of entering the node.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::Node;
+    ///
+    /// let mut node = Node::new(0, 0, false, 10, 10);
+    /// node.set_cost(5);
+    /// assert_eq!(node.cost, 5);
+    /// ```
+    pub fn set_cost(&mut self, cost: usize) {
+        // Update the cost of entering the node
+        self.cost = cost;
+    }
+
     /// Sets whether the node is blocked or not.
     ///
     /// # Parameters
@@ -117,6 +268,8 @@ impl Node {
     ///
     /// # Example
     /// ```rust
+    /// use controlled_astar::Node;
+    ///
     /// let mut node = Node::new(0, 0, false, 10, 10);
     /// node.set_blocked(true);
     /// ```
@@ -132,6 +285,8 @@ impl Node {
     ///
     /// # Example
     /// ```rust
+    /// use controlled_astar::Node;
+    ///
     /// let node = Node::new(0, 0, false, 10, 10);
     /// let directions = node.get_directions();
     /// ```
@@ -150,6 +305,8 @@ impl Node {
     ///
     /// # Example
     /// ```rust
+    /// use controlled_astar::Node;
+    ///
     /// let grid = vec![
     ///     vec![0, 1, 0],
     ///     vec![0, 0, 1],
@@ -176,44 +333,35 @@ impl Node {
 
     /// Prints a 2D grid and an optional path to the screen.
     ///
+    /// Blocked cells are always shown, and the path is drawn from `S` to `G`.
+    /// Use `TextRenderer` to write to other destinations or to change glyphs and colors.
+    ///
     /// # Parameters
     /// - `grid`: The 2D grid.
     /// - `path`: An optional vector representing the path to be printed on the grid.
     ///
     /// # Example
     /// ```rust
+    /// use controlled_astar::Node;
+    ///
     /// let grid = vec![
     ///     vec![0, 0, 0],
     ///     vec![0, 1, 0],
     /// ];
-    /// let path = Some(vec![(0, 0), (1, 1)]);
+    /// let path = Some(vec![(0, 0), (1, 0), (2, 0), (2, 1)]);
     /// Node::print_grid(&grid, &path);
     /// ```
     pub fn print_grid(grid: &[Vec<i32>], path: &Option<Vec<(usize, usize)>>) {
-        // Iterate over each row in the grid
-        for (y, row) in grid.iter().enumerate() {
-            // Iterate over each cell in the row
-            for (x, &cell) in row.iter().enumerate() {
-                // Check if the path is defined
-                if let Some(ref p) = *path {
-                    // Print 'o' if the cell is part of the path
-                    if p.contains(&(x, y)) {
-                        print!("o ");
-                    } else if cell == 1 {
-                        // Print '#' if the cell is an obstacle
-                        print!("# ");
-                    } else {
-                        // Print '.' if the cell is free
-                        print!(". ");
-                    }
-                } else {
-                    // If no path is provided, just print '.'
-                    print!(". ");
-                }
-            }
-            // Print a newline after each row
-            println!();
+        if grid.first().is_none_or(|row| row.is_empty()) {
+            return;
+        }
+
+        let nodes = Node::grid_to_nodes(grid);
+        let mut renderer = TextRenderer::new(&nodes);
+        if let Some(path) = path {
+            renderer.set_path(path);
         }
+        print!("{}", renderer);
     }
 }
 
@@ -0,0 +1,645 @@
+use crate::node::Node;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The state of a cell in an occupancy grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Occupancy {
+    /// The cell is known to be free.
+    Free,
+    /// The cell is known to be occupied.
+    Occupied,
+    /// Nothing is known about the cell.
+    Unknown,
+}
+
+/// Errors that can occur while loading an occupancy grid.
+#[derive(Debug)]
+pub enum OccupancyError {
+    /// A file could not be read.
+    Io(io::Error),
+    /// A line of the YAML metadata is missing, malformed or out of range.
+    InvalidMetadata { line: usize, message: String },
+    /// The image data is malformed.
+    InvalidImage(String),
+    /// The image is in a format this crate cannot read.
+    UnsupportedImage(String),
+}
+
+impl fmt::Display for OccupancyError {
+    /// Formats the error for display in user-facing contexts.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OccupancyError::Io(error) => write!(f, "Could not read the map: {}", error),
+            OccupancyError::InvalidMetadata { line, message } => {
+                write!(f, "line {}: {}", line, message)
+            }
+            OccupancyError::InvalidImage(message) => write!(f, "Invalid map image: {}", message),
+            OccupancyError::UnsupportedImage(message) => {
+                write!(f, "Unsupported map image: {}", message)
+            }
+        }
+    }
+}
+
+impl Error for OccupancyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            OccupancyError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for OccupancyError {
+    fn from(error: io::Error) -> Self {
+        OccupancyError::Io(error)
+    }
+}
+
+/// The metadata of an occupancy map, as written by ROS `map_server`.
+///
+/// `origin` is the world position `(x, y, yaw)` of the lower-left pixel of the
+/// image, in metres and radians. The yaw is kept but not applied.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MapMetadata {
+    pub image: String,
+    pub resolution: f64,
+    pub origin: (f64, f64, f64),
+    pub negate: bool,
+    pub occupied_thresh: f64,
+    pub free_thresh: f64,
+}
+
+impl MapMetadata {
+    /// Creates metadata with the `map_server` default thresholds.
+    ///
+    /// # Parameters
+    /// - `resolution`: The size of a cell in metres.
+    ///
+    /// # Returns
+    /// A `MapMetadata` with its origin at `(0, 0, 0)`, an occupied threshold of
+    /// `0.65` and a free threshold of `0.196`.
+    pub fn new(resolution: f64) -> Self {
+        MapMetadata {
+            image: String::new(),
+            resolution,
+            origin: (0.0, 0.0, 0.0),
+            negate: false,
+            occupied_thresh: 0.65,
+            free_thresh: 0.196,
+        }
+    }
+
+    /// Parses the YAML metadata of a `map_server` map.
+    ///
+    /// Only flat `key: value` lines are read; unknown keys such as `mode` are ignored.
+    /// `image` and `resolution` are required, the other keys default to the values
+    /// of `MapMetadata::new`.
+    ///
+    /// # Parameters
+    /// - `text`: The content of the YAML file.
+    ///
+    /// # Returns
+    /// The parsed `MapMetadata`, or an `OccupancyError::InvalidMetadata` pointing at the offending line.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::MapMetadata;
+    ///
+    /// let yaml = "image: map.pgm\nresolution: 0.05\norigin: [-10.0, -5.0, 0.0]\n";
+    /// let metadata = MapMetadata::parse_yaml(yaml).unwrap();
+    /// assert_eq!(metadata.image, "map.pgm");
+    /// assert_eq!(metadata.origin, (-10.0, -5.0, 0.0));
+    /// ```
+    pub fn parse_yaml(text: &str) -> Result<Self, OccupancyError> {
+        let mut metadata = MapMetadata::new(0.0);
+        let mut has_image = false;
+        let mut has_resolution = false;
+
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let error = |message: String| OccupancyError::InvalidMetadata {
+                line: line_number,
+                message,
+            };
+
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| error(format!("expected `key: value` but found {:?}", line)))?;
+            let value = value.trim();
+            let number = |value: &str| {
+                value
+                    .parse::<f64>()
+                    .map_err(|_| error(format!("invalid number {:?} for {}", value, key.trim())))
+            };
+
+            match key.trim() {
+                "image" => {
+                    metadata.image = value.trim_matches(['"', '\'']).to_string();
+                    has_image = true;
+                }
+                "resolution" => {
+                    metadata.resolution = number(value)?;
+                    has_resolution = true;
+                }
+                "origin" => {
+                    let values = value
+                        .strip_prefix('[')
+                        .and_then(|value| value.strip_suffix(']'))
+                        .ok_or_else(|| error(format!("invalid origin {:?}", value)))?
+                        .split(',')
+                        .map(|value| number(value.trim()))
+                        .collect::<Result<Vec<f64>, _>>()?;
+                    match values[..] {
+                        [x, y] => metadata.origin = (x, y, 0.0),
+                        [x, y, yaw] => metadata.origin = (x, y, yaw),
+                        _ => return Err(error(format!("invalid origin {:?}", value))),
+                    }
+                }
+                "negate" => {
+                    metadata.negate = match value {
+                        "0" | "false" => false,
+                        "1" | "true" => true,
+                        _ => return Err(error(format!("invalid negate value {:?}", value))),
+                    }
+                }
+                "occupied_thresh" => metadata.occupied_thresh = number(value)?,
+                "free_thresh" => metadata.free_thresh = number(value)?,
+                _ => {}
+            }
+        }
+
+        let last_line = text.lines().count().max(1);
+        if !has_image {
+            return Err(OccupancyError::InvalidMetadata {
+                line: last_line,
+                message: "missing image".to_string(),
+            });
+        }
+        if !has_resolution || metadata.resolution <= 0.0 {
+            return Err(OccupancyError::InvalidMetadata {
+                line: last_line,
+                message: "missing or non-positive resolution".to_string(),
+            });
+        }
+
+        Ok(metadata)
+    }
+
+    /// Classifies a grayscale pixel value with the `map_server` thresholds.
+    ///
+    /// Dark pixels are occupied and light pixels are free, unless `negate` is set.
+    ///
+    /// # Parameters
+    /// - `value`: The pixel value, `0` to `255`.
+    ///
+    /// # Returns
+    /// The `Occupancy` of the pixel.
+    pub fn classify(&self, value: u8) -> Occupancy {
+        let probability = self.probability(value);
+        if probability > self.occupied_thresh {
+            Occupancy::Occupied
+        } else if probability < self.free_thresh {
+            Occupancy::Free
+        } else {
+            Occupancy::Unknown
+        }
+    }
+
+    /// Returns the occupancy probability of a grayscale pixel value.
+    fn probability(&self, value: u8) -> f64 {
+        let value = f64::from(value) / 255.0;
+        if self.negate {
+            value
+        } else {
+            1.0 - value
+        }
+    }
+}
+
+/// Options for turning an occupancy image into nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OccupancyOptions {
+    /// Whether unknown cells are blocked. Defaults to `true`.
+    pub unknown_is_blocked: bool,
+    /// If set, passable cells cost between `1` and `max_cost`, rising linearly with
+    /// their occupancy probability up to the occupied threshold. Otherwise all
+    /// passable cells cost `1`.
+    pub max_cost: Option<usize>,
+}
+
+impl Default for OccupancyOptions {
+    fn default() -> Self {
+        OccupancyOptions {
+            unknown_is_blocked: true,
+            max_cost: None,
+        }
+    }
+}
+
+/// A node map read from an occupancy image, together with its metadata.
+///
+/// Nodes are keyed like in `Node::grid_to_nodes`, with row `0` being the top row
+/// of the image. Cells are addressed as `(column, row)`, ready to be passed to
+/// `AStar::find_shortest_path`.
+#[derive(Debug, Clone)]
+pub struct OccupancyGrid {
+    pub width: usize,
+    pub height: usize,
+    pub metadata: MapMetadata,
+    pub nodes: HashMap<(usize, usize), Node>,
+    cells: Vec<Occupancy>,
+}
+
+impl OccupancyGrid {
+    /// Creates an occupancy grid from 8-bit grayscale pixels.
+    ///
+    /// # Parameters
+    /// - `width`: The width of the image in pixels.
+    /// - `height`: The height of the image in pixels.
+    /// - `pixels`: The pixel values, row by row from the top of the image.
+    /// - `metadata`: The resolution, origin and thresholds of the map.
+    /// - `options`: How cells are turned into nodes.
+    ///
+    /// # Returns
+    /// The `OccupancyGrid`, or an `OccupancyError::InvalidImage` if the number of pixels does not match.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{MapMetadata, Occupancy, OccupancyGrid, OccupancyOptions};
+    ///
+    /// let pixels = [254, 0, 205, 254];
+    /// let grid =
+    ///     OccupancyGrid::from_gray(2, 2, &pixels, MapMetadata::new(0.5), OccupancyOptions::default())
+    ///         .unwrap();
+    /// assert_eq!(grid.occupancy((1, 0)), Some(Occupancy::Occupied));
+    /// assert_eq!(grid.occupancy((0, 1)), Some(Occupancy::Unknown));
+    /// assert!(grid.nodes[&(1, 0)].is_blocked);
+    /// ```
+    pub fn from_gray(
+        width: usize,
+        height: usize,
+        pixels: &[u8],
+        metadata: MapMetadata,
+        options: OccupancyOptions,
+    ) -> Result<Self, OccupancyError> {
+        if width == 0 || height == 0 {
+            return Err(OccupancyError::InvalidImage(
+                "the image is empty".to_string(),
+            ));
+        }
+        let count = width
+            .checked_mul(height)
+            .ok_or_else(|| OccupancyError::InvalidImage("the image is too large".to_string()))?;
+        if pixels.len() != count {
+            return Err(OccupancyError::InvalidImage(format!(
+                "expected {} pixels but found {}",
+                count,
+                pixels.len()
+            )));
+        }
+
+        let mut nodes = HashMap::new();
+        let mut cells = Vec::with_capacity(pixels.len());
+
+        for (index, &value) in pixels.iter().enumerate() {
+            let (row, col) = (index / width, index % width);
+            let occupancy = metadata.classify(value);
+            let is_blocked = match occupancy {
+                Occupancy::Free => false,
+                Occupancy::Occupied => true,
+                Occupancy::Unknown => options.unknown_is_blocked,
+            };
+
+            let mut node = Node::new(row, col, is_blocked, height - 1, width - 1);
+            if let Some(max_cost) = options.max_cost {
+                let ratio =
+                    (metadata.probability(value) / metadata.occupied_thresh).clamp(0.0, 1.0);
+                let extra = (ratio * max_cost.saturating_sub(1) as f64).round() as usize;
+                node.set_cost(1 + extra);
+            }
+            nodes.insert((row, col), node);
+            cells.push(occupancy);
+        }
+
+        Ok(OccupancyGrid {
+            width,
+            height,
+            metadata,
+            nodes,
+            cells,
+        })
+    }
+
+    /// Creates an occupancy grid from a binary (`P5`) or plain (`P2`) PGM image.
+    ///
+    /// Images with a maximum value other than `255` are rescaled to `0`-`255`.
+    ///
+    /// # Parameters
+    /// - `bytes`: The content of the PGM file.
+    /// - `metadata`: The resolution, origin and thresholds of the map.
+    /// - `options`: How cells are turned into nodes.
+    ///
+    /// # Returns
+    /// The `OccupancyGrid`, or an `OccupancyError` if the image is malformed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{MapMetadata, OccupancyGrid, OccupancyOptions};
+    ///
+    /// let pgm = b"P2\n# A 3x1 map\n3 1\n255\n254 0 254\n";
+    /// let grid =
+    ///     OccupancyGrid::from_pgm(pgm, MapMetadata::new(0.05), OccupancyOptions::default())
+    ///         .unwrap();
+    /// assert_eq!((grid.width, grid.height), (3, 1));
+    /// assert!(grid.nodes[&(0, 1)].is_blocked);
+    /// ```
+    pub fn from_pgm(
+        bytes: &[u8],
+        metadata: MapMetadata,
+        options: OccupancyOptions,
+    ) -> Result<Self, OccupancyError> {
+        let (width, height, pixels) = read_pgm(bytes)?;
+        OccupancyGrid::from_gray(width, height, &pixels, metadata, options)
+    }
+
+    /// Creates an occupancy grid from a PNG image.
+    ///
+    /// Color images are converted to grayscale by averaging their channels;
+    /// alpha channels are ignored.
+    ///
+    /// # Parameters
+    /// - `bytes`: The content of the PNG file.
+    /// - `metadata`: The resolution, origin and thresholds of the map.
+    /// - `options`: How cells are turned into nodes.
+    ///
+    /// # Returns
+    /// The `OccupancyGrid`, or an `OccupancyError` if the image cannot be decoded.
+    #[cfg(feature = "png")]
+    pub fn from_png(
+        bytes: &[u8],
+        metadata: MapMetadata,
+        options: OccupancyOptions,
+    ) -> Result<Self, OccupancyError> {
+        let (width, height, pixels) = read_png(bytes)?;
+        OccupancyGrid::from_gray(width, height, &pixels, metadata, options)
+    }
+
+    /// Loads a `map_server` map from its YAML file and the image it points to.
+    ///
+    /// The image path is relative to the YAML file. PGM images are always
+    /// supported, PNG images need the `png` feature.
+    ///
+    /// # Parameters
+    /// - `yaml_path`: The path of the YAML file.
+    /// - `options`: How cells are turned into nodes.
+    ///
+    /// # Returns
+    /// The `OccupancyGrid`, or an `OccupancyError` if a file cannot be read or is malformed.
+    pub fn load(
+        yaml_path: impl AsRef<Path>,
+        options: OccupancyOptions,
+    ) -> Result<Self, OccupancyError> {
+        let yaml_path = yaml_path.as_ref();
+        let metadata = MapMetadata::parse_yaml(&fs::read_to_string(yaml_path)?)?;
+
+        let image_path = yaml_path.parent().map_or_else(
+            || PathBuf::from(&metadata.image),
+            |dir| dir.join(&metadata.image),
+        );
+        let bytes = fs::read(&image_path)?;
+
+        let extension = image_path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("pgm") => OccupancyGrid::from_pgm(&bytes, metadata, options),
+            #[cfg(feature = "png")]
+            Some("png") => OccupancyGrid::from_png(&bytes, metadata, options),
+            _ => Err(OccupancyError::UnsupportedImage(format!(
+                "cannot read {}",
+                image_path.display()
+            ))),
+        }
+    }
+
+    /// Returns the occupancy of a cell, or `None` if it lies outside the map.
+    ///
+    /// # Parameters
+    /// - `cell`: The `(column, row)` of the cell.
+    pub fn occupancy(&self, cell: (usize, usize)) -> Option<Occupancy> {
+        let (col, row) = cell;
+        if col >= self.width || row >= self.height {
+            return None;
+        }
+        Some(self.cells[row * self.width + col])
+    }
+
+    /// Converts a world position into the cell containing it.
+    ///
+    /// # Parameters
+    /// - `position`: The world `(x, y)` position in metres.
+    ///
+    /// # Returns
+    /// The `(column, row)` of the cell, or `None` if the position lies outside the map or
+    /// is not finite.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{MapMetadata, OccupancyGrid, OccupancyOptions};
+    ///
+    /// let mut metadata = MapMetadata::new(0.5);
+    /// metadata.origin = (-1.0, -1.0, 0.0);
+    /// let grid = OccupancyGrid::from_gray(4, 4, &[254; 16], metadata, OccupancyOptions::default())
+    ///     .unwrap();
+    ///
+    /// // The origin is the lower-left corner, row 0 is the top of the image
+    /// assert_eq!(grid.world_to_cell((-1.0, -1.0)), Some((0, 3)));
+    /// assert_eq!(grid.cell_to_world((0, 3)), Some((-0.75, -0.75)));
+    /// assert_eq!(grid.world_to_cell((1.0, 0.0)), None);
+    /// ```
+    pub fn world_to_cell(&self, position: (f64, f64)) -> Option<(usize, usize)> {
+        // NaN fails every range check below and would turn into cell 0
+        if !position.0.is_finite() || !position.1.is_finite() {
+            return None;
+        }
+        let (origin_x, origin_y, _) = self.metadata.origin;
+        let col = ((position.0 - origin_x) / self.metadata.resolution).floor();
+        let row_from_bottom = ((position.1 - origin_y) / self.metadata.resolution).floor();
+
+        if col < 0.0
+            || row_from_bottom < 0.0
+            || col >= self.width as f64
+            || row_from_bottom >= self.height as f64
+        {
+            return None;
+        }
+        Some((col as usize, self.height - 1 - row_from_bottom as usize))
+    }
+
+    /// Converts a cell into the world position of its centre.
+    ///
+    /// # Parameters
+    /// - `cell`: The `(column, row)` of the cell.
+    ///
+    /// # Returns
+    /// The world `(x, y)` position in metres, or `None` if the cell lies outside the map.
+    pub fn cell_to_world(&self, cell: (usize, usize)) -> Option<(f64, f64)> {
+        let (origin_x, origin_y, _) = self.metadata.origin;
+        let resolution = self.metadata.resolution;
+        let (col, row) = cell;
+        if col >= self.width || row >= self.height {
+            return None;
+        }
+
+        Some((
+            origin_x + (col as f64 + 0.5) * resolution,
+            origin_y + ((self.height - row) as f64 - 0.5) * resolution,
+        ))
+    }
+}
+
+/// Reads the width, height and 8-bit pixels of a PGM image.
+fn read_pgm(bytes: &[u8]) -> Result<(usize, usize, Vec<u8>), OccupancyError> {
+    let invalid = |message: &str| OccupancyError::InvalidImage(message.to_string());
+
+    let binary = match bytes.get(..2) {
+        Some(b"P5") => true,
+        Some(b"P2") => false,
+        _ => {
+            return Err(OccupancyError::UnsupportedImage(
+                "not a PGM image".to_string(),
+            ))
+        }
+    };
+
+    // Header: width, height and maximum value, separated by whitespace and comments
+    let mut position = 2;
+    let mut header = [0usize; 3];
+    for value in header.iter_mut() {
+        *value = read_pgm_number(bytes, &mut position).ok_or_else(|| invalid("bad header"))?;
+    }
+    let [width, height, max_value] = header;
+    if max_value == 0 || max_value > 65535 {
+        return Err(invalid("the maximum value must be between 1 and 65535"));
+    }
+
+    let scale = |value: usize| (value.min(max_value) * 255 / max_value) as u8;
+    let count = width
+        .checked_mul(height)
+        .ok_or_else(|| invalid("the image is too large"))?;
+
+    // Check the size against the data before reserving memory for the pixels. A
+    // single whitespace character separates the header from the raster
+    let data = bytes.get(position + 1..).unwrap_or(&[]);
+    let (sample_size, available) = if binary {
+        (if max_value < 256 { 1 } else { 2 }, data.len())
+    } else {
+        // Plain values take at least one digit each, separated by whitespace
+        (1, data.len() / 2 + 1)
+    };
+    let needed = count
+        .checked_mul(sample_size)
+        .ok_or_else(|| invalid("the image is too large"))?;
+    if available < needed {
+        return Err(invalid("the image data is truncated"));
+    }
+    let mut pixels = Vec::with_capacity(count);
+
+    if binary {
+        for sample in data.chunks_exact(sample_size).take(count) {
+            let value = match sample {
+                [value] => usize::from(*value),
+                [high, low] => usize::from(*high) << 8 | usize::from(*low),
+                _ => unreachable!(),
+            };
+            pixels.push(scale(value));
+        }
+    } else {
+        for _ in 0..count {
+            let value = read_pgm_number(bytes, &mut position)
+                .ok_or_else(|| invalid("the image data is truncated"))?;
+            pixels.push(scale(value));
+        }
+    }
+
+    Ok((width, height, pixels))
+}
+
+/// Reads the next decimal number of a PGM header or plain raster, skipping whitespace and comments.
+fn read_pgm_number(bytes: &[u8], position: &mut usize) -> Option<usize> {
+    loop {
+        match bytes.get(*position)? {
+            b'#' => {
+                while bytes.get(*position).is_some_and(|&byte| byte != b'\n') {
+                    *position += 1;
+                }
+            }
+            byte if byte.is_ascii_whitespace() => *position += 1,
+            _ => break,
+        }
+    }
+
+    let start = *position;
+    while bytes.get(*position).is_some_and(u8::is_ascii_digit) {
+        *position += 1;
+    }
+    std::str::from_utf8(&bytes[start..*position])
+        .ok()?
+        .parse()
+        .ok()
+}
+
+/// Reads the width, height and 8-bit grayscale pixels of a PNG image.
+#[cfg(feature = "png")]
+fn read_png(bytes: &[u8]) -> Result<(usize, usize, Vec<u8>), OccupancyError> {
+    let invalid = |error: png::DecodingError| OccupancyError::InvalidImage(error.to_string());
+
+    let mut decoder = png::Decoder::new(io::Cursor::new(bytes));
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().map_err(invalid)?;
+    let mut buffer = vec![
+        0;
+        reader.output_buffer_size().ok_or_else(
+            || OccupancyError::InvalidImage("the image is too large".to_string())
+        )?
+    ];
+    let info = reader.next_frame(&mut buffer).map_err(invalid)?;
+
+    let (width, height) = (info.width as usize, info.height as usize);
+    let color_channels = match info.color_type {
+        png::ColorType::Grayscale | png::ColorType::GrayscaleAlpha => 1,
+        png::ColorType::Rgb | png::ColorType::Rgba => 3,
+        png::ColorType::Indexed => {
+            return Err(OccupancyError::UnsupportedImage(
+                "indexed images must be expanded".to_string(),
+            ))
+        }
+    };
+    let samples = info.color_type.samples();
+
+    let mut pixels = Vec::with_capacity(width * height);
+    for line in buffer.chunks(info.line_size).take(height) {
+        for pixel in line.chunks_exact(samples).take(width) {
+            let sum: usize = pixel[..color_channels]
+                .iter()
+                .map(|&v| usize::from(v))
+                .sum();
+            pixels.push((sum / color_channels) as u8);
+        }
+    }
+
+    Ok((width, height, pixels))
+}
@@ -0,0 +1,326 @@
+use crate::node::{Direction, Node};
+use std::collections::{HashMap, HashSet};
+
+/// A path found by a search, with the cost and direction of every step.
+///
+/// Positions are given as `(x, y)`, like the start and goal of
+/// `AStar::find_shortest_path`. A path always contains at least one position.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "PathParts", into = "PathParts")
+)]
+pub struct Path {
+    positions: Vec<(usize, usize)>,
+    step_costs: Vec<usize>,
+    directions: Vec<Option<Direction>>,
+    /// The positions of the path, for constant-time `Path::contains`.
+    visited: HashSet<(usize, usize)>,
+}
+
+/// The serialized form of a `Path`, without the lookup set rebuilt on loading.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PathParts {
+    positions: Vec<(usize, usize)>,
+    step_costs: Vec<usize>,
+    directions: Vec<Option<Direction>>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<PathParts> for Path {
+    type Error = String;
+
+    fn try_from(parts: PathParts) -> Result<Self, Self::Error> {
+        if parts.positions.len() != parts.step_costs.len() + 1
+            || parts.step_costs.len() != parts.directions.len()
+        {
+            return Err(format!(
+                "a path with {} positions needs one step cost and direction less, found {} and {}",
+                parts.positions.len(),
+                parts.step_costs.len(),
+                parts.directions.len()
+            ));
+        }
+        Ok(Path::from_parts(
+            parts.positions,
+            parts.step_costs,
+            parts.directions,
+        ))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Path> for PathParts {
+    fn from(path: Path) -> Self {
+        PathParts {
+            positions: path.positions,
+            step_costs: path.step_costs,
+            directions: path.directions,
+        }
+    }
+}
+
+/// A run of steps in the same direction, see `Path::segments`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment {
+    /// The position the run starts at, as `(x, y)`.
+    pub start: (usize, usize),
+    /// The position the run ends at, as `(x, y)`.
+    pub end: (usize, usize),
+    /// The direction of every step of the run, `None` for a custom edge.
+    pub direction: Option<Direction>,
+    /// The number of steps in the run.
+    pub steps: usize,
+    /// The total cost of the steps in the run.
+    pub cost: usize,
+}
+
+impl Path {
+    /// Creates a path from its parts.
+    ///
+    /// `step_costs` and `directions` hold one entry per step, so one less than `positions`.
+    pub(crate) fn from_parts(
+        positions: Vec<(usize, usize)>,
+        step_costs: Vec<usize>,
+        directions: Vec<Option<Direction>>,
+    ) -> Self {
+        debug_assert_eq!(positions.len(), step_costs.len() + 1);
+        debug_assert_eq!(step_costs.len(), directions.len());
+        Path {
+            visited: positions.iter().copied().collect(),
+            positions,
+            step_costs,
+            directions,
+        }
+    }
+
+    /// Creates a path by following a list of positions through a node map.
+    ///
+    /// Every step takes the cheapest directional neighbor or custom edge between
+    /// two consecutive positions. The directions are the `Node::neighbors` keys of
+    /// the steps, and `None` for steps along custom edges.
+    ///
+    /// # Parameters
+    /// - `nodes`: The node map the positions belong to.
+    /// - `positions`: The positions as `(x, y)`.
+    ///
+    /// # Returns
+    /// The path, or `None` if `positions` is empty or two consecutive positions are not linked.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{Direction, Node, Path};
+    ///
+    /// let nodes = Node::grid_to_nodes(&vec![vec![0; 3]; 3]);
+    /// let path = Path::from_positions(&nodes, vec![(0, 0), (1, 0), (1, 1)]).unwrap();
+    /// assert_eq!(path.total_cost(), 2);
+    /// // `Node::new` labels steps along x as south and steps along y as east
+    /// assert_eq!(path.directions(), &[Some(Direction::South), Some(Direction::East)]);
+    ///
+    /// assert!(Path::from_positions(&nodes, vec![(0, 0), (2, 2)]).is_none());
+    /// ```
+    pub fn from_positions(
+        nodes: &HashMap<(usize, usize), Node>,
+        positions: Vec<(usize, usize)>,
+    ) -> Option<Self> {
+        if positions.is_empty() {
+            return None;
+        }
+
+        let mut step_costs = Vec::new();
+        let mut directions = Vec::new();
+        for pair in positions.windows(2) {
+            let (cost, direction) = step(nodes, pair[0], pair[1])?;
+            step_costs.push(cost);
+            directions.push(direction);
+        }
+        Some(Path::from_parts(positions, step_costs, directions))
+    }
+
+    /// Returns the positions of the path as `(x, y)`, from the start to the goal.
+    pub fn positions(&self) -> &[(usize, usize)] {
+        &self.positions
+    }
+
+    /// Returns the cost of every step, in order.
+    pub fn step_costs(&self) -> &[usize] {
+        &self.step_costs
+    }
+
+    /// Returns the `Node::neighbors` direction of every step, `None` for custom edges.
+    pub fn directions(&self) -> &[Option<Direction>] {
+        &self.directions
+    }
+
+    /// Returns the total cost of the path.
+    pub fn total_cost(&self) -> usize {
+        self.step_costs.iter().sum()
+    }
+
+    /// Returns the number of steps, one less than the number of positions.
+    pub fn len(&self) -> usize {
+        self.step_costs.len()
+    }
+
+    /// Checks whether the path has no steps, that is whether it starts at its goal.
+    pub fn is_empty(&self) -> bool {
+        self.step_costs.is_empty()
+    }
+
+    /// Returns the first position of the path.
+    pub fn start(&self) -> (usize, usize) {
+        self.positions[0]
+    }
+
+    /// Returns the last position of the path.
+    pub fn goal(&self) -> (usize, usize) {
+        self.positions[self.positions.len() - 1]
+    }
+
+    /// Iterates over the positions of the path.
+    pub fn iter(&self) -> std::slice::Iter<'_, (usize, usize)> {
+        self.positions.iter()
+    }
+
+    /// Checks in constant time whether the path passes through a position.
+    pub fn contains(&self, position: (usize, usize)) -> bool {
+        self.visited.contains(&position)
+    }
+
+    /// Returns the path walked backwards.
+    ///
+    /// Directions are replaced by their opposites. Steps keep the cost they had in
+    /// the original path, since walking back along the same links may not be
+    /// possible, or cost the same, on maps with one-way edges or terrain costs.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{Direction, Node, Path};
+    ///
+    /// let nodes = Node::grid_to_nodes(&vec![vec![0; 3]; 3]);
+    /// let path = Path::from_positions(&nodes, vec![(0, 0), (1, 0)]).unwrap();
+    /// let back = path.reverse();
+    /// assert_eq!(back.positions(), &[(1, 0), (0, 0)]);
+    /// assert_eq!(back.directions(), &[Some(Direction::North)]);
+    /// ```
+    pub fn reverse(&self) -> Path {
+        Path {
+            positions: self.positions.iter().rev().copied().collect(),
+            step_costs: self.step_costs.iter().rev().copied().collect(),
+            directions: self
+                .directions
+                .iter()
+                .rev()
+                .map(|direction| direction.map(Direction::opposite))
+                .collect(),
+            visited: self.visited.clone(),
+        }
+    }
+
+    /// Splits the path into runs of steps in the same direction.
+    ///
+    /// Every custom edge step is a run of its own. A path without steps has no runs.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{Direction, Node, Path};
+    ///
+    /// let nodes = Node::grid_to_nodes(&vec![vec![0; 3]; 3]);
+    /// let path = Path::from_positions(&nodes, vec![(0, 0), (1, 0), (2, 0), (2, 1)]).unwrap();
+    /// let segments = path.segments();
+    /// assert_eq!(segments.len(), 2);
+    /// assert_eq!((segments[0].end, segments[0].steps), ((2, 0), 2));
+    /// assert_eq!(segments[1].direction, Some(Direction::East));
+    /// ```
+    pub fn segments(&self) -> Vec<Segment> {
+        let mut segments: Vec<Segment> = Vec::new();
+
+        for (index, (&direction, &cost)) in self.directions.iter().zip(&self.step_costs).enumerate()
+        {
+            let end = self.positions[index + 1];
+            match segments.last_mut() {
+                Some(segment) if direction.is_some() && segment.direction == direction => {
+                    segment.end = end;
+                    segment.steps += 1;
+                    segment.cost += cost;
+                }
+                _ => segments.push(Segment {
+                    start: self.positions[index],
+                    end,
+                    direction,
+                    steps: 1,
+                    cost,
+                }),
+            }
+        }
+
+        segments
+    }
+
+    /// Appends a path that starts where this one ends, without repeating the junction.
+    pub(crate) fn append(&mut self, next: &Path) {
+        debug_assert_eq!(self.goal(), next.start());
+        self.positions.extend_from_slice(&next.positions[1..]);
+        self.step_costs.extend_from_slice(&next.step_costs);
+        self.directions.extend_from_slice(&next.directions);
+        self.visited.extend(next.positions.iter().copied());
+    }
+
+    /// Consumes the path and returns its positions.
+    pub fn into_positions(self) -> Vec<(usize, usize)> {
+        self.positions
+    }
+}
+
+impl PartialEq for Path {
+    fn eq(&self, other: &Self) -> bool {
+        self.positions == other.positions
+            && self.step_costs == other.step_costs
+            && self.directions == other.directions
+    }
+}
+
+impl Eq for Path {}
+
+impl<'a> IntoIterator for &'a Path {
+    type Item = &'a (usize, usize);
+    type IntoIter = std::slice::Iter<'a, (usize, usize)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Finds the cheapest link between two positions given as `(x, y)`.
+///
+/// # Returns
+/// The cost of the step and its direction, `None` for a custom edge, or `None`
+/// if the positions are not linked.
+pub(crate) fn step(
+    nodes: &HashMap<(usize, usize), Node>,
+    from: (usize, usize),
+    to: (usize, usize),
+) -> Option<(usize, Option<Direction>)> {
+    let (from, to) = ((from.1, from.0), (to.1, to.0));
+    let node = nodes.get(&from)?;
+    let target = nodes.get(&to)?;
+
+    let neighbor = node
+        .neighbors
+        .iter()
+        .filter(|(_, &position)| position == Some(to))
+        .map(|(&direction, _)| (target.cost, Some(direction)));
+    let custom_edge = node
+        .custom_edges
+        .iter()
+        .filter(|edge| edge.target == to)
+        .map(|edge| (edge.cost, None));
+
+    // Prefer a directional step over a custom edge of the same cost
+    neighbor
+        .chain(custom_edge)
+        .min_by_key(|&(cost, direction)| (cost, direction.is_none()))
+}
@@ -0,0 +1,358 @@
+use crate::astar::{unreachable_reason, AStarError, Unreachable};
+use crate::landmarks::index_positions;
+use crate::node::{Direction, Node};
+use crate::path::Path;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Directions in the order of their move codes.
+const DIRECTIONS: [Direction; 8] = [
+    Direction::North,
+    Direction::South,
+    Direction::West,
+    Direction::East,
+    Direction::NorthEast,
+    Direction::NorthWest,
+    Direction::SouthEast,
+    Direction::SouthWest,
+];
+
+/// Move code of the first custom edge, later edges follow in order.
+const FIRST_CUSTOM_EDGE: u16 = DIRECTIONS.len() as u16;
+
+/// Move code for positions that are the goal or cannot reach it. Custom edges
+/// must have codes below it.
+const NO_MOVE: u16 = u16::MAX;
+
+/// A move out of a node as its move code, the target's map key and the cost.
+type Move = (u16, (usize, usize), usize);
+
+/// A compressed path database: the first move of a shortest path between every
+/// pair of positions, looked up instead of searched.
+///
+/// For every goal, the move to take from every other position is stored as runs
+/// of equal moves over the positions sorted by map key. Neighboring positions
+/// usually head the same way, so the runs stay far fewer than the pairs. Queries
+/// follow the moves from the start and take time linear in the path length.
+///
+/// Building the database runs Dijkstra's algorithm once per passable position,
+/// which suits small, static maps. The database describes the node map it was
+/// built from; build a new one after changing it. Positions are given as `(x, y)`,
+/// like for `AStar::find_shortest_path`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "PathDatabaseParts", into = "PathDatabaseParts")
+)]
+pub struct PathDatabase {
+    /// The map keys of the database, sorted.
+    positions: Vec<(usize, usize)>,
+    /// The index of every map key in `positions`.
+    indices: HashMap<(usize, usize), usize>,
+    /// For every goal by index, runs of the first move as the index of the first
+    /// position of the run and the move code.
+    runs: Vec<Vec<(usize, u16)>>,
+}
+
+/// The serialized form of a `PathDatabase`, without the index rebuilt on loading.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PathDatabaseParts {
+    positions: Vec<(usize, usize)>,
+    runs: Vec<Vec<(usize, u16)>>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<PathDatabaseParts> for PathDatabase {
+    type Error = String;
+
+    fn try_from(parts: PathDatabaseParts) -> Result<Self, Self::Error> {
+        let size = parts.positions.len();
+        if parts.runs.len() != size {
+            return Err(format!(
+                "a database with {} positions needs as many lists of runs, found {}",
+                size,
+                parts.runs.len()
+            ));
+        }
+        // Every list starts at the first position and stays in order
+        let is_valid = |runs: &Vec<(usize, u16)>| {
+            runs.first().map(|run| run.0) == Some(0)
+                && runs.windows(2).all(|pair| pair[0].0 < pair[1].0)
+                && runs.last().is_some_and(|run| run.0 < size)
+        };
+        if size > 0 && !parts.runs.iter().all(is_valid) {
+            return Err("the runs of a database must start at 0 and increase".to_string());
+        }
+        Ok(PathDatabase {
+            indices: index_positions(&parts.positions),
+            positions: parts.positions,
+            runs: parts.runs,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<PathDatabase> for PathDatabaseParts {
+    fn from(database: PathDatabase) -> Self {
+        PathDatabaseParts {
+            positions: database.positions,
+            runs: database.runs,
+        }
+    }
+}
+
+impl PathDatabase {
+    /// Computes the first moves between all pairs of positions of a node map.
+    ///
+    /// # Parameters
+    /// - `nodes`: The node map.
+    ///
+    /// # Returns
+    /// A new `PathDatabase`, or `AStarError::InvalidConfiguration` if a node has more
+    /// custom edges than move codes are left for (65527).
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, Node, PathDatabase};
+    ///
+    /// let grid = vec![
+    ///     vec![0, 0, 0, 0],
+    ///     vec![1, 1, 1, 0],
+    ///     vec![0, 0, 0, 0],
+    /// ];
+    /// let nodes = Node::grid_to_nodes(&grid);
+    /// let database = PathDatabase::new(&nodes).unwrap();
+    ///
+    /// let path = database.find_path(&nodes, (0, 0), (0, 2)).unwrap();
+    /// let expected = AStar::new(nodes.clone()).find_shortest_path((0, 0), (0, 2)).unwrap();
+    /// assert_eq!(path.total_cost(), expected.total_cost());
+    /// ```
+    pub fn new(nodes: &HashMap<(usize, usize), Node>) -> Result<Self, AStarError> {
+        let mut positions: Vec<(usize, usize)> = nodes.keys().copied().collect();
+        positions.sort_unstable();
+        let indices = index_positions(&positions);
+
+        // Links into every passable position, as the source index, move code and cost
+        let mut incoming: Vec<Vec<(usize, u16, usize)>> = vec![Vec::new(); positions.len()];
+        for (index, position) in positions.iter().enumerate() {
+            let node = &nodes[position];
+            if node.is_blocked {
+                continue;
+            }
+            for (code, target, cost) in moves(nodes, node)? {
+                if !nodes[&target].is_blocked {
+                    incoming[indices[&target]].push((index, code, cost));
+                }
+            }
+        }
+
+        let runs = (0..positions.len())
+            .map(|goal| {
+                if nodes[&positions[goal]].is_blocked {
+                    return vec![(0, NO_MOVE)];
+                }
+                compress(&first_moves(&incoming, goal))
+            })
+            .collect();
+
+        Ok(PathDatabase {
+            positions,
+            indices,
+            runs,
+        })
+    }
+
+    /// Returns the number of runs stored for all goals, a measure of the database size.
+    pub fn run_count(&self) -> usize {
+        self.runs.iter().map(Vec::len).sum()
+    }
+
+    /// Finds a shortest path by following the stored first moves.
+    ///
+    /// # Parameters
+    /// - `nodes`: The node map the database was built from.
+    /// - `start`: The starting point as a tuple (x, y).
+    /// - `goal`: The goal point as a tuple (x, y).
+    ///
+    /// # Returns
+    /// The `Path` from the start to the goal, or an `AStarError` if no path is found.
+    /// `AStarError::InvalidConfiguration` reports a database that does not match the map.
+    pub fn find_path(
+        &self,
+        nodes: &HashMap<(usize, usize), Node>,
+        start: (usize, usize),
+        goal: (usize, usize),
+    ) -> Result<Path, AStarError> {
+        let (start_key, goal_key) = ((start.1, start.0), (goal.1, goal.0));
+        match (nodes.get(&start_key), nodes.get(&goal_key)) {
+            (None, _) => return Err(AStarError::NodeNotFound(start)),
+            (_, None) => return Err(AStarError::NodeNotFound(goal)),
+            (Some(node), _) if node.is_blocked => return Err(AStarError::StartNodeBlocked(start)),
+            (_, Some(node)) if node.is_blocked => return Err(AStarError::GoalNodeBlocked(goal)),
+            _ => {}
+        }
+        let stale = || {
+            AStarError::InvalidConfiguration(
+                "the path database does not match the node map".to_string(),
+            )
+        };
+        let (Some(&start_index), Some(&goal_index)) =
+            (self.indices.get(&start_key), self.indices.get(&goal_key))
+        else {
+            return Err(stale());
+        };
+
+        let runs = &self.runs[goal_index];
+        let mut positions = vec![start];
+        let (mut current, mut index) = (start_key, start_index);
+        while current != goal_key {
+            let run = runs.partition_point(|&(first, _)| first <= index) - 1;
+            let code = runs[run].1;
+            if code == NO_MOVE {
+                if current == start_key {
+                    return Err(AStarError::PathNotFound(Unreachable {
+                        reason: unreachable_reason(nodes, &[start_key], &[goal_key]),
+                        closest: start,
+                        level: None,
+                        explored: 0,
+                    }));
+                }
+                return Err(stale());
+            }
+
+            // Moves into blocked nodes or in circles were stored for another map
+            let next = target(&nodes[&current], code)
+                .filter(|next| nodes.get(next).is_some_and(|node| !node.is_blocked));
+            let (Some(next), Some(&next_index)) =
+                (next, next.and_then(|next| self.indices.get(&next)))
+            else {
+                return Err(stale());
+            };
+            if positions.len() > self.positions.len() {
+                return Err(stale());
+            }
+            positions.push((next.1, next.0));
+            (current, index) = (next, next_index);
+        }
+
+        Path::from_positions(nodes, positions).ok_or_else(stale)
+    }
+
+    /// Writes the database in a compact binary form.
+    ///
+    /// # Returns
+    /// The encoded bytes, or a `bincode::Error` if serialization fails.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{Node, PathDatabase};
+    ///
+    /// let nodes = Node::grid_to_nodes(&vec![vec![0, 1], vec![0, 0]]);
+    /// let database = PathDatabase::new(&nodes).unwrap();
+    /// let bytes = database.to_bytes().unwrap();
+    /// assert_eq!(PathDatabase::from_bytes(&bytes).unwrap(), database);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, bincode::Error> {
+        bincode::serialize(self)
+    }
+
+    /// Reads a database from its compact binary form.
+    ///
+    /// # Parameters
+    /// - `bytes`: The encoded bytes.
+    ///
+    /// # Returns
+    /// The `PathDatabase`, or a `bincode::Error` if the bytes are invalid.
+    #[cfg(feature = "serde")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, bincode::Error> {
+        bincode::deserialize(bytes)
+    }
+}
+
+/// Lists the moves out of a node to positions in the map, as move code, target and cost.
+///
+/// Costs match `Node::edges`.
+///
+/// # Returns
+/// The moves, or `AStarError::InvalidConfiguration` if a custom edge has no move code.
+fn moves(nodes: &HashMap<(usize, usize), Node>, node: &Node) -> Result<Vec<Move>, AStarError> {
+    let neighbors = DIRECTIONS
+        .iter()
+        .enumerate()
+        .filter_map(|(code, direction)| {
+            let target = (*node.neighbors.get(direction)?)?;
+            nodes
+                .get(&target)
+                .map(|target_node| (code as u16, target, target_node.cost))
+        });
+    let custom_edges = node
+        .custom_edges
+        .iter()
+        .enumerate()
+        .filter(|(_, edge)| nodes.contains_key(&edge.target))
+        .map(|(index, edge)| {
+            let code = u16::try_from(index)
+                .ok()
+                .and_then(|index| FIRST_CUSTOM_EDGE.checked_add(index))
+                .filter(|&code| code < NO_MOVE)
+                .ok_or_else(|| {
+                    AStarError::InvalidConfiguration(format!(
+                        "the node at position {:?} has too many custom edges for a path database",
+                        (node.y, node.x)
+                    ))
+                })?;
+            Ok((code, edge.target, edge.cost))
+        });
+
+    neighbors.map(Ok).chain(custom_edges).collect()
+}
+
+/// Returns the map key a move code leads to from a node.
+fn target(node: &Node, code: u16) -> Option<(usize, usize)> {
+    match code.checked_sub(FIRST_CUSTOM_EDGE) {
+        None => *node.neighbors.get(&DIRECTIONS[code as usize])?,
+        Some(index) => node
+            .custom_edges
+            .get(index as usize)
+            .map(|edge| edge.target),
+    }
+}
+
+/// Runs Dijkstra's algorithm backwards from a goal and records the move every
+/// position takes towards it.
+///
+/// The moves form a tree rooted at the goal, so following them never runs in circles.
+fn first_moves(incoming: &[Vec<(usize, u16, usize)>], goal: usize) -> Vec<u16> {
+    let mut costs = vec![usize::MAX; incoming.len()];
+    let mut first_moves = vec![NO_MOVE; incoming.len()];
+    costs[goal] = 0;
+    let mut open = BinaryHeap::from([Reverse((0, goal))]);
+    while let Some(Reverse((cost, index))) = open.pop() {
+        if cost > costs[index] {
+            continue;
+        }
+        for &(source, code, step_cost) in &incoming[index] {
+            let source_cost = cost.saturating_add(step_cost);
+            if source_cost < costs[source] {
+                costs[source] = source_cost;
+                first_moves[source] = code;
+                open.push(Reverse((source_cost, source)));
+            }
+        }
+    }
+    first_moves
+}
+
+/// Run-length encodes moves as the index where every run starts and its move.
+fn compress(moves: &[u16]) -> Vec<(usize, u16)> {
+    let mut runs: Vec<(usize, u16)> = Vec::new();
+    for (index, &code) in moves.iter().enumerate() {
+        if runs.last().map(|run| run.1) != Some(code) {
+            runs.push((index, code));
+        }
+    }
+    runs
+}
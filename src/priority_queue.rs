@@ -3,6 +3,7 @@ use std::collections::BinaryHeap;
 
 /// Represents the states used in the A* algorithm.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct State {
     pub cost: usize,
     pub position: (usize, usize),
@@ -37,7 +38,7 @@ impl PartialOrd for State {
 }
 
 /// Priority queue used in the A* algorithm.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct PriorityQueue {
     heap: BinaryHeap<State>,
 }
@@ -51,7 +52,7 @@ impl PriorityQueue {
     /// # Examples
     ///
     /// ```
-    /// use crate::PriorityQueue;
+    /// use controlled_astar::PriorityQueue;
     ///
     /// // Create a new empty priority queue
     /// let mut open_set = PriorityQueue::new();
@@ -73,7 +74,7 @@ impl PriorityQueue {
     /// # Examples
     ///
     /// ```
-    /// use crate::{PriorityQueue, State};
+    /// use controlled_astar::{PriorityQueue, State};
     /// use std::collections::HashMap;
     ///
     /// // Create a new priority queue
@@ -104,7 +105,7 @@ impl PriorityQueue {
     /// # Examples
     ///
     /// ```
-    /// use crate::{PriorityQueue, State};
+    /// use controlled_astar::{PriorityQueue, State};
     /// use std::collections::HashMap;
     ///
     /// // Create a new priority queue and add some states
@@ -133,7 +134,7 @@ impl PriorityQueue {
     /// # Examples
     ///
     /// ```
-    /// use crate::{PriorityQueue, State};
+    /// use controlled_astar::{PriorityQueue, State};
     ///
     /// // Create a new priority queue
     /// let mut open_set = PriorityQueue::new();
@@ -151,4 +152,19 @@ impl PriorityQueue {
         // Check if the heap is empty
         self.heap.is_empty()
     }
+
+    /// Iterates over the states in the queue, in no particular order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use controlled_astar::{PriorityQueue, State};
+    ///
+    /// let mut open_set = PriorityQueue::new();
+    /// open_set.push(State { cost: 10, position: (1, 2) });
+    /// assert_eq!(open_set.iter().count(), 1);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &State> {
+        self.heap.iter()
+    }
 }
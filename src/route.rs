@@ -0,0 +1,313 @@
+use crate::astar::{AStar, AStarError};
+use crate::path::Path;
+use std::error::Error;
+use std::fmt;
+
+/// Up to this many waypoints, `WaypointOrder::Optimized` finds the cheapest order exactly.
+const MAX_EXACT_WAYPOINTS: usize = 12;
+
+/// The shortest path between every pair of stops, by the index of the stop the
+/// leg starts at and the index of the stop it ends at.
+type LegPaths = Vec<Vec<Option<Path>>>;
+
+/// How `AStar::find_route` visits its waypoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WaypointOrder {
+    /// In the order they are given.
+    #[default]
+    Fixed,
+    /// In the order with the lowest total cost. The order is exact for up to
+    /// 12 waypoints and found by nearest neighbor and 2-opt improvements beyond.
+    Optimized,
+}
+
+/// A path through waypoints, see `AStar::find_route`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    /// The whole path, with every junction between two legs listed once.
+    pub path: Path,
+    /// The indices of the waypoints in the order they are visited.
+    pub order: Vec<usize>,
+    /// The cost of every leg, from the start to the first waypoint visited to the goal.
+    pub leg_costs: Vec<usize>,
+}
+
+/// A leg of a route that could not be found.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LegError {
+    /// The index of the leg: `0` leads from the start to the first waypoint visited.
+    pub leg: usize,
+    /// Where the leg starts, as `(x, y)`.
+    pub from: (usize, usize),
+    /// Where the leg ends, as `(x, y)`.
+    pub to: (usize, usize),
+    /// Why the leg could not be found.
+    pub error: AStarError,
+}
+
+impl fmt::Display for LegError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Leg {} from {:?} to {:?} failed: {}",
+            self.leg, self.from, self.to, self.error
+        )
+    }
+}
+
+impl Error for LegError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl AStar {
+    /// Finds a path from the start through every waypoint to the goal.
+    ///
+    /// Every leg between two consecutive stops is a shortest path, and the legs are
+    /// joined without repeating their junctions. With `WaypointOrder::Optimized`, one
+    /// search from every stop finds the legs to all other stops, and the legs of the
+    /// chosen order are reused from these searches.
+    ///
+    /// # Parameters
+    /// - `start`: The starting point as a tuple (x, y).
+    /// - `waypoints`: The positions to pass through, as `(x, y)`.
+    /// - `goal`: The goal point as a tuple (x, y).
+    /// - `order`: Whether to keep the order of the waypoints or pick the cheapest one.
+    ///
+    /// # Returns
+    /// The `Route`, or a `LegError` naming the first leg that could not be found.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, Node, WaypointOrder};
+    ///
+    /// let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 5]; 5]));
+    /// let waypoints = [(4, 4), (0, 1)];
+    ///
+    /// let route = astar.find_route((0, 0), &waypoints, (0, 4), WaypointOrder::Fixed).unwrap();
+    /// assert_eq!(route.leg_costs, vec![8, 7, 3]);
+    /// assert_eq!(route.path.total_cost(), 18);
+    ///
+    /// let route = astar.find_route((0, 0), &waypoints, (0, 4), WaypointOrder::Optimized).unwrap();
+    /// assert_eq!(route.order, vec![1, 0]);
+    /// assert_eq!(route.path.total_cost(), 12);
+    /// ```
+    pub fn find_route(
+        &mut self,
+        start: (usize, usize),
+        waypoints: &[(usize, usize)],
+        goal: (usize, usize),
+        order: WaypointOrder,
+    ) -> Result<Route, LegError> {
+        let (order, mut known_legs) = match order {
+            WaypointOrder::Fixed => ((0..waypoints.len()).collect(), Vec::new()),
+            WaypointOrder::Optimized => self.cheapest_order(start, waypoints, goal),
+        };
+
+        // Stop `0` is the start, `1..=count` the waypoints and `count + 1` the goal
+        let mut stops = vec![0];
+        stops.extend(order.iter().map(|&index| index + 1));
+        stops.push(waypoints.len() + 1);
+        let position = |stop: usize| match stop {
+            0 => start,
+            stop if stop > waypoints.len() => goal,
+            stop => waypoints[stop - 1],
+        };
+
+        let mut path: Option<Path> = None;
+        let mut leg_costs = Vec::new();
+        for (leg, pair) in stops.windows(2).enumerate() {
+            let (from, to) = (position(pair[0]), position(pair[1]));
+            // Reuse the legs found while ordering, search the others
+            let known = known_legs
+                .get_mut(pair[0])
+                .and_then(|row| row.get_mut(pair[1]))
+                .and_then(Option::take);
+            let leg_path = match known {
+                Some(leg_path) => leg_path,
+                None => self
+                    .find_shortest_path(from, to)
+                    .map_err(|error| LegError {
+                        leg,
+                        from,
+                        to,
+                        error,
+                    })?,
+            };
+            leg_costs.push(leg_path.total_cost());
+            match path.as_mut() {
+                Some(path) => path.append(&leg_path),
+                None => path = Some(leg_path),
+            }
+        }
+
+        Ok(Route {
+            path: path.expect("a route has at least one leg"),
+            order,
+            leg_costs,
+        })
+    }
+
+    /// Finds the order of the waypoints with the lowest total cost.
+    ///
+    /// Legs that cannot be found count as infinitely expensive. If no order reaches
+    /// the goal, the given order is returned so the failing leg can be reported.
+    ///
+    /// # Returns
+    /// The order and the legs found between the stops, so they need not be searched again.
+    fn cheapest_order(
+        &mut self,
+        start: (usize, usize),
+        waypoints: &[(usize, usize)],
+        goal: (usize, usize),
+    ) -> (Vec<usize>, LegPaths) {
+        let count = waypoints.len();
+        if count < 2 {
+            return ((0..count).collect(), Vec::new());
+        }
+
+        // Stop `0` is the start, `1..=count` the waypoints and `count + 1` the goal.
+        // A single search from every stop reaches all others.
+        let mut stops = vec![start];
+        stops.extend_from_slice(waypoints);
+        stops.push(goal);
+        let mut legs: LegPaths = (0..=count)
+            .map(|from| self.find_paths_to_goals(stops[from], &stops))
+            .collect();
+        let mut costs = vec![vec![usize::MAX; count + 2]; count + 2];
+        for (from, row) in legs.iter_mut().enumerate() {
+            // No leg leads back to the start or from a stop to itself
+            row[0] = None;
+            row[from] = None;
+            for (to, path) in row.iter().enumerate() {
+                if let Some(path) = path {
+                    costs[from][to] = path.total_cost();
+                }
+            }
+        }
+
+        let order = if count <= MAX_EXACT_WAYPOINTS {
+            held_karp(&costs)
+        } else {
+            nearest_neighbor_two_opt(&costs)
+        };
+        if route_cost(&costs, &order) == usize::MAX {
+            return ((0..count).collect(), legs);
+        }
+        (order, legs)
+    }
+}
+
+/// Returns the total cost of visiting the waypoints in `order`, `usize::MAX` if any leg is missing.
+fn route_cost(costs: &[Vec<usize>], order: &[usize]) -> usize {
+    let goal = costs.len() - 1;
+    let mut stops = vec![0];
+    stops.extend(order.iter().map(|&index| index + 1));
+    stops.push(goal);
+
+    stops
+        .windows(2)
+        .try_fold(0usize, |total, pair| match costs[pair[0]][pair[1]] {
+            usize::MAX => None,
+            cost => Some(total.saturating_add(cost)),
+        })
+        .unwrap_or(usize::MAX)
+}
+
+/// Finds the cheapest order exactly with the Held-Karp dynamic program.
+///
+/// `costs` holds the leg costs between the start (`0`), the waypoints and the goal (last).
+fn held_karp(costs: &[Vec<usize>]) -> Vec<usize> {
+    let count = costs.len() - 2;
+    let goal = count + 1;
+    let subsets = 1usize << count;
+
+    // best[mask][last]: cheapest cost from the start through `mask`, ending at waypoint `last`
+    let mut best = vec![vec![usize::MAX; count]; subsets];
+    let mut previous = vec![vec![usize::MAX; count]; subsets];
+    for last in 0..count {
+        best[1 << last][last] = costs[0][last + 1];
+    }
+    for mask in 1..subsets {
+        for last in 0..count {
+            let cost = best[mask][last];
+            if mask & (1 << last) == 0 || cost == usize::MAX {
+                continue;
+            }
+            for next in (0..count).filter(|&next| mask & (1 << next) == 0) {
+                let step = costs[last + 1][next + 1];
+                if step == usize::MAX {
+                    continue;
+                }
+                let extended = mask | (1 << next);
+                let total = cost.saturating_add(step);
+                if total < best[extended][next] {
+                    best[extended][next] = total;
+                    previous[extended][next] = last;
+                }
+            }
+        }
+    }
+
+    let full = subsets - 1;
+    let Some(mut last) = (0..count)
+        .filter(|&last| best[full][last] != usize::MAX && costs[last + 1][goal] != usize::MAX)
+        .min_by_key(|&last| best[full][last].saturating_add(costs[last + 1][goal]))
+    else {
+        return (0..count).collect();
+    };
+
+    let mut order = Vec::with_capacity(count);
+    let mut mask = full;
+    loop {
+        order.push(last);
+        let before = previous[mask][last];
+        mask &= !(1 << last);
+        if mask == 0 {
+            break;
+        }
+        last = before;
+    }
+    order.reverse();
+    order
+}
+
+/// Finds a cheap order by visiting the nearest waypoint first, then reversing
+/// parts of the order while that lowers the total cost.
+fn nearest_neighbor_two_opt(costs: &[Vec<usize>]) -> Vec<usize> {
+    let count = costs.len() - 2;
+    let mut order = Vec::with_capacity(count);
+    let mut remaining: Vec<usize> = (0..count).collect();
+    let mut current = 0;
+    while !remaining.is_empty() {
+        let (position, &next) = remaining
+            .iter()
+            .enumerate()
+            .min_by_key(|&(_, &waypoint)| costs[current][waypoint + 1])
+            .expect("remaining is not empty");
+        remaining.remove(position);
+        order.push(next);
+        current = next + 1;
+    }
+
+    let mut best = route_cost(costs, &order);
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for i in 0..count - 1 {
+            for j in i + 1..count {
+                order[i..=j].reverse();
+                let cost = route_cost(costs, &order);
+                if cost < best {
+                    best = cost;
+                    improved = true;
+                } else {
+                    order[i..=j].reverse();
+                }
+            }
+        }
+    }
+    order
+}
@@ -0,0 +1,283 @@
+use crate::node::Node;
+use crate::path::Path;
+use std::collections::HashMap;
+
+/// Tolerance used to detect lines passing exactly through a cell corner.
+const CORNER_EPSILON: f64 = 1e-9;
+
+/// Post-processes grid paths into fewer waypoints and smooth curves.
+///
+/// Positions are given as `(x, y)`, like the positions of a `Path`, and cells are
+/// squares of size `1` centred on their position. Line-of-sight checks treat
+/// missing and blocked nodes as walls and never squeeze diagonally between two
+/// walls touching at a corner.
+///
+/// Shortcuts found by line of sight assume that an agent can move freely between
+/// passable cells: terrain costs, one-way neighbors and removed neighbors are
+/// not taken into account. Steps along custom edges are always kept.
+#[derive(Debug, Clone)]
+pub struct PathSmoother<'a> {
+    nodes: &'a HashMap<(usize, usize), Node>,
+}
+
+impl<'a> PathSmoother<'a> {
+    /// Creates a smoother for the paths of a node map, such as `AStar::nodes`.
+    ///
+    /// # Parameters
+    /// - `nodes`: The node map the paths were found in.
+    ///
+    /// # Returns
+    /// A new `PathSmoother`.
+    pub fn new(nodes: &'a HashMap<(usize, usize), Node>) -> Self {
+        PathSmoother { nodes }
+    }
+
+    /// Checks whether a straight line between the centres of two cells only crosses passable cells.
+    ///
+    /// # Parameters
+    /// - `from`: The first cell as `(x, y)`.
+    /// - `to`: The second cell as `(x, y)`.
+    ///
+    /// # Returns
+    /// `true` if every cell touched by the line is passable.
+    pub fn has_line_of_sight(&self, from: (usize, usize), to: (usize, usize)) -> bool {
+        self.is_segment_clear((from.0 as f64, from.1 as f64), (to.0 as f64, to.1 as f64))
+    }
+
+    /// Removes the positions in the middle of straight runs, keeping the turns.
+    ///
+    /// # Parameters
+    /// - `path`: The path to simplify.
+    ///
+    /// # Returns
+    /// The start, every position where the direction changes and the goal.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, Node, PathSmoother};
+    ///
+    /// let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 4]; 4]));
+    /// let path = astar.find_shortest_path((0, 0), (3, 3)).unwrap();
+    /// let waypoints = PathSmoother::new(astar.nodes()).simplify(&path);
+    /// assert_eq!(waypoints, vec![(0, 0), (3, 0), (3, 3)]);
+    /// ```
+    pub fn simplify(&self, path: &Path) -> Vec<(usize, usize)> {
+        let mut waypoints = vec![path.start()];
+        waypoints.extend(path.segments().iter().map(|segment| segment.end));
+        waypoints
+    }
+
+    /// Removes the positions that can be skipped by walking in a straight line (string pulling).
+    ///
+    /// From each kept position, the path jumps to the furthest later position in line
+    /// of sight. Runs separated by custom edge steps are pulled separately.
+    ///
+    /// # Parameters
+    /// - `path`: The path to shorten.
+    ///
+    /// # Returns
+    /// The waypoints of the shortened path, from the start to the goal.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, Node, PathSmoother};
+    ///
+    /// let grid = vec![
+    ///     vec![0, 0, 0, 0],
+    ///     vec![0, 0, 0, 0],
+    ///     vec![0, 0, 0, 1],
+    /// ];
+    /// let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+    /// let path = astar.find_shortest_path((0, 0), (3, 1)).unwrap();
+    /// let waypoints = PathSmoother::new(astar.nodes()).string_pull(&path);
+    /// assert_eq!(waypoints, vec![(0, 0), (3, 1)]);
+    /// ```
+    pub fn string_pull(&self, path: &Path) -> Vec<(usize, usize)> {
+        let positions = path.positions();
+        let mut waypoints = vec![positions[0]];
+
+        // Positions joined by custom edges are anchors that cannot be skipped
+        let mut run_start = 0;
+        for (index, direction) in path.directions().iter().enumerate() {
+            if direction.is_none() {
+                self.pull_run(&positions[run_start..=index], &mut waypoints);
+                waypoints.push(positions[index + 1]);
+                run_start = index + 1;
+            }
+        }
+        self.pull_run(&positions[run_start..], &mut waypoints);
+
+        waypoints
+    }
+
+    /// Smooths waypoints into a Catmull-Rom spline checked against obstacles.
+    ///
+    /// Every part of the curve between two waypoints is sampled and kept only if
+    /// the samples are linked by clear lines; otherwise that part stays a straight
+    /// line. Waypoints should be in line of sight of each other, as returned by
+    /// `PathSmoother::string_pull`.
+    ///
+    /// # Parameters
+    /// - `waypoints`: The positions the curve passes through, as `(x, y)`.
+    /// - `samples`: The number of points per part of the curve, at least `1`.
+    ///
+    /// # Returns
+    /// The points of the curve, starting at the first waypoint and ending at the last.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{Node, PathSmoother};
+    ///
+    /// let nodes = Node::grid_to_nodes(&vec![vec![0; 5]; 5]);
+    /// let curve = PathSmoother::new(&nodes).catmull_rom(&[(0, 0), (4, 0), (4, 4)], 4);
+    /// assert_eq!(curve.len(), 9);
+    /// assert_eq!(curve[0], (0.0, 0.0));
+    /// assert_eq!(curve[8], (4.0, 4.0));
+    /// ```
+    pub fn catmull_rom(&self, waypoints: &[(usize, usize)], samples: usize) -> Vec<(f64, f64)> {
+        let points: Vec<(f64, f64)> = waypoints
+            .iter()
+            .map(|&(x, y)| (x as f64, y as f64))
+            .collect();
+        let Some(&last) = points.last() else {
+            return Vec::new();
+        };
+        let samples = samples.max(1);
+
+        let mut curve = Vec::new();
+        for index in 0..points.len() - 1 {
+            let p0 = points[index.saturating_sub(1)];
+            let (p1, p2) = (points[index], points[index + 1]);
+            let p3 = points[(index + 2).min(points.len() - 1)];
+
+            let spline: Vec<_> = (0..=samples)
+                .map(|sample| catmull_rom_point(p0, p1, p2, p3, sample as f64 / samples as f64))
+                .collect();
+            let clear = spline
+                .windows(2)
+                .all(|pair| self.is_segment_clear(pair[0], pair[1]));
+
+            if clear {
+                curve.extend_from_slice(&spline[..samples]);
+            } else {
+                curve.extend((0..samples).map(|sample| {
+                    let t = sample as f64 / samples as f64;
+                    (p1.0 + (p2.0 - p1.0) * t, p1.1 + (p2.1 - p1.1) * t)
+                }));
+            }
+        }
+        curve.push(last);
+        curve
+    }
+
+    /// Appends the string-pulled waypoints of a run of positions, except its first position.
+    fn pull_run(&self, run: &[(usize, usize)], waypoints: &mut Vec<(usize, usize)>) {
+        let mut anchor = 0;
+        let mut next = 1;
+        while next < run.len() {
+            if next + 1 < run.len() && self.has_line_of_sight(run[anchor], run[next + 1]) {
+                next += 1;
+                continue;
+            }
+            waypoints.push(run[next]);
+            anchor = next;
+            next += 1;
+        }
+    }
+
+    /// Checks whether the cell at `(x, y)` exists and is passable.
+    fn is_free(&self, x: isize, y: isize) -> bool {
+        x >= 0
+            && y >= 0
+            && self
+                .nodes
+                .get(&(y as usize, x as usize))
+                .is_some_and(|node| !node.is_blocked)
+    }
+
+    /// Walks the cells crossed by a line segment and checks that all are passable.
+    ///
+    /// Uses a grid traversal in which cell `(x, y)` covers `[x - 0.5, x + 0.5)` on
+    /// both axes. Where the line passes exactly through a corner, both cells beside
+    /// the corner must be passable.
+    fn is_segment_clear(&self, from: (f64, f64), to: (f64, f64)) -> bool {
+        let (x0, y0) = (from.0 + 0.5, from.1 + 0.5);
+        let (x1, y1) = (to.0 + 0.5, to.1 + 0.5);
+        let (mut x, mut y) = (x0.floor() as isize, y0.floor() as isize);
+        let (end_x, end_y) = (x1.floor() as isize, y1.floor() as isize);
+        if !self.is_free(x, y) {
+            return false;
+        }
+
+        let (dx, dy) = (x1 - x0, y1 - y0);
+        let step_x = if dx > 0.0 { 1 } else { -1 };
+        let step_y = if dy > 0.0 { 1 } else { -1 };
+        let boundary = |start: f64, cell: isize, delta: f64| {
+            if delta > 0.0 {
+                ((cell + 1) as f64 - start) / delta
+            } else if delta < 0.0 {
+                (start - cell as f64) / -delta
+            } else {
+                f64::INFINITY
+            }
+        };
+        let mut t_x = boundary(x0, x, dx);
+        let mut t_y = boundary(y0, y, dy);
+        let t_delta_x = if dx != 0.0 {
+            1.0 / dx.abs()
+        } else {
+            f64::INFINITY
+        };
+        let t_delta_y = if dy != 0.0 {
+            1.0 / dy.abs()
+        } else {
+            f64::INFINITY
+        };
+
+        // Every iteration moves one cell closer to the end, this bounds rounding errors
+        let mut remaining = x.abs_diff(end_x) + y.abs_diff(end_y);
+        while (x, y) != (end_x, end_y) && remaining > 0 {
+            if (t_x - t_y).abs() < CORNER_EPSILON {
+                if !self.is_free(x + step_x, y) || !self.is_free(x, y + step_y) {
+                    return false;
+                }
+                x += step_x;
+                y += step_y;
+                t_x += t_delta_x;
+                t_y += t_delta_y;
+                remaining = remaining.saturating_sub(2);
+            } else if t_x < t_y {
+                x += step_x;
+                t_x += t_delta_x;
+                remaining -= 1;
+            } else {
+                y += step_y;
+                t_y += t_delta_y;
+                remaining -= 1;
+            }
+            if !self.is_free(x, y) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Evaluates a uniform Catmull-Rom spline between `p1` and `p2` at `t` in `[0, 1]`.
+fn catmull_rom_point(
+    p0: (f64, f64),
+    p1: (f64, f64),
+    p2: (f64, f64),
+    p3: (f64, f64),
+    t: f64,
+) -> (f64, f64) {
+    let (t2, t3) = (t * t, t * t * t);
+    let axis = |a: f64, b: f64, c: f64, d: f64| {
+        0.5 * (2.0 * b
+            + (c - a) * t
+            + (2.0 * a - 5.0 * b + 4.0 * c - d) * t2
+            + (3.0 * b - a - 3.0 * c + d) * t3)
+    };
+    (axis(p0.0, p1.0, p2.0, p3.0), axis(p0.1, p1.1, p2.1, p3.1))
+}
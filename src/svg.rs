@@ -0,0 +1,341 @@
+use crate::astar::AStar;
+use crate::node::Node;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+/// Fill color of blocked cells.
+const BLOCKED_COLOR: &str = "#333333";
+/// Fill color of terrain costs, drawn more opaque the higher the cost.
+const COST_COLOR: &str = "#a0522d";
+/// Fill color of positions left in the open set.
+const OPEN_COLOR: &str = "#7ccf7c";
+/// Fill color of positions expanded by the search.
+const CLOSED_COLOR: &str = "#7ca7cf";
+/// Stroke color of directional neighbor edges.
+const EDGE_COLOR: &str = "#555555";
+/// Stroke color of custom edges.
+const CUSTOM_EDGE_COLOR: &str = "#8e44ad";
+/// Stroke color of the path.
+const PATH_COLOR: &str = "#d62728";
+
+/// Which neighbor edges an SVG shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EdgeDisplay {
+    /// No edges.
+    None,
+    /// Edges that differ from a plain four-connected grid: diagonal and
+    /// non-adjacent neighbors, one-way links and custom edges.
+    #[default]
+    Irregular,
+    /// Every edge.
+    All,
+}
+
+/// Options for rendering an SVG.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SvgOptions {
+    /// The width and height of a cell in pixels.
+    pub cell_size: usize,
+    /// Which neighbor edges to draw.
+    pub edges: EdgeDisplay,
+    /// Whether cells with a cost other than `1` are labelled with their cost.
+    pub cost_labels: bool,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            cell_size: 24,
+            edges: EdgeDisplay::default(),
+            cost_labels: false,
+        }
+    }
+}
+
+/// Renders a node map as an SVG image.
+///
+/// The image shows blocked cells, terrain costs as shading, neighbor and custom
+/// edges as arrows and, when given, the open and closed sets of a search and a path.
+/// Cells are laid out like in `Node::print_grid`: a node keyed `(row, col)` is
+/// drawn at `x = col`, `y = row`.
+#[derive(Debug, Clone)]
+pub struct SvgRenderer<'a> {
+    nodes: &'a HashMap<(usize, usize), Node>,
+    options: SvgOptions,
+    path: Vec<(usize, usize)>,
+    open: HashSet<(usize, usize)>,
+    closed: HashSet<(usize, usize)>,
+}
+
+impl<'a> SvgRenderer<'a> {
+    /// Creates a renderer for a node map with the default options.
+    ///
+    /// # Parameters
+    /// - `nodes`: The node map to draw.
+    ///
+    /// # Returns
+    /// A new `SvgRenderer` without a path or search.
+    pub fn new(nodes: &'a HashMap<(usize, usize), Node>) -> Self {
+        SvgRenderer {
+            nodes,
+            options: SvgOptions::default(),
+            path: Vec::new(),
+            open: HashSet::new(),
+            closed: HashSet::new(),
+        }
+    }
+
+    /// Sets the options used by `SvgRenderer::render`.
+    pub fn set_options(&mut self, options: SvgOptions) {
+        self.options = options;
+    }
+
+    /// Sets the path to draw, as returned by `AStar::find_shortest_path`.
+    pub fn set_path(&mut self, path: &[(usize, usize)]) {
+        self.path = path.to_vec();
+    }
+
+    /// Takes the open and closed sets of the last search of `astar`.
+    pub fn set_search(&mut self, astar: &AStar) {
+        self.open = astar.open_positions();
+        self.closed = astar.closed_positions();
+    }
+
+    /// Renders the SVG document.
+    ///
+    /// # Returns
+    /// The SVG document as a string.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{AStar, Direction, Node, SvgRenderer};
+    ///
+    /// let mut nodes = Node::grid_to_nodes(&vec![vec![0, 0, 0], vec![0, 1, 0]]);
+    /// nodes.get_mut(&(0, 0)).unwrap().set_neighbor(Direction::SouthEast, Some((1, 1)));
+    ///
+    /// let mut astar = AStar::new(nodes.clone());
+    /// let path = astar.find_shortest_path((0, 0), (2, 1)).unwrap();
+    ///
+    /// let mut renderer = SvgRenderer::new(&nodes);
+    /// renderer.set_path(path.positions());
+    /// renderer.set_search(&astar);
+    /// let svg = renderer.render();
+    /// assert!(svg.starts_with("<svg"));
+    /// ```
+    pub fn render(&self) -> String {
+        let size = self.options.cell_size as f64;
+        let width = self
+            .nodes
+            .keys()
+            .map(|&(_, col)| col + 1)
+            .max()
+            .unwrap_or(0);
+        let height = self
+            .nodes
+            .keys()
+            .map(|&(row, _)| row + 1)
+            .max()
+            .unwrap_or(0);
+        let max_cost = self
+            .nodes
+            .values()
+            .filter(|node| !node.is_blocked)
+            .map(|node| node.cost)
+            .max()
+            .unwrap_or(1);
+
+        let mut svg = String::new();
+        let (pixel_width, pixel_height) = (width as f64 * size, height as f64 * size);
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+            pixel_width, pixel_height
+        );
+        let _ = writeln!(
+            svg,
+            r#"<defs><marker id="arrow" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="6" markerHeight="6" orient="auto-start-reverse"><path d="M 0 0 L 10 5 L 0 10 z" fill="context-stroke"/></marker></defs>"#
+        );
+
+        // Cells, sorted so the output is stable
+        let mut positions: Vec<_> = self.nodes.keys().copied().collect();
+        positions.sort();
+        for &(row, col) in &positions {
+            let node = &self.nodes[&(row, col)];
+            let (x, y) = (col as f64 * size, row as f64 * size);
+            let fill = if node.is_blocked {
+                BLOCKED_COLOR
+            } else {
+                "#ffffff"
+            };
+            let _ = writeln!(
+                svg,
+                r##"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" stroke="#cccccc"/>"##,
+                x, y, size, size, fill
+            );
+            if node.is_blocked {
+                continue;
+            }
+
+            if node.cost != 1 && max_cost > 1 {
+                let opacity =
+                    0.15 + 0.6 * node.cost.saturating_sub(1) as f64 / (max_cost - 1) as f64;
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" fill-opacity="{:.2}"/>"#,
+                    x, y, size, size, COST_COLOR, opacity
+                );
+            }
+
+            let searched = if self.closed.contains(&(col, row)) {
+                Some(CLOSED_COLOR)
+            } else if self.open.contains(&(col, row)) {
+                Some(OPEN_COLOR)
+            } else {
+                None
+            };
+            if let Some(color) = searched {
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" fill-opacity="0.5"/>"#,
+                    x, y, size, size, color
+                );
+            }
+
+            if self.options.cost_labels && node.cost != 1 {
+                let _ = writeln!(
+                    svg,
+                    r#"<text x="{}" y="{}" font-size="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                    x + size / 2.0,
+                    y + size / 2.0,
+                    size * 0.4,
+                    node.cost
+                );
+            }
+        }
+
+        if self.options.edges != EdgeDisplay::None {
+            for &position in &positions {
+                self.write_edges(&mut svg, position);
+            }
+        }
+
+        if !self.path.is_empty() {
+            let points: Vec<String> = self
+                .path
+                .iter()
+                .map(|&(x, y)| format!("{},{}", (x as f64 + 0.5) * size, (y as f64 + 0.5) * size))
+                .collect();
+            let _ = writeln!(
+                svg,
+                r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linejoin="round" stroke-linecap="round"/>"#,
+                points.join(" "),
+                PATH_COLOR,
+                size * 0.15
+            );
+            for (&(x, y), fill) in [
+                (self.path.first(), "#2ca02c"),
+                (self.path.last(), PATH_COLOR),
+            ]
+            .iter()
+            .filter_map(|(position, fill)| position.map(|position| (position, *fill)))
+            {
+                let _ = writeln!(
+                    svg,
+                    r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+                    (x as f64 + 0.5) * size,
+                    (y as f64 + 0.5) * size,
+                    size * 0.25,
+                    fill
+                );
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Writes the edges leaving a node as arrows.
+    fn write_edges(&self, svg: &mut String, position: (usize, usize)) {
+        let node = &self.nodes[&position];
+        if node.is_blocked {
+            return;
+        }
+
+        let neighbors = node
+            .neighbors
+            .values()
+            .flatten()
+            .map(|&target| (target, false));
+        let custom_edges = node.custom_edges.iter().map(|edge| (edge.target, true));
+
+        for (target, custom) in neighbors.chain(custom_edges) {
+            let Some(target_node) = self.nodes.get(&target) else {
+                continue;
+            };
+            if target_node.is_blocked {
+                continue;
+            }
+
+            // A plain grid edge links adjacent cells in both directions
+            let adjacent = position.0.abs_diff(target.0) + position.1.abs_diff(target.1) == 1;
+            let two_way = target_node
+                .neighbors
+                .values()
+                .flatten()
+                .any(|&pos| pos == position);
+            if self.options.edges == EdgeDisplay::Irregular && !custom && adjacent && two_way {
+                continue;
+            }
+
+            self.write_arrow(svg, position, target, custom);
+        }
+    }
+
+    /// Writes an arrow between the centres of two cells.
+    ///
+    /// The arrow is shortened at both ends and shifted to its right, so the two
+    /// arrows of a two-way link do not overlap.
+    fn write_arrow(
+        &self,
+        svg: &mut String,
+        from: (usize, usize),
+        to: (usize, usize),
+        custom: bool,
+    ) {
+        let size = self.options.cell_size as f64;
+        let center =
+            |(row, col): (usize, usize)| ((col as f64 + 0.5) * size, (row as f64 + 0.5) * size);
+        let (x1, y1) = center(from);
+        let (x2, y2) = center(to);
+
+        let length = ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt();
+        if length == 0.0 {
+            return;
+        }
+        let (dx, dy) = ((x2 - x1) / length, (y2 - y1) / length);
+        let (shift_x, shift_y) = (-dy * size * 0.08, dx * size * 0.08);
+        let inset = size * 0.2;
+
+        let color = if custom {
+            CUSTOM_EDGE_COLOR
+        } else {
+            EDGE_COLOR
+        };
+        let dash = if custom {
+            r#" stroke-dasharray="4 2""#
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{}" stroke-width="{:.1}"{} marker-end="url(#arrow)"/>"#,
+            x1 + dx * inset + shift_x,
+            y1 + dy * inset + shift_y,
+            x2 - dx * inset + shift_x,
+            y2 - dy * inset + shift_y,
+            color,
+            (size * 0.05).max(1.0),
+            dash
+        );
+    }
+}
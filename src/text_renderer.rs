@@ -0,0 +1,234 @@
+use crate::astar::AStar;
+use crate::node::Node;
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+
+/// ANSI escape code that resets all colors.
+const ANSI_RESET: &str = "\x1b[0m";
+/// ANSI 256-color backgrounds of the heatmap, from cheap to expensive.
+const HEATMAP_COLORS: [u8; 10] = [46, 82, 118, 154, 190, 226, 220, 214, 208, 202];
+
+/// Characters used to draw the cells of a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyphs {
+    pub free: char,
+    pub blocked: char,
+    pub path: char,
+    pub start: char,
+    pub goal: char,
+    /// Used for positions missing from the node map.
+    pub missing: char,
+}
+
+impl Default for Glyphs {
+    fn default() -> Self {
+        Glyphs {
+            free: '.',
+            blocked: '#',
+            path: 'o',
+            start: 'S',
+            goal: 'G',
+            missing: ' ',
+        }
+    }
+}
+
+/// Options for rendering a grid as text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextOptions {
+    pub glyphs: Glyphs,
+    /// Whether the first and last positions of the path use the start and goal glyphs.
+    pub endpoints: bool,
+    /// Whether path cells show an arrow pointing to the next cell instead of the path glyph.
+    pub arrows: bool,
+    /// Whether ANSI escape codes are used to color the cells.
+    pub colors: bool,
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        TextOptions {
+            glyphs: Glyphs::default(),
+            endpoints: true,
+            arrows: false,
+            colors: false,
+        }
+    }
+}
+
+/// Renders a node map as text, one character and a space per cell.
+///
+/// Blocked cells are always shown. A path, start and goal markers and a heatmap
+/// of g-scores can be drawn on top. Cells are laid out like in `Node::print_grid`:
+/// a node keyed `(row, col)` is drawn in line `row`, column `col`.
+#[derive(Debug, Clone)]
+pub struct TextRenderer<'a> {
+    nodes: &'a HashMap<(usize, usize), Node>,
+    options: TextOptions,
+    /// Index of every path position in the path, for constant-time lookups.
+    path: HashMap<(usize, usize), usize>,
+    path_positions: Vec<(usize, usize)>,
+    scores: HashMap<(usize, usize), usize>,
+}
+
+impl<'a> TextRenderer<'a> {
+    /// Creates a renderer for a node map with the default options.
+    ///
+    /// # Parameters
+    /// - `nodes`: The node map to draw.
+    ///
+    /// # Returns
+    /// A new `TextRenderer` without a path or heatmap.
+    pub fn new(nodes: &'a HashMap<(usize, usize), Node>) -> Self {
+        TextRenderer {
+            nodes,
+            options: TextOptions::default(),
+            path: HashMap::new(),
+            path_positions: Vec::new(),
+            scores: HashMap::new(),
+        }
+    }
+
+    /// Sets the options used for rendering.
+    pub fn set_options(&mut self, options: TextOptions) {
+        self.options = options;
+    }
+
+    /// Sets the path to draw, as returned by `AStar::find_shortest_path`.
+    pub fn set_path(&mut self, path: &[(usize, usize)]) {
+        self.path_positions = path.to_vec();
+        self.path = path
+            .iter()
+            .enumerate()
+            .map(|(index, &position)| (position, index))
+            .collect();
+    }
+
+    /// Sets the g-scores shown as a heatmap, keyed by `(x, y)`.
+    ///
+    /// Cells are drawn as digits from `0` (cheapest) to `9` (most expensive),
+    /// or with colored backgrounds when colors are enabled.
+    pub fn set_scores(&mut self, scores: HashMap<(usize, usize), usize>) {
+        self.scores = scores;
+    }
+
+    /// Shows the g-scores of the last search of `astar` as a heatmap.
+    pub fn set_search(&mut self, astar: &AStar) {
+        self.set_scores(astar.g_scores());
+    }
+
+    /// Renders the grid into any `fmt::Write`.
+    ///
+    /// # Parameters
+    /// - `out`: The writer to render into.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{Node, TextRenderer};
+    ///
+    /// let nodes = Node::grid_to_nodes(&vec![vec![0, 0, 0], vec![1, 1, 0]]);
+    /// let mut renderer = TextRenderer::new(&nodes);
+    /// renderer.set_path(&[(0, 0), (1, 0), (2, 0), (2, 1)]);
+    ///
+    /// let mut text = String::new();
+    /// renderer.render(&mut text).unwrap();
+    /// assert_eq!(text, "S o o \n# # G \n");
+    /// ```
+    pub fn render(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        let width = self
+            .nodes
+            .keys()
+            .map(|&(_, col)| col + 1)
+            .max()
+            .unwrap_or(0);
+        let height = self
+            .nodes
+            .keys()
+            .map(|&(row, _)| row + 1)
+            .max()
+            .unwrap_or(0);
+        let max_score = self.scores.values().copied().max().unwrap_or(0);
+
+        for row in 0..height {
+            for col in 0..width {
+                let (glyph, color) = self.cell(col, row, max_score);
+                match color.filter(|_| self.options.colors) {
+                    Some(color) => write!(out, "{}{}{} ", color, glyph, ANSI_RESET)?,
+                    None => write!(out, "{} ", glyph)?,
+                }
+            }
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the rendered grid to any `io::Write`, such as stdout or a file.
+    pub fn write_to(&self, out: &mut impl io::Write) -> io::Result<()> {
+        write!(out, "{}", self)
+    }
+
+    /// Returns the glyph of a cell and the ANSI code to color it with, if any.
+    fn cell(&self, x: usize, y: usize, max_score: usize) -> (char, Option<String>) {
+        let glyphs = &self.options.glyphs;
+        let Some(node) = self.nodes.get(&(y, x)) else {
+            return (glyphs.missing, None);
+        };
+        if node.is_blocked {
+            return (glyphs.blocked, Some("\x1b[90m".to_string()));
+        }
+
+        if let Some(&index) = self.path.get(&(x, y)) {
+            let last = self.path_positions.len() - 1;
+            if self.options.endpoints && index == 0 {
+                return (glyphs.start, Some("\x1b[1;32m".to_string()));
+            }
+            if self.options.endpoints && index == last {
+                return (glyphs.goal, Some("\x1b[1;31m".to_string()));
+            }
+            let glyph = match self.path_positions.get(index + 1) {
+                Some(&next) if self.options.arrows => arrow((x, y), next),
+                _ => glyphs.path,
+            };
+            return (glyph, Some("\x1b[1;33m".to_string()));
+        }
+
+        if let Some(&score) = self.scores.get(&(x, y)) {
+            let level = (score * (HEATMAP_COLORS.len() - 1))
+                .checked_div(max_score)
+                .unwrap_or(0);
+            let digit = char::from(b'0' + level as u8);
+            return (
+                digit,
+                Some(format!("\x1b[30;48;5;{}m", HEATMAP_COLORS[level])),
+            );
+        }
+
+        (glyphs.free, None)
+    }
+}
+
+impl fmt::Display for TextRenderer<'_> {
+    /// Formats the rendered grid, see `TextRenderer::render`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f)
+    }
+}
+
+/// Returns the arrow pointing from one position to the next, or `*` for jumps.
+fn arrow(from: (usize, usize), to: (usize, usize)) -> char {
+    let dx = to.0 as isize - from.0 as isize;
+    let dy = to.1 as isize - from.1 as isize;
+    match (dx, dy) {
+        (1, 0) => '→',
+        (-1, 0) => '←',
+        (0, -1) => '↑',
+        (0, 1) => '↓',
+        (1, -1) => '↗',
+        (-1, -1) => '↖',
+        (1, 1) => '↘',
+        (-1, 1) => '↙',
+        _ => '*',
+    }
+}
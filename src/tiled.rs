@@ -0,0 +1,603 @@
+use crate::node::{Direction, Node};
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+
+/// Bits Tiled sets in a global tile id to flip or rotate the tile.
+const FLIP_FLAGS: u32 = 0xF000_0000;
+
+/// Errors that can occur while importing a Tiled map.
+#[derive(Debug)]
+pub enum TiledError {
+    /// The JSON form could not be read.
+    #[cfg(feature = "serde")]
+    Json(serde_json::Error),
+    /// The TMX form is not well-formed XML.
+    InvalidXml(String),
+    /// A required value is missing or malformed.
+    InvalidMap(String),
+    /// The map uses a Tiled feature this importer does not read.
+    Unsupported(String),
+    /// A layer named in the options does not exist.
+    UnknownLayer(String),
+}
+
+impl fmt::Display for TiledError {
+    /// Formats the error for display in user-facing contexts.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "serde")]
+            TiledError::Json(error) => write!(f, "Invalid Tiled JSON map: {}", error),
+            TiledError::InvalidXml(message) => write!(f, "Invalid Tiled TMX map: {}", message),
+            TiledError::InvalidMap(message) => write!(f, "Invalid Tiled map: {}", message),
+            TiledError::Unsupported(message) => write!(f, "Unsupported Tiled map: {}", message),
+            TiledError::UnknownLayer(name) => write!(f, "The map has no tile layer {:?}!", name),
+        }
+    }
+}
+
+impl Error for TiledError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            #[cfg(feature = "serde")]
+            TiledError::Json(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Options for importing a Tiled map.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TiledOptions {
+    /// Names of the tile layers whose tile properties are read. All tile layers
+    /// are read if empty.
+    pub layers: Vec<String>,
+    /// Name of a layer on which every tile blocks its cell, whatever its properties.
+    pub collision_layer: Option<String>,
+}
+
+/// A node map imported from a Tiled map.
+///
+/// Tiles are configured through their custom properties in the tileset:
+/// - `blocked` (bool): the cell cannot be entered;
+/// - `cost` (int): the cost of entering the cell, the largest cost wins when tiles are stacked;
+/// - `directions` (string): the directions the cell may be left in, such as
+///   `"north, east"` or `"n e"`. Stacked tiles only allow the directions they all allow,
+///   so a one-way platform is a tile with a single direction.
+///
+/// Nodes are keyed like in `Node::grid_to_nodes` and connected in four
+/// directions, named as seen in Tiled: `Direction::North` is the row above.
+#[derive(Debug, Clone)]
+pub struct TiledMap {
+    pub width: usize,
+    pub height: usize,
+    pub nodes: HashMap<(usize, usize), Node>,
+}
+
+impl TiledMap {
+    /// Imports a map saved in Tiled's TMX (XML) format.
+    ///
+    /// Tile layers must use the CSV or XML encoding and tilesets must be embedded.
+    ///
+    /// # Parameters
+    /// - `text`: The content of the `.tmx` file.
+    /// - `options`: The layers to read.
+    ///
+    /// # Returns
+    /// The imported `TiledMap`, or a `TiledError` if the map cannot be read.
+    ///
+    /// # Example
+    /// ```rust
+    /// use controlled_astar::{Direction, TiledMap, TiledOptions};
+    ///
+    /// let tmx = r#"<map width="2" height="1">
+    ///   <tileset firstgid="1">
+    ///     <tile id="0"><properties><property name="directions" value="east"/></properties></tile>
+    ///     <tile id="1"><properties><property name="cost" type="int" value="3"/></properties></tile>
+    ///   </tileset>
+    ///   <layer name="ground" width="2" height="1"><data encoding="csv">1,2</data></layer>
+    /// </map>"#;
+    /// let map = TiledMap::from_tmx(tmx, &TiledOptions::default()).unwrap();
+    /// assert_eq!(map.nodes[&(0, 0)].get_directions(), vec![Direction::East]);
+    /// assert_eq!(map.nodes[&(0, 1)].cost, 3);
+    /// ```
+    pub fn from_tmx(text: &str, options: &TiledOptions) -> Result<Self, TiledError> {
+        TiledMap::build(read_tmx(text)?, options)
+    }
+
+    /// Imports a map saved in Tiled's JSON format.
+    ///
+    /// Tile layers must not be base64 encoded and tilesets must be embedded.
+    ///
+    /// # Parameters
+    /// - `text`: The content of the `.tmj` or `.json` file.
+    /// - `options`: The layers to read.
+    ///
+    /// # Returns
+    /// The imported `TiledMap`, or a `TiledError` if the map cannot be read.
+    #[cfg(feature = "serde")]
+    pub fn from_json(text: &str, options: &TiledOptions) -> Result<Self, TiledError> {
+        let value: serde_json::Value = serde_json::from_str(text).map_err(TiledError::Json)?;
+        TiledMap::build(read_json(&value)?, options)
+    }
+
+    /// Turns the tile ids of the chosen layers into nodes.
+    fn build(raw: RawMap, options: &TiledOptions) -> Result<Self, TiledError> {
+        let (width, height) = (raw.width, raw.height);
+        if width == 0 || height == 0 {
+            return Err(TiledError::InvalidMap("the map is empty".to_string()));
+        }
+        let tiles = width.checked_mul(height).ok_or_else(|| {
+            TiledError::InvalidMap(format!("the map size {}x{} is too large", width, height))
+        })?;
+        for layer in &raw.layers {
+            if layer.gids.len() != tiles {
+                return Err(TiledError::InvalidMap(format!(
+                    "layer {:?} has {} tiles, expected {}",
+                    layer.name,
+                    layer.gids.len(),
+                    tiles
+                )));
+            }
+        }
+
+        let find_layer = |name: &String| {
+            raw.layers
+                .iter()
+                .find(|layer| &layer.name == name)
+                .ok_or_else(|| TiledError::UnknownLayer(name.clone()))
+        };
+        let property_layers = if options.layers.is_empty() {
+            raw.layers.iter().collect()
+        } else {
+            options
+                .layers
+                .iter()
+                .map(find_layer)
+                .collect::<Result<Vec<_>, _>>()?
+        };
+        let collision_layer = options
+            .collision_layer
+            .as_ref()
+            .map(find_layer)
+            .transpose()?;
+
+        let mut nodes = HashMap::new();
+        for row in 0..height {
+            for col in 0..width {
+                let index = row * width + col;
+                let mut is_blocked = collision_layer.is_some_and(|layer| layer.gids[index] != 0);
+                let mut cost = None;
+                let mut directions: Option<Vec<Direction>> = None;
+
+                for layer in &property_layers {
+                    let Some(properties) = raw.tiles.get(&layer.gids[index]) else {
+                        continue;
+                    };
+                    is_blocked |= properties.blocked;
+                    if let Some(tile_cost) = properties.cost {
+                        cost = Some(cost.map_or(tile_cost, |cost: usize| cost.max(tile_cost)));
+                    }
+                    if let Some(allowed) = &properties.directions {
+                        directions = Some(match directions {
+                            None => allowed.clone(),
+                            Some(current) => current
+                                .into_iter()
+                                .filter(|direction| allowed.contains(direction))
+                                .collect(),
+                        });
+                    }
+                }
+
+                let mut neighbors = BTreeMap::new();
+                let candidates = [
+                    (Direction::North, row > 0, (row.wrapping_sub(1), col)),
+                    (Direction::South, row + 1 < height, (row + 1, col)),
+                    (Direction::West, col > 0, (row, col.wrapping_sub(1))),
+                    (Direction::East, col + 1 < width, (row, col + 1)),
+                ];
+                for (direction, inside, position) in candidates {
+                    let allowed = directions
+                        .as_ref()
+                        .is_none_or(|allowed| allowed.contains(&direction));
+                    if inside && allowed {
+                        neighbors.insert(direction, Some(position));
+                    }
+                }
+
+                nodes.insert(
+                    (row, col),
+                    Node {
+                        x: row,
+                        y: col,
+                        is_blocked,
+                        neighbors,
+                        custom_edges: Vec::new(),
+                        cost: cost.unwrap_or(1),
+                    },
+                );
+            }
+        }
+
+        Ok(TiledMap {
+            width,
+            height,
+            nodes,
+        })
+    }
+}
+
+/// The parts of a Tiled map the importer needs, independent of the file format.
+#[derive(Default)]
+struct RawMap {
+    width: usize,
+    height: usize,
+    /// Properties of the tiles that have any, by global tile id.
+    tiles: HashMap<u32, TileProperties>,
+    layers: Vec<RawLayer>,
+}
+
+/// A tile layer with the global tile ids of its cells, row by row.
+struct RawLayer {
+    name: String,
+    gids: Vec<u32>,
+}
+
+/// The custom properties of a tile that affect pathfinding.
+#[derive(Default)]
+struct TileProperties {
+    blocked: bool,
+    cost: Option<usize>,
+    directions: Option<Vec<Direction>>,
+}
+
+impl TileProperties {
+    /// Reads a custom property given as text, ignoring properties that do not affect pathfinding.
+    fn set(&mut self, name: &str, value: &str) -> Result<(), TiledError> {
+        let invalid = || TiledError::InvalidMap(format!("invalid {} property {:?}", name, value));
+        match name {
+            "blocked" => self.blocked = value.parse().map_err(|_| invalid())?,
+            "cost" => self.cost = Some(value.parse().map_err(|_| invalid())?),
+            "directions" => {
+                let directions = value
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|name| !name.is_empty())
+                    .map(|name| parse_direction(name).ok_or_else(invalid))
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.directions = Some(directions);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Parses the name of one of the four straight directions.
+fn parse_direction(name: &str) -> Option<Direction> {
+    match name.to_ascii_lowercase().as_str() {
+        "north" | "n" => Some(Direction::North),
+        "south" | "s" => Some(Direction::South),
+        "west" | "w" => Some(Direction::West),
+        "east" | "e" => Some(Direction::East),
+        _ => None,
+    }
+}
+
+/// Strips the flip flags from a global tile id.
+fn tile_id(gid: u32) -> u32 {
+    gid & !FLIP_FLAGS
+}
+
+/// Turns the local id of a tile into its global id.
+fn global_id(first_gid: u32, id: u32) -> Result<u32, TiledError> {
+    first_gid.checked_add(id).ok_or_else(|| {
+        TiledError::InvalidMap(format!(
+            "tile id {} is too large for firstgid {}",
+            id, first_gid
+        ))
+    })
+}
+
+/// Parses a required numeric attribute or field.
+fn parse_number<T: std::str::FromStr>(value: Option<&str>, name: &str) -> Result<T, TiledError> {
+    value
+        .and_then(|value| value.trim().parse().ok())
+        .ok_or_else(|| TiledError::InvalidMap(format!("missing or invalid {}", name)))
+}
+
+/// A piece of a TMX document.
+enum XmlToken {
+    Start {
+        name: String,
+        attributes: HashMap<String, String>,
+        empty: bool,
+    },
+    End(String),
+    Text(String),
+}
+
+/// Splits a TMX document into tags and text.
+///
+/// This reads the subset of XML Tiled writes: elements, attributes, text,
+/// comments and the XML declaration.
+fn tokenize_xml(text: &str) -> Result<Vec<XmlToken>, TiledError> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('<') {
+        if !rest[..start].trim().is_empty() {
+            tokens.push(XmlToken::Text(unescape_xml(&rest[..start])));
+        }
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment
+                .find("-->")
+                .ok_or_else(|| TiledError::InvalidXml("unterminated comment".to_string()))?;
+            rest = &comment[end + 3..];
+            continue;
+        }
+
+        let end = rest
+            .find('>')
+            .ok_or_else(|| TiledError::InvalidXml("unterminated tag".to_string()))?;
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if tag.starts_with('?') || tag.starts_with('!') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(XmlToken::End(name.trim().to_string()));
+            continue;
+        }
+
+        let (tag, empty) = match tag.strip_suffix('/') {
+            Some(tag) => (tag, true),
+            None => (tag, false),
+        };
+        let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+        let name = tag[..name_end].to_string();
+
+        let mut attributes = HashMap::new();
+        let mut attribute_text = tag[name_end..].trim_start();
+        while !attribute_text.is_empty() {
+            let invalid = || TiledError::InvalidXml(format!("malformed attributes in <{}>", name));
+            let (key, value_text) = attribute_text.split_once('=').ok_or_else(invalid)?;
+            let value_text = value_text.trim_start();
+            let quote = value_text
+                .chars()
+                .next()
+                .filter(|c| *c == '"' || *c == '\'');
+            let quote = quote.ok_or_else(invalid)?;
+            let value_end = value_text[1..].find(quote).ok_or_else(invalid)?;
+            attributes.insert(
+                key.trim().to_string(),
+                unescape_xml(&value_text[1..=value_end]),
+            );
+            attribute_text = value_text[value_end + 2..].trim_start();
+        }
+
+        tokens.push(XmlToken::Start {
+            name,
+            attributes,
+            empty,
+        });
+    }
+
+    Ok(tokens)
+}
+
+/// Replaces the predefined XML entities.
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Reads the tilesets and tile layers of a TMX document.
+fn read_tmx(text: &str) -> Result<RawMap, TiledError> {
+    let mut raw = RawMap::default();
+    let mut open: Vec<String> = Vec::new();
+    let mut first_gid = 0;
+    let mut tile: Option<(u32, TileProperties)> = None;
+    let mut layer: Option<RawLayer> = None;
+    let mut csv = false;
+    let mut has_map = false;
+
+    for token in tokenize_xml(text)? {
+        match token {
+            XmlToken::Start {
+                name,
+                attributes,
+                empty,
+            } => {
+                let attribute = |key: &str| attributes.get(key).map(String::as_str);
+                match name.as_str() {
+                    "map" => {
+                        if attribute("infinite") == Some("1") {
+                            return Err(TiledError::Unsupported("infinite maps".to_string()));
+                        }
+                        raw.width = parse_number(attribute("width"), "map width")?;
+                        raw.height = parse_number(attribute("height"), "map height")?;
+                        has_map = true;
+                    }
+                    "tileset" => {
+                        if let Some(source) = attribute("source") {
+                            return Err(TiledError::Unsupported(format!(
+                                "external tileset {:?}",
+                                source
+                            )));
+                        }
+                        first_gid = parse_number(attribute("firstgid"), "tileset firstgid")?;
+                    }
+                    "tile" if open.last().is_some_and(|parent| parent == "data") => {
+                        if let Some(layer) = layer.as_mut() {
+                            let gid: u32 = parse_number(attribute("gid").or(Some("0")), "gid")?;
+                            layer.gids.push(tile_id(gid));
+                        }
+                    }
+                    "tile" => {
+                        let id: u32 = parse_number(attribute("id"), "tile id")?;
+                        tile = Some((global_id(first_gid, id)?, TileProperties::default()));
+                    }
+                    "property" => {
+                        if let (Some((_, properties)), Some(key)) =
+                            (tile.as_mut(), attribute("name"))
+                        {
+                            properties.set(key, attribute("value").unwrap_or(""))?;
+                        }
+                    }
+                    "layer" => {
+                        layer = Some(RawLayer {
+                            name: attribute("name").unwrap_or("").to_string(),
+                            gids: Vec::new(),
+                        });
+                    }
+                    "data" => match attribute("encoding") {
+                        None => csv = false,
+                        Some("csv") => csv = true,
+                        Some(encoding) => {
+                            return Err(TiledError::Unsupported(format!(
+                                "{} encoded layer data",
+                                encoding
+                            )))
+                        }
+                    },
+                    "chunk" => return Err(TiledError::Unsupported("infinite maps".to_string())),
+                    _ => {}
+                }
+                if !empty {
+                    open.push(name);
+                } else if name == "layer" {
+                    raw.layers.extend(layer.take());
+                } else if name == "tile" {
+                    tile = None;
+                }
+            }
+            XmlToken::End(name) => {
+                if open.pop().as_deref() != Some(name.as_str()) {
+                    return Err(TiledError::InvalidXml(format!("unexpected </{}>", name)));
+                }
+                match name.as_str() {
+                    "tile" => {
+                        if let Some((gid, properties)) = tile.take() {
+                            raw.tiles.insert(gid, properties);
+                        }
+                    }
+                    "layer" => raw.layers.extend(layer.take()),
+                    _ => {}
+                }
+            }
+            XmlToken::Text(text) => {
+                if let (Some(layer), true) = (layer.as_mut(), csv) {
+                    for value in text.split(',').filter(|value| !value.trim().is_empty()) {
+                        let gid: u32 = parse_number(Some(value), "layer data")?;
+                        layer.gids.push(tile_id(gid));
+                    }
+                }
+            }
+        }
+    }
+
+    if !has_map {
+        return Err(TiledError::InvalidXml("missing <map> element".to_string()));
+    }
+    if let Some(name) = open.pop() {
+        return Err(TiledError::InvalidXml(format!("unclosed <{}>", name)));
+    }
+    Ok(raw)
+}
+
+/// Reads the tilesets and tile layers of a Tiled JSON document.
+#[cfg(feature = "serde")]
+fn read_json(map: &serde_json::Value) -> Result<RawMap, TiledError> {
+    use serde_json::Value;
+
+    let number = |value: &Value, key: &str| -> Result<usize, TiledError> {
+        value[key]
+            .as_u64()
+            .map(|number| number as usize)
+            .ok_or_else(|| TiledError::InvalidMap(format!("missing or invalid {}", key)))
+    };
+    let id_number = |value: &Value, key: &str| -> Result<u32, TiledError> {
+        u32::try_from(number(value, key)?)
+            .map_err(|_| TiledError::InvalidMap(format!("missing or invalid {}", key)))
+    };
+
+    if map["infinite"].as_bool() == Some(true) {
+        return Err(TiledError::Unsupported("infinite maps".to_string()));
+    }
+    let mut raw = RawMap {
+        width: number(map, "width")?,
+        height: number(map, "height")?,
+        ..RawMap::default()
+    };
+
+    for tileset in map["tilesets"].as_array().into_iter().flatten() {
+        if let Some(source) = tileset["source"].as_str() {
+            return Err(TiledError::Unsupported(format!(
+                "external tileset {:?}",
+                source
+            )));
+        }
+        let first_gid = id_number(tileset, "firstgid")?;
+
+        for tile in tileset["tiles"].as_array().into_iter().flatten() {
+            let mut properties = TileProperties::default();
+            for property in tile["properties"].as_array().into_iter().flatten() {
+                let Some(name) = property["name"].as_str() else {
+                    continue;
+                };
+                let value = match &property["value"] {
+                    Value::String(text) => text.clone(),
+                    value => value.to_string(),
+                };
+                properties.set(name, &value)?;
+            }
+            raw.tiles
+                .insert(global_id(first_gid, id_number(tile, "id")?)?, properties);
+        }
+    }
+
+    // Group layers are flattened, keeping the names of the tile layers inside them
+    let mut pending: Vec<&Value> = map["layers"].as_array().into_iter().flatten().collect();
+    pending.reverse();
+    while let Some(layer) = pending.pop() {
+        match layer["type"].as_str() {
+            Some("group") => {
+                let children = layer["layers"].as_array().into_iter().flatten();
+                pending.extend(children.rev());
+            }
+            Some("tilelayer") => {
+                if layer["encoding"]
+                    .as_str()
+                    .is_some_and(|encoding| encoding != "csv")
+                {
+                    return Err(TiledError::Unsupported(
+                        "base64 encoded layer data".to_string(),
+                    ));
+                }
+                let gids = layer["data"]
+                    .as_array()
+                    .ok_or_else(|| TiledError::InvalidMap("missing layer data".to_string()))?
+                    .iter()
+                    .map(|gid| {
+                        gid.as_u64()
+                            .map(|gid| tile_id(gid as u32))
+                            .ok_or_else(|| TiledError::InvalidMap("invalid layer data".to_string()))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                raw.layers.push(RawLayer {
+                    name: layer["name"].as_str().unwrap_or("").to_string(),
+                    gids,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    Ok(raw)
+}
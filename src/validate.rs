@@ -0,0 +1,307 @@
+use crate::node::{Direction, Node};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// How a node map lays out its `Direction`s, used to check neighbor offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DirectionConvention {
+    /// The layout of `Node::new` and `Node::grid_to_nodes`: for a node keyed `(a, b)`,
+    /// `Direction::North` is `(a, b - 1)` and `Direction::East` is `(a + 1, b)`.
+    #[default]
+    Node,
+    /// The layout of a map keyed `(row, col)` as drawn on screen, like `TiledMap`:
+    /// `Direction::North` is the row above and `Direction::East` the next column.
+    Visual,
+    /// Offsets are not checked, for maps like hexagonal grids whose neighbors
+    /// depend on the row or column.
+    Unchecked,
+}
+
+impl DirectionConvention {
+    /// Returns the key offset of a direction, or `None` if offsets are not checked.
+    fn offset(self, direction: Direction) -> Option<(isize, isize)> {
+        let (dx, dy) = match direction {
+            Direction::North => (0, -1),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+            Direction::East => (1, 0),
+            Direction::NorthEast => (1, -1),
+            Direction::NorthWest => (-1, -1),
+            Direction::SouthEast => (1, 1),
+            Direction::SouthWest => (-1, 1),
+        };
+        match self {
+            DirectionConvention::Node => Some((dx, dy)),
+            DirectionConvention::Visual => Some((dy, dx)),
+            DirectionConvention::Unchecked => None,
+        }
+    }
+}
+
+/// Options for `validate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationOptions {
+    /// The layout used to check that neighbors sit at the offset of their direction.
+    pub directions: DirectionConvention,
+    /// Whether neighbors that do not link back are reported. Turn this off for maps
+    /// with intended one-way cells.
+    pub asymmetric_edges: bool,
+    /// Whether passable nodes linking to blocked nodes are reported. Off by default,
+    /// since `Node::grid_to_nodes` links every cell to its blocked neighbors.
+    pub blocked_neighbors: bool,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions {
+            directions: DirectionConvention::default(),
+            asymmetric_edges: true,
+            blocked_neighbors: false,
+        }
+    }
+}
+
+/// A problem found in a node map by `validate`.
+///
+/// Positions are keys of the node map, as used by `Node::set_neighbor`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// The `x` and `y` fields of a node do not match its key.
+    KeyMismatch {
+        key: (usize, usize),
+        x: usize,
+        y: usize,
+    },
+    /// A neighbor is not in the map.
+    DanglingNeighbor {
+        position: (usize, usize),
+        direction: Direction,
+        target: (usize, usize),
+    },
+    /// A custom edge leads to a position that is not in the map.
+    DanglingCustomEdge {
+        position: (usize, usize),
+        label: String,
+        target: (usize, usize),
+    },
+    /// A passable node has a blocked neighbor.
+    BlockedNeighbor {
+        position: (usize, usize),
+        direction: Direction,
+        target: (usize, usize),
+    },
+    /// A neighbor is not at the offset of its direction.
+    DirectionMismatch {
+        position: (usize, usize),
+        direction: Direction,
+        target: (usize, usize),
+        /// The position the direction points to, or `None` if it is outside the key range.
+        expected: Option<(usize, usize)>,
+    },
+    /// A neighbor has no neighbor in any direction leading back.
+    AsymmetricEdge {
+        position: (usize, usize),
+        direction: Direction,
+        target: (usize, usize),
+    },
+    /// A passable node with no edge to or from another passable node.
+    IsolatedNode((usize, usize)),
+}
+
+impl fmt::Display for ValidationIssue {
+    /// Formats the issue for display in user-facing contexts.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationIssue::KeyMismatch { key, x, y } => write!(
+                f,
+                "The node at key {:?} has coordinates ({}, {})",
+                key, x, y
+            ),
+            ValidationIssue::DanglingNeighbor {
+                position,
+                direction,
+                target,
+            } => write!(
+                f,
+                "The {:?} neighbor of {:?} is {:?}, which is not in the map",
+                direction, position, target
+            ),
+            ValidationIssue::DanglingCustomEdge {
+                position,
+                label,
+                target,
+            } => write!(
+                f,
+                "The custom edge '{}' of {:?} leads to {:?}, which is not in the map",
+                label, position, target
+            ),
+            ValidationIssue::BlockedNeighbor {
+                position,
+                direction,
+                target,
+            } => write!(
+                f,
+                "The {:?} neighbor of {:?} is {:?}, which is blocked",
+                direction, position, target
+            ),
+            ValidationIssue::DirectionMismatch {
+                position,
+                direction,
+                target,
+                expected,
+            } => match expected {
+                Some(expected) => write!(
+                    f,
+                    "The {:?} neighbor of {:?} is {:?}, expected {:?}",
+                    direction, position, target, expected
+                ),
+                None => write!(
+                    f,
+                    "The {:?} neighbor of {:?} is {:?}, but there is no position in that direction",
+                    direction, position, target
+                ),
+            },
+            ValidationIssue::AsymmetricEdge {
+                position,
+                direction,
+                target,
+            } => write!(
+                f,
+                "The {:?} neighbor of {:?} is {:?}, which does not link back",
+                direction, position, target
+            ),
+            ValidationIssue::IsolatedNode(position) => {
+                write!(f, "The node at {:?} cannot be entered or left", position)
+            }
+        }
+    }
+}
+
+/// Checks a node map for mistakes made when editing neighbors by hand.
+///
+/// Nodes are checked in key order, so the issues come out in the same order
+/// for the same map.
+///
+/// # Parameters
+/// - `nodes`: The node map to check.
+/// - `options`: Which checks to run and how directions are laid out.
+///
+/// # Returns
+/// The issues found, empty if the map is consistent.
+///
+/// # Example
+/// ```rust
+/// use controlled_astar::{validate, Direction, Node, ValidationIssue, ValidationOptions};
+///
+/// let mut nodes = Node::grid_to_nodes(&vec![vec![0; 3]; 3]);
+/// nodes.get_mut(&(0, 0)).unwrap().set_neighbor(Direction::SouthEast, Some((1, 1)));
+///
+/// let issues = validate(&nodes, &ValidationOptions::default());
+/// assert_eq!(
+///     issues,
+///     vec![ValidationIssue::AsymmetricEdge {
+///         position: (0, 0),
+///         direction: Direction::SouthEast,
+///         target: (1, 1),
+///     }]
+/// );
+/// ```
+pub fn validate(
+    nodes: &HashMap<(usize, usize), Node>,
+    options: &ValidationOptions,
+) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let mut keys: Vec<_> = nodes.keys().copied().collect();
+    keys.sort();
+
+    // Passable nodes with an edge to or from another passable node
+    let mut connected = HashSet::new();
+
+    for &key in &keys {
+        let node = &nodes[&key];
+        if (node.x, node.y) != key {
+            issues.push(ValidationIssue::KeyMismatch {
+                key,
+                x: node.x,
+                y: node.y,
+            });
+        }
+
+        for (&direction, &target) in &node.neighbors {
+            let Some(target) = target else {
+                continue;
+            };
+            let Some(target_node) = nodes.get(&target) else {
+                issues.push(ValidationIssue::DanglingNeighbor {
+                    position: key,
+                    direction,
+                    target,
+                });
+                continue;
+            };
+
+            if let Some((dx, dy)) = options.directions.offset(direction) {
+                let expected = key
+                    .0
+                    .checked_add_signed(dx)
+                    .zip(key.1.checked_add_signed(dy));
+                if expected != Some(target) {
+                    issues.push(ValidationIssue::DirectionMismatch {
+                        position: key,
+                        direction,
+                        target,
+                        expected,
+                    });
+                }
+            }
+
+            if options.asymmetric_edges
+                && !target_node
+                    .neighbors
+                    .values()
+                    .any(|&back| back == Some(key))
+            {
+                issues.push(ValidationIssue::AsymmetricEdge {
+                    position: key,
+                    direction,
+                    target,
+                });
+            }
+
+            if !node.is_blocked && target_node.is_blocked && options.blocked_neighbors {
+                issues.push(ValidationIssue::BlockedNeighbor {
+                    position: key,
+                    direction,
+                    target,
+                });
+            }
+        }
+
+        for edge in &node.custom_edges {
+            if !nodes.contains_key(&edge.target) {
+                issues.push(ValidationIssue::DanglingCustomEdge {
+                    position: key,
+                    label: edge.label.clone(),
+                    target: edge.target,
+                });
+            }
+        }
+
+        if !node.is_blocked {
+            for (target, _) in node.edges(nodes) {
+                if target != key && !nodes[&target].is_blocked {
+                    connected.insert(key);
+                    connected.insert(target);
+                }
+            }
+        }
+    }
+
+    for &key in &keys {
+        if !nodes[&key].is_blocked && !connected.contains(&key) {
+            issues.push(ValidationIssue::IsolatedNode(key));
+        }
+    }
+
+    issues
+}
@@ -0,0 +1,183 @@
+#[cfg(test)]
+mod tests {
+    use controlled_astar::{AStar, AStarError, AlternativeOptions, Node};
+    use std::collections::HashSet;
+
+    /// Collects the costs of all loopless paths between two cells of a grid with unit costs.
+    fn all_path_costs(
+        grid: &[Vec<i32>],
+        position: (usize, usize),
+        goal: (usize, usize),
+        visited: &mut HashSet<(usize, usize)>,
+        costs: &mut Vec<usize>,
+    ) {
+        if position == goal {
+            costs.push(visited.len() - 1);
+            return;
+        }
+        let (x, y) = position;
+        let steps = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+        for next in steps {
+            let free = grid
+                .get(next.1)
+                .and_then(|row| row.get(next.0))
+                .is_some_and(|&cell| cell == 0);
+            if free && visited.insert(next) {
+                all_path_costs(grid, next, goal, visited, costs);
+                visited.remove(&next);
+            }
+        }
+    }
+
+    #[test]
+    fn test_k_shortest_paths_match_enumeration() {
+        let grid = vec![vec![0, 0, 0, 0], vec![0, 1, 0, 0], vec![0, 0, 0, 0]];
+        let mut expected = Vec::new();
+        all_path_costs(
+            &grid,
+            (0, 0),
+            (3, 2),
+            &mut HashSet::from([(0, 0)]),
+            &mut expected,
+        );
+        expected.sort_unstable();
+
+        let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+        let paths = astar
+            .find_k_shortest_paths((0, 0), (3, 2), expected.len() + 5)
+            .unwrap();
+        let costs: Vec<usize> = paths.iter().map(|alternative| alternative.cost).collect();
+        assert_eq!(costs, expected);
+
+        let mut seen = HashSet::new();
+        for alternative in &paths {
+            let positions = alternative.path.positions();
+            assert_eq!(alternative.path.total_cost(), alternative.cost);
+            assert_eq!((positions[0], alternative.path.goal()), ((0, 0), (3, 2)));
+            // Loopless and all different
+            let unique: HashSet<_> = positions.iter().collect();
+            assert_eq!(unique.len(), positions.len());
+            assert!(seen.insert(positions.to_vec()));
+        }
+    }
+
+    #[test]
+    fn test_k_shortest_paths_overlap() {
+        let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 4]; 4]));
+        let paths = astar.find_k_shortest_paths((0, 0), (3, 3), 4).unwrap();
+
+        assert_eq!(paths.len(), 4);
+        assert_eq!(paths[0].overlap, 1.0);
+        for alternative in &paths[1..] {
+            assert_eq!(alternative.cost, 6);
+            assert!(alternative.overlap < 1.0);
+            assert!(alternative.overlap >= 0.0);
+        }
+
+        // The search is left without restrictions
+        let path = astar.find_shortest_path((0, 0), (3, 3)).unwrap();
+        assert_eq!(path, paths[0].path);
+    }
+
+    #[test]
+    fn test_k_shortest_paths_errors() {
+        let grid = vec![vec![0, 1, 0], vec![0, 1, 0]];
+        let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+
+        assert!(astar
+            .find_k_shortest_paths((0, 0), (2, 0), 0)
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            astar.find_k_shortest_paths((0, 0), (2, 0), 3),
+            Err(AStarError::PathNotFound(_))
+        ));
+
+        // A corridor has a single loopless path
+        let paths = astar.find_k_shortest_paths((0, 0), (0, 1), 3).unwrap();
+        assert_eq!(paths.len(), 1);
+    }
+
+    #[test]
+    fn test_alternative_paths() {
+        // Three corridors between the left and right columns
+        let grid = vec![
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 1, 1, 1, 1, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 1, 1, 1, 1, 0],
+            vec![0, 0, 0, 0, 0, 0],
+        ];
+        let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+        let options = AlternativeOptions {
+            max_cost_ratio: 2.0,
+            ..AlternativeOptions::default()
+        };
+        let paths = astar
+            .find_alternative_paths((0, 2), (5, 2), &options)
+            .unwrap();
+
+        assert_eq!(paths.len(), 3);
+        assert_eq!(paths[0].cost, 5);
+        assert_eq!(paths[0].overlap, 1.0);
+        let corridors: HashSet<usize> = paths
+            .iter()
+            .map(|alternative| alternative.path.positions()[3].1)
+            .collect();
+        assert_eq!(corridors, HashSet::from([0, 2, 4]));
+        for alternative in &paths[1..] {
+            assert_eq!(alternative.cost, 9);
+            assert_eq!(alternative.overlap, 0.0);
+        }
+
+        // The detours cost 1.8 times as much as the best path
+        let options = AlternativeOptions {
+            max_cost_ratio: 1.5,
+            ..AlternativeOptions::default()
+        };
+        let paths = astar
+            .find_alternative_paths((0, 2), (5, 2), &options)
+            .unwrap();
+        assert_eq!(paths.len(), 1);
+
+        let options = AlternativeOptions {
+            count: 2,
+            max_cost_ratio: 2.0,
+            ..AlternativeOptions::default()
+        };
+        let paths = astar
+            .find_alternative_paths((0, 2), (5, 2), &options)
+            .unwrap();
+        assert_eq!(paths.len(), 2);
+    }
+
+    #[test]
+    fn test_alternative_paths_overlap_limit() {
+        let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 6]; 6]));
+        let options = AlternativeOptions {
+            count: 4,
+            max_overlap: 0.5,
+            max_cost_ratio: 2.0,
+            max_searches: 20,
+            ..AlternativeOptions::default()
+        };
+        let paths = astar
+            .find_alternative_paths((0, 0), (5, 5), &options)
+            .unwrap();
+
+        assert!(paths.len() > 1);
+        assert!(paths.iter().all(|alternative| alternative.cost <= 20));
+        for alternative in &paths[1..] {
+            assert!(alternative.overlap <= 0.5);
+        }
+        assert!(matches!(
+            astar.find_alternative_paths((0, 0), (9, 9), &options),
+            Err(AStarError::NodeNotFound((9, 9)))
+        ));
+    }
+}
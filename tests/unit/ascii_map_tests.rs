@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod tests {
+    use controlled_astar::{AStar, AsciiMap, AsciiMapErrorKind, Legend, Tile};
+
+    #[test]
+    fn test_parse_default_legend() {
+        let map: AsciiMap = "S.#\n.5.\n#.G\n".parse().unwrap();
+
+        // Check the size and the markers
+        assert_eq!(map.width, 3);
+        assert_eq!(map.height, 3);
+        assert_eq!(map.start, Some((0, 0)));
+        assert_eq!(map.goal, Some((2, 2)));
+
+        // Nodes are keyed by (row, column)
+        assert!(map.nodes[&(0, 2)].is_blocked);
+        assert!(map.nodes[&(2, 0)].is_blocked);
+        assert_eq!(map.nodes[&(1, 1)].cost, 5);
+        assert_eq!(map.nodes[&(0, 1)].cost, 1);
+        assert!(!map.nodes[&(0, 0)].is_blocked); // The start cell is free
+    }
+
+    #[test]
+    fn test_parse_custom_legend() {
+        let mut legend = Legend::default();
+        legend.set('~', Tile::Cost(4));
+        legend.set('T', Tile::Blocked);
+        legend.set('@', Tile::Start);
+        legend.remove('S');
+
+        let map = AsciiMap::parse("@~T\n...", &legend).unwrap();
+        assert_eq!(map.start, Some((0, 0)));
+        assert_eq!(map.nodes[&(0, 1)].cost, 4);
+        assert!(map.nodes[&(0, 2)].is_blocked);
+
+        // 'S' is no longer part of the legend
+        let error = AsciiMap::parse("S..", &legend).unwrap_err();
+        assert_eq!(error.kind, AsciiMapErrorKind::UnknownCharacter('S'));
+    }
+
+    #[test]
+    fn test_parse_errors_report_position() {
+        // Unknown character on the second line
+        let error = AsciiMap::parse("...\n.?.", &Legend::default()).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.kind, AsciiMapErrorKind::UnknownCharacter('?'));
+
+        // The default legend has no digit for a cost of 0
+        let error = AsciiMap::parse("10", &Legend::default()).unwrap_err();
+        assert_eq!(error.kind, AsciiMapErrorKind::UnknownCharacter('0'));
+
+        // Line shorter than the first one
+        let error = AsciiMap::parse("...\n..\n...", &Legend::default()).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(
+            error.kind,
+            AsciiMapErrorKind::RaggedLine {
+                expected: 3,
+                found: 2
+            }
+        );
+
+        // Second start marker
+        let error = AsciiMap::parse("S..\n..S", &Legend::default()).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.kind, AsciiMapErrorKind::DuplicateStart);
+
+        // Empty input
+        let error = AsciiMap::parse("\n\n", &Legend::default()).unwrap_err();
+        assert_eq!(error.kind, AsciiMapErrorKind::EmptyMap);
+    }
+
+    #[test]
+    fn test_parse_windows_line_endings() {
+        let map: AsciiMap = "S.\r\n.G\r\n".parse().unwrap();
+        assert_eq!(map.width, 2);
+        assert_eq!(map.goal, Some((1, 1)));
+    }
+
+    #[test]
+    fn test_write_round_trip() {
+        let legend = Legend::default();
+        let text = "S..#\n.37#\n...G\n";
+        let map = AsciiMap::parse(text, &legend).unwrap();
+        assert_eq!(map.write(&legend).unwrap(), text);
+    }
+
+    #[test]
+    fn test_write_unmapped_cost() {
+        let legend = Legend::default();
+        let mut map = AsciiMap::parse("...\n...", &legend).unwrap();
+        map.nodes.get_mut(&(1, 2)).unwrap().set_cost(12);
+
+        // There is no character for a cost of 12
+        let error = map.write(&legend).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.kind, AsciiMapErrorKind::UnmappedTile(Tile::Cost(12)));
+    }
+
+    #[test]
+    fn test_astar_avoids_expensive_cells() {
+        // Walking through the 9s is shorter but more expensive than going around
+        let map: AsciiMap = "S9G\n.9.\n...".parse().unwrap();
+        let mut astar = AStar::new(map.nodes);
+        let path = astar
+            .find_shortest_path(map.start.unwrap(), map.goal.unwrap())
+            .unwrap();
+
+        assert_eq!(
+            path.positions(),
+            [(0, 0), (0, 1), (0, 2), (1, 2), (2, 2), (2, 1), (2, 0)]
+        );
+    }
+
+    #[test]
+    fn test_astar_on_non_square_map() {
+        // A single row: the goal's column is outside the range of row indices
+        let map: AsciiMap = "S...G".parse().unwrap();
+        let mut astar = AStar::new(map.nodes);
+        let path = astar
+            .find_shortest_path(map.start.unwrap(), map.goal.unwrap())
+            .unwrap();
+
+        assert_eq!(path.positions(), [(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)]);
+    }
+}
@@ -1,7 +1,11 @@
 #[cfg(test)]
 mod tests {
-    use controlled_astar::{AStar, AStarError, Node};
+    use controlled_astar::{
+        AStar, AStarError, AnytimeOptions, ComponentIndex, Direction, Heuristic, IdaOptions, Node,
+        UnreachableReason,
+    };
     use std::collections::HashMap;
+    use std::time::Duration;
 
     // Helper function to create a simple 3x3 grid with no obstacles
     fn create_test_grid() -> HashMap<(usize, usize), Node> {
@@ -30,7 +34,7 @@ mod tests {
             Ok(path) => {
                 let expected_path = vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)];
                 // Assert that the path is as expected
-                assert_eq!(path, Some(expected_path));
+                assert_eq!(path.positions(), expected_path);
             }
             Err(AStarError::PathNotFound(_)) => {
                 panic!("Expected a path, but got PathNotFound error.");
@@ -54,7 +58,7 @@ mod tests {
             Ok(path) => {
                 let expected_path = vec![(1, 1)];
                 // Assert that the path consists of the single node
-                assert_eq!(path, Some(expected_path));
+                assert_eq!(path.positions(), expected_path);
             }
             Err(AStarError::PathNotFound(_)) => {
                 panic!("Expected a path, but got PathNotFound error.");
@@ -80,7 +84,7 @@ mod tests {
             Ok(path) => {
                 let expected_path = vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)];
                 // Assert that the path avoids the obstacle at (1, 1)
-                assert_eq!(path, Some(expected_path));
+                assert_eq!(path.positions(), expected_path);
             }
             Err(AStarError::PathNotFound(_)) => {
                 panic!("Expected a path, but got PathNotFound error.");
@@ -106,8 +110,8 @@ mod tests {
 
         match result {
             Ok(path) => {
-                // Assert that no path is found due to blocked paths
-                assert_eq!(path, None);
+                // Every route is blocked, so a path must not be found
+                panic!("Expected no path, but got {:?}", path.positions());
             }
             Err(AStarError::PathNotFound(_)) => {
                 // Test passes because PathNotFound error is expected
@@ -131,8 +135,8 @@ mod tests {
 
         match result {
             Ok(path) => {
-                // Assert that no path is found because the start node is blocked
-                assert_eq!(path, None);
+                // The start node is blocked, so a path must not be found
+                panic!("Expected no path, but got {:?}", path.positions());
             }
             Err(AStarError::StartNodeBlocked(_)) => {
                 // Test passes because StartNodeBlocked error is expected
@@ -156,8 +160,8 @@ mod tests {
 
         match result {
             Ok(path) => {
-                // Assert that no path is found because the goal node is blocked
-                assert_eq!(path, None);
+                // The goal node is blocked, so a path must not be found
+                panic!("Expected no path, but got {:?}", path.positions());
             }
             Err(AStarError::GoalNodeBlocked(_)) => {
                 // Test passes because GoalNodeBlocked error is expected
@@ -184,7 +188,7 @@ mod tests {
             Ok(path) => {
                 let expected_path = vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)];
                 // Assert that the path avoids the obstacles
-                assert_eq!(path, Some(expected_path));
+                assert_eq!(path.positions(), expected_path);
             }
             Err(AStarError::PathNotFound(_)) => {
                 panic!("Expected a path, but got PathNotFound error.");
@@ -206,8 +210,8 @@ mod tests {
 
         match result {
             Ok(path) => {
-                // Assert that the path is None because the start node is out of bounds
-                assert_eq!(path, None);
+                // The start node is out of bounds, so a path must not be found
+                panic!("Expected no path, but got {:?}", path.positions());
             }
             Err(AStarError::NodeNotFound(_)) => {
                 // Test passes because NodeNotFound error is expected
@@ -229,8 +233,8 @@ mod tests {
 
         match result {
             Ok(path) => {
-                // Assert that the path is None because the goal node is out of bounds
-                assert_eq!(path, None);
+                // The goal node is out of bounds, so a path must not be found
+                panic!("Expected no path, but got {:?}", path.positions());
             }
             Err(AStarError::NodeNotFound(_)) => {
                 // Test passes because NodeNotFound error is expected
@@ -240,4 +244,699 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_astar_follows_custom_edge() {
+        // Create an open 5x5 grid with a teleporter from one corner to the other
+        let mut nodes = Node::grid_to_nodes(&vec![vec![0; 5]; 5]);
+        nodes
+            .get_mut(&(0, 0))
+            .unwrap()
+            .add_custom_edge((4, 4), 1, "teleporter");
+
+        let mut astar = AStar::new(nodes);
+        let result = astar.find_shortest_path((0, 0), (4, 4));
+
+        // The teleporter takes the path straight to the goal
+        assert_eq!(result.unwrap().positions(), [(0, 0), (4, 4)]);
+    }
+
+    #[test]
+    fn test_astar_custom_edge_cost_is_respected() {
+        // An expensive jump pad should not be preferred over walking
+        let mut nodes = Node::grid_to_nodes(&vec![vec![0; 5]; 5]);
+        nodes
+            .get_mut(&(0, 0))
+            .unwrap()
+            .add_custom_edge((0, 2), 10, "jump pad");
+
+        let mut astar = AStar::new(nodes);
+        let path = astar.find_shortest_path((0, 0), (2, 0)).unwrap();
+
+        assert_eq!(path.positions(), [(0, 0), (1, 0), (2, 0)]);
+    }
+
+    #[test]
+    fn test_astar_teleporter_away_from_direct_route() {
+        // The teleporter starts two steps away from the start, in the opposite direction
+        // of the goal, and still yields a cheaper path than walking straight there
+        let mut nodes = Node::grid_to_nodes(&vec![vec![0; 5]; 5]);
+        nodes
+            .get_mut(&(0, 0))
+            .unwrap()
+            .add_custom_edge((2, 4), 1, "teleporter");
+
+        let mut astar = AStar::new(nodes);
+        let path = astar.find_shortest_path((0, 2), (4, 2)).unwrap();
+
+        // Two steps to the teleporter plus the teleporter itself
+        assert_eq!(path.positions(), [(0, 2), (0, 1), (0, 0), (4, 2)]);
+    }
+
+    #[test]
+    fn test_astar_diagonal_neighbors_keep_heuristic_useful() {
+        // Cost-1 diagonals added by hand are cheaper than the Manhattan distance assumes
+        let size = 16;
+        let mut nodes = Node::grid_to_nodes(&vec![vec![0; size]; size]);
+        for (&(x, y), node) in nodes.iter_mut() {
+            let diagonals = [
+                (
+                    Direction::NorthEast,
+                    x + 1 < size && y > 0,
+                    (x + 1, y.wrapping_sub(1)),
+                ),
+                (
+                    Direction::NorthWest,
+                    x > 0 && y > 0,
+                    (x.wrapping_sub(1), y.wrapping_sub(1)),
+                ),
+                (
+                    Direction::SouthEast,
+                    x + 1 < size && y + 1 < size,
+                    (x + 1, y + 1),
+                ),
+                (
+                    Direction::SouthWest,
+                    x > 0 && y + 1 < size,
+                    (x.wrapping_sub(1), y + 1),
+                ),
+            ];
+            for (direction, exists, neighbor) in diagonals {
+                if exists {
+                    node.set_neighbor(direction, Some(neighbor));
+                }
+            }
+        }
+        let mut dijkstra = AStar::new(nodes.clone());
+        dijkstra.set_heuristic(Heuristic::Zero);
+        let mut astar = AStar::new(nodes);
+
+        let expected = dijkstra.find_shortest_path((0, 0), (15, 9)).unwrap();
+        let path = astar.find_shortest_path((0, 0), (15, 9)).unwrap();
+        assert_eq!(path.total_cost(), expected.total_cost());
+        assert_eq!(path.total_cost(), 15);
+        assert!(astar.closed_positions().len() * 4 < dijkstra.closed_positions().len());
+    }
+
+    #[test]
+    fn test_astar_blocked_custom_edge_target_is_skipped() {
+        // A teleporter leading next to the goal, but its target is blocked
+        let mut nodes = Node::grid_to_nodes(&vec![vec![0; 5]; 5]);
+        nodes
+            .get_mut(&(0, 0))
+            .unwrap()
+            .add_custom_edge((4, 4), 1, "teleporter");
+        nodes.get_mut(&(4, 4)).unwrap().set_blocked(true);
+
+        let mut astar = AStar::new(nodes);
+        let path = astar.find_shortest_path((0, 0), (3, 4)).unwrap();
+
+        // The path has to walk all the way
+        assert_eq!(path.len(), 7);
+        assert!(!path.contains((4, 4)));
+    }
+
+    #[test]
+    fn test_astar_error_composes_with_box_dyn_error() {
+        fn search() -> Result<Vec<(usize, usize)>, Box<dyn std::error::Error>> {
+            let mut nodes = Node::grid_to_nodes(&[vec![0, 0], vec![0, 0]]);
+            nodes.get_mut(&(1, 1)).unwrap().set_blocked(true);
+            let mut astar = AStar::new(nodes);
+            Ok(astar.find_shortest_path((0, 0), (1, 1))?.into_positions())
+        }
+
+        let error = search().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The goal node at position (1, 1) is blocked!"
+        );
+        assert!(format!("{:?}", error).contains("GoalNodeBlocked"));
+    }
+
+    #[test]
+    fn test_astar_invalid_neighbor() {
+        let mut nodes = Node::grid_to_nodes(&[vec![0, 0, 0], vec![0, 0, 0]]);
+        nodes
+            .get_mut(&(0, 1))
+            .unwrap()
+            .set_neighbor(Direction::SouthEast, Some((7, 7)));
+
+        let mut astar = AStar::new(nodes);
+        match astar.find_shortest_path((0, 0), (2, 1)) {
+            Err(AStarError::InvalidNeighbor { node, neighbor }) => {
+                assert_eq!(node, (1, 0));
+                assert_eq!(neighbor, (7, 7));
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_astar_invalid_configuration() {
+        let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 3]; 3]));
+        astar.set_heuristic(Heuristic::Octile {
+            straight: 2,
+            diagonal: 5,
+        });
+        assert!(matches!(
+            astar.find_shortest_path((0, 0), (2, 2)),
+            Err(AStarError::InvalidConfiguration(_))
+        ));
+
+        astar.set_heuristic(Heuristic::Octile {
+            straight: 2,
+            diagonal: 3,
+        });
+        assert!(astar.find_shortest_path((0, 0), (2, 2)).is_ok());
+    }
+
+    #[test]
+    fn test_astar_search_limit() {
+        let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 10]; 10]));
+        astar.set_max_expansions(Some(3));
+        assert!(matches!(
+            astar.find_shortest_path((0, 0), (9, 9)),
+            Err(AStarError::SearchLimitExceeded { limit: 3, .. })
+        ));
+        // Exactly the allowed number of nodes was expanded
+        assert_eq!(astar.closed_positions().len(), 3);
+
+        // A goal reached within the limit is still found
+        let path = astar.find_shortest_path((0, 0), (2, 0)).unwrap();
+        assert_eq!(path.len(), 2);
+
+        astar.set_max_expansions(None);
+        assert!(astar.find_shortest_path((0, 0), (9, 9)).is_ok());
+    }
+
+    #[test]
+    fn test_astar_unreachable_disconnected_components() {
+        let grid = vec![vec![0, 0, 1, 0], vec![0, 0, 1, 0], vec![0, 0, 1, 0]];
+        let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+
+        match astar.find_shortest_path((0, 1), (3, 1)) {
+            Err(AStarError::PathNotFound(unreachable)) => {
+                assert_eq!(
+                    unreachable.reason,
+                    UnreachableReason::DisconnectedComponents
+                );
+                assert_eq!(unreachable.closest, (1, 1));
+                assert_eq!(unreachable.explored, 6);
+                assert_eq!(unreachable.level, None);
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_astar_unreachable_one_way_edges() {
+        // The only link between the halves leads from the goal side to the start side
+        let grid = vec![vec![0, 0, 0], vec![1, 0, 1], vec![0, 0, 0]];
+        let mut nodes = Node::grid_to_nodes(&grid);
+        nodes
+            .get_mut(&(1, 1))
+            .unwrap()
+            .remove_neighbor(Direction::East);
+        let components = ComponentIndex::new(&nodes);
+
+        let mut astar = AStar::new(nodes);
+        match astar.find_shortest_path((0, 0), (1, 2)) {
+            Err(AStarError::PathNotFound(unreachable)) => {
+                assert_eq!(unreachable.reason, UnreachableReason::OneWayEdges);
+                assert_eq!(unreachable.closest, (1, 1));
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+
+        // IDA* takes the reason from the component index
+        let options = IdaOptions {
+            components: Some(&components),
+            ..IdaOptions::default()
+        };
+        match astar.find_path_ida((0, 0), (1, 2), &options) {
+            Err(AStarError::PathNotFound(unreachable)) => {
+                assert_eq!(unreachable.reason, UnreachableReason::OneWayEdges);
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_astar_best_effort_path() {
+        let grid = vec![vec![0, 0, 1, 0], vec![0, 0, 1, 0], vec![0, 0, 1, 0]];
+        let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+
+        let path = astar.find_best_effort_path((0, 0), (3, 2)).unwrap();
+        assert_eq!(path.start(), (0, 0));
+        assert_eq!(path.goal(), (1, 2));
+        assert_eq!(path.len(), 3);
+
+        // Reachable goals give the shortest path
+        let path = astar.find_best_effort_path((0, 0), (1, 2)).unwrap();
+        assert_eq!(path.len(), 3);
+
+        // Invalid positions are still errors
+        assert!(matches!(
+            astar.find_best_effort_path((0, 0), (2, 0)),
+            Err(AStarError::GoalNodeBlocked(_))
+        ));
+
+        // A search stopped by the limit returns the best position found so far
+        let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 10]; 10]));
+        astar.set_max_expansions(Some(10));
+        let path = astar.find_best_effort_path((0, 0), (9, 9)).unwrap();
+        assert_eq!(path.start(), (0, 0));
+        assert_ne!(path.goal(), (9, 9));
+        assert_eq!(path.len(), 4);
+    }
+
+    #[test]
+    fn test_astar_nearest_goal() {
+        let grid = vec![
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 1, 1, 1, 1, 0],
+            vec![0, 0, 0, 0, 1, 0],
+            vec![1, 1, 1, 0, 1, 0],
+            vec![0, 0, 0, 0, 0, 0],
+        ];
+        let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+        let goals = [(0, 4), (5, 4), (2, 2), (3, 0)];
+
+        // The single search agrees with the cheapest of the separate searches
+        let path = astar.find_nearest_goal((0, 0), &goals).unwrap();
+        let cheapest = goals
+            .iter()
+            .map(|&goal| astar.find_shortest_path((0, 0), goal).unwrap().total_cost())
+            .min()
+            .unwrap();
+        assert_eq!(path.goal(), (3, 0));
+        assert_eq!(path.total_cost(), cheapest);
+
+        // Blocked goals are skipped, unless all of them are blocked
+        let path = astar.find_nearest_goal((0, 4), &[(1, 1), (5, 4)]).unwrap();
+        assert_eq!(path.goal(), (5, 4));
+        assert!(matches!(
+            astar.find_nearest_goal((0, 0), &[(1, 1), (0, 3)]),
+            Err(AStarError::GoalNodeBlocked((1, 1)))
+        ));
+        assert!(matches!(
+            astar.find_nearest_goal((0, 0), &[(1, 1), (9, 9)]),
+            Err(AStarError::NodeNotFound((9, 9)))
+        ));
+        assert!(matches!(
+            astar.find_nearest_goal((0, 0), &[]),
+            Err(AStarError::InvalidConfiguration(_))
+        ));
+    }
+
+    #[test]
+    fn test_astar_nearest_goal_with_teleporter() {
+        // The teleporter makes the far goal the nearest one
+        let mut nodes = Node::grid_to_nodes(&vec![vec![0; 10]; 10]);
+        nodes
+            .get_mut(&(0, 0))
+            .unwrap()
+            .add_custom_edge((9, 8), 1, "teleporter");
+        let mut astar = AStar::new(nodes);
+
+        let path = astar.find_nearest_goal((0, 0), &[(5, 0), (9, 9)]).unwrap();
+        assert_eq!(path.goal(), (9, 9));
+        assert_eq!(path.total_cost(), 2);
+    }
+
+    #[test]
+    fn test_astar_nearest_matching() {
+        let mut nodes = Node::grid_to_nodes(&vec![vec![0; 7]; 7]);
+        nodes.get_mut(&(2, 2)).unwrap().set_cost(10);
+        let mut astar = AStar::new(nodes);
+
+        // Positions on the diagonal, the closest one is expensive to enter
+        let path = astar
+            .find_nearest_matching((2, 3), |(x, y)| x == y)
+            .unwrap();
+        assert_eq!(path.total_cost(), 1);
+        assert_eq!(path.goal(), (3, 3));
+
+        let path = astar.find_nearest_matching((2, 3), |_| true).unwrap();
+        assert!(path.is_empty());
+
+        match astar.find_nearest_matching((0, 0), |(x, _)| x > 10) {
+            Err(AStarError::PathNotFound(unreachable)) => {
+                assert_eq!(
+                    unreachable.reason,
+                    UnreachableReason::DisconnectedComponents
+                );
+                assert_eq!(unreachable.explored, 49);
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_astar_nearest_goal_unreachable() {
+        let grid = vec![vec![0, 0, 1, 0], vec![0, 0, 1, 0], vec![0, 0, 1, 0]];
+        let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+
+        match astar.find_nearest_goal((0, 0), &[(3, 0), (3, 2)]) {
+            Err(AStarError::PathNotFound(unreachable)) => {
+                assert_eq!(
+                    unreachable.reason,
+                    UnreachableReason::DisconnectedComponents
+                );
+                assert_eq!(unreachable.explored, 6);
+                assert_eq!(unreachable.closest.0, 1);
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_astar_nearest_start() {
+        let grid = vec![
+            vec![0, 0, 0, 0, 0],
+            vec![1, 1, 1, 1, 0],
+            vec![0, 0, 0, 0, 0],
+            vec![0, 1, 1, 1, 1],
+            vec![0, 0, 0, 0, 0],
+        ];
+        let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+        let starts = [(0, 0), (4, 4), (0, 2)];
+
+        // The single search agrees with the cheapest of the separate searches
+        let path = astar.find_nearest_start(&starts, (2, 2)).unwrap();
+        let cheapest = starts
+            .iter()
+            .map(|&start| {
+                astar
+                    .find_shortest_path(start, (2, 2))
+                    .unwrap()
+                    .total_cost()
+            })
+            .min()
+            .unwrap();
+        assert_eq!(path.start(), (0, 2));
+        assert_eq!(path.goal(), (2, 2));
+        assert_eq!(path.total_cost(), cheapest);
+
+        // A start on the goal wins without moving
+        let path = astar.find_nearest_start(&[(0, 0), (2, 2)], (2, 2)).unwrap();
+        assert!(path.is_empty());
+
+        // Blocked starts are skipped, unless all of them are blocked
+        let path = astar.find_nearest_start(&[(1, 1), (4, 4)], (2, 2)).unwrap();
+        assert_eq!(path.start(), (4, 4));
+        assert!(matches!(
+            astar.find_nearest_start(&[(1, 1), (2, 3)], (2, 2)),
+            Err(AStarError::StartNodeBlocked((1, 1)))
+        ));
+        assert!(matches!(
+            astar.find_nearest_start(&[(0, 0), (9, 9)], (2, 2)),
+            Err(AStarError::NodeNotFound((9, 9)))
+        ));
+        assert!(matches!(
+            astar.find_nearest_start(&[(0, 0)], (1, 1)),
+            Err(AStarError::GoalNodeBlocked((1, 1)))
+        ));
+        assert!(matches!(
+            astar.find_nearest_start(&[], (2, 2)),
+            Err(AStarError::InvalidConfiguration(_))
+        ));
+    }
+
+    #[test]
+    fn test_astar_nearest_start_with_costs() {
+        let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 10]; 10]));
+
+        // The closer start needs too long to get ready
+        let path = astar
+            .find_nearest_start_with_costs(&[((5, 5), 11), ((0, 0), 0)], (6, 6))
+            .unwrap();
+        assert_eq!(path.start(), (0, 0));
+        assert_eq!(path.total_cost(), 12);
+
+        let path = astar
+            .find_nearest_start_with_costs(&[((5, 5), 9), ((0, 0), 0)], (6, 6))
+            .unwrap();
+        assert_eq!(path.start(), (5, 5));
+        assert_eq!(path.total_cost(), 2);
+
+        // A start listed twice keeps its cheapest initial cost
+        let path = astar
+            .find_nearest_start_with_costs(&[((5, 5), 20), ((0, 0), 0), ((5, 5), 1)], (6, 6))
+            .unwrap();
+        assert_eq!(path.start(), (5, 5));
+
+        // Walking past an expensive start is cheaper than starting there
+        let path = astar
+            .find_nearest_start_with_costs(&[((0, 0), 0), ((2, 0), 5)], (4, 0))
+            .unwrap();
+        assert_eq!(path.start(), (0, 0));
+        assert!(path.contains((2, 0)));
+        assert_eq!(path.total_cost(), 4);
+    }
+
+    #[test]
+    fn test_astar_nearest_start_unreachable() {
+        let grid = vec![vec![0, 0, 1, 0], vec![0, 0, 1, 0], vec![0, 0, 1, 0]];
+        let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+
+        match astar.find_nearest_start(&[(0, 0), (1, 2)], (3, 1)) {
+            Err(AStarError::PathNotFound(unreachable)) => {
+                assert_eq!(
+                    unreachable.reason,
+                    UnreachableReason::DisconnectedComponents
+                );
+                assert_eq!(unreachable.closest, (1, 1));
+                assert_eq!(unreachable.explored, 6);
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    // Helper function to create a grid with uneven terrain costs and a wall
+    fn create_terrain_grid(size: usize) -> HashMap<(usize, usize), Node> {
+        let mut grid = vec![vec![0; size]; size];
+        for row in grid.iter_mut().take(size - 2).skip(2) {
+            row[size / 2] = 1;
+        }
+        let mut nodes = Node::grid_to_nodes(&grid);
+        for (&(a, b), node) in nodes.iter_mut() {
+            node.set_cost((a * 7 + b * 3) % 5 + 1);
+        }
+        nodes
+    }
+
+    #[test]
+    fn test_astar_weighted_heuristic() {
+        let mut astar = AStar::new(create_terrain_grid(30));
+        let optimal = astar.find_shortest_path((0, 15), (29, 14)).unwrap();
+        let expanded = astar.closed_positions().len();
+
+        for weight in [1.5, 2.0, 5.0] {
+            astar.set_heuristic_weight(weight);
+            let path = astar.find_shortest_path((0, 15), (29, 14)).unwrap();
+            assert!(path.total_cost() as f64 <= optimal.total_cost() as f64 * weight);
+            assert!(astar.closed_positions().len() <= expanded);
+        }
+
+        for weight in [0.5, f64::NAN, f64::INFINITY] {
+            astar.set_heuristic_weight(weight);
+            assert!(matches!(
+                astar.find_shortest_path((0, 15), (29, 14)),
+                Err(AStarError::InvalidConfiguration(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_astar_anytime_path() {
+        let mut astar = AStar::new(create_terrain_grid(30));
+        let optimal = astar
+            .find_shortest_path((0, 15), (29, 14))
+            .unwrap()
+            .total_cost();
+
+        // Given enough time, the shortest path is found and proven
+        let options = AnytimeOptions {
+            time_budget: Duration::from_secs(60),
+            ..AnytimeOptions::default()
+        };
+        let result = astar
+            .find_anytime_path((0, 15), (29, 14), &options)
+            .unwrap();
+        assert_eq!(result.bound, 1.0);
+        assert_eq!(result.path.total_cost(), optimal);
+        assert!(result.searches > 1);
+
+        // Without time to improve, the first path comes with its bound
+        let options = AnytimeOptions {
+            time_budget: Duration::ZERO,
+            ..AnytimeOptions::default()
+        };
+        let result = astar
+            .find_anytime_path((0, 15), (29, 14), &options)
+            .unwrap();
+        assert_eq!(result.searches, 1);
+        assert!(result.bound >= 1.0 && result.bound <= options.initial_weight);
+        assert!(result.path.total_cost() as f64 <= optimal as f64 * result.bound);
+        assert_eq!(result.path.start(), (0, 15));
+        assert_eq!(result.path.goal(), (29, 14));
+    }
+
+    #[test]
+    fn test_astar_anytime_path_errors() {
+        let grid = vec![vec![0, 0, 1, 0], vec![0, 0, 1, 0], vec![0, 0, 1, 0]];
+        let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+
+        assert!(matches!(
+            astar.find_anytime_path((0, 0), (3, 0), &AnytimeOptions::default()),
+            Err(AStarError::PathNotFound(_))
+        ));
+        assert!(matches!(
+            astar.find_anytime_path((0, 0), (2, 0), &AnytimeOptions::default()),
+            Err(AStarError::GoalNodeBlocked(_))
+        ));
+        let options = AnytimeOptions {
+            weight_step: 0.0,
+            ..AnytimeOptions::default()
+        };
+        assert!(matches!(
+            astar.find_anytime_path((0, 0), (1, 2), &options),
+            Err(AStarError::InvalidConfiguration(_))
+        ));
+
+        let result = astar
+            .find_anytime_path((0, 0), (1, 2), &AnytimeOptions::default())
+            .unwrap();
+        assert_eq!(result.path.total_cost(), 3);
+    }
+
+    #[test]
+    fn test_astar_ida_matches_astar() {
+        let mut astar = AStar::new(create_terrain_grid(8));
+        let tables = [None, Some(4), Some(1000)];
+
+        for (start, goal) in [((0, 0), (7, 7)), ((0, 4), (7, 3)), ((6, 1), (1, 6))] {
+            let expected = astar.find_shortest_path(start, goal).unwrap();
+            for transposition_table in tables {
+                let options = IdaOptions {
+                    transposition_table,
+                    ..IdaOptions::default()
+                };
+                let path = astar.find_path_ida(start, goal, &options).unwrap();
+                assert_eq!(path.total_cost(), expected.total_cost());
+                assert_eq!((path.start(), path.goal()), (start, goal));
+                assert!(astar.g_scores().is_empty());
+            }
+        }
+
+        let path = astar
+            .find_path_ida((3, 3), (3, 3), &IdaOptions::default())
+            .unwrap();
+        assert!(path.is_empty());
+    }
+
+    #[test]
+    fn test_astar_ida_with_teleporter() {
+        let mut nodes = Node::grid_to_nodes(&vec![vec![0; 10]; 10]);
+        nodes
+            .get_mut(&(0, 0))
+            .unwrap()
+            .add_custom_edge((9, 8), 1, "teleporter");
+        let mut astar = AStar::new(nodes);
+
+        let path = astar
+            .find_path_ida((0, 0), (9, 9), &IdaOptions::default())
+            .unwrap();
+        assert_eq!(path.positions(), [(0, 0), (8, 9), (9, 9)]);
+    }
+
+    #[test]
+    fn test_astar_ida_default_table_on_open_grid() {
+        let mut grid = vec![vec![0; 12]; 12];
+        grid[6][..11].fill(1);
+        let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+        let expected = astar.find_shortest_path((0, 0), (0, 11)).unwrap();
+
+        let path = astar
+            .find_path_ida((0, 0), (0, 11), &IdaOptions::default())
+            .unwrap();
+        assert_eq!(path.total_cost(), expected.total_cost());
+    }
+
+    #[test]
+    fn test_astar_ida_unreachable_goal_without_table() {
+        let mut grid = vec![vec![0; 8]; 6];
+        grid[3].fill(1);
+        let nodes = Node::grid_to_nodes(&grid);
+        let components = ComponentIndex::new(&nodes);
+        let mut astar = AStar::new(nodes);
+
+        // The index reports the goal before any route is followed
+        let options = IdaOptions {
+            transposition_table: None,
+            components: Some(&components),
+        };
+        match astar.find_path_ida((0, 0), (7, 5), &options) {
+            Err(AStarError::PathNotFound(unreachable)) => {
+                assert_eq!(
+                    unreachable.reason,
+                    UnreachableReason::DisconnectedComponents
+                );
+                assert_eq!(unreachable.explored, 0);
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_astar_ida_unreachable_goal_on_large_map() {
+        // The goal is walled into the far corner of a large open grid
+        let size = 200;
+        let mut grid = vec![vec![0; size]; size];
+        grid[size - 2][size - 1] = 1;
+        grid[size - 1][size - 2] = 1;
+        let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+        astar.set_max_expansions(Some(1000));
+
+        // Only the expansion limit ends the search, no score tables are filled
+        assert!(matches!(
+            astar.find_path_ida((0, 0), (size - 1, size - 1), &IdaOptions::default()),
+            Err(AStarError::SearchLimitExceeded { limit: 1000, .. })
+        ));
+        assert!(astar.g_scores().is_empty());
+        assert!(astar.closed_positions().is_empty());
+        assert!(astar.open_positions().is_empty());
+    }
+
+    #[test]
+    fn test_astar_ida_errors() {
+        let grid = vec![vec![0, 0, 1, 0], vec![0, 0, 1, 0], vec![0, 0, 1, 0]];
+        let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+        let options = IdaOptions {
+            transposition_table: Some(16),
+            ..IdaOptions::default()
+        };
+
+        // Without a component index, the search gives up after following every route
+        match astar.find_path_ida((0, 1), (3, 1), &options) {
+            Err(AStarError::PathNotFound(unreachable)) => {
+                assert_eq!(unreachable.reason, UnreachableReason::Unknown);
+                assert_eq!(unreachable.closest, (1, 1));
+                assert!(unreachable.explored >= 5);
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+        assert!(matches!(
+            astar.find_path_ida((0, 0), (2, 0), &options),
+            Err(AStarError::GoalNodeBlocked(_))
+        ));
+
+        let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 10]; 10]));
+        astar.set_max_expansions(Some(5));
+        assert!(matches!(
+            astar.find_path_ida((0, 0), (9, 9), &options),
+            Err(AStarError::SearchLimitExceeded { limit: 5, .. })
+        ));
+    }
 }
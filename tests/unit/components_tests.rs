@@ -0,0 +1,167 @@
+#[cfg(test)]
+mod tests {
+    use controlled_astar::{AStar, ComponentIndex, Direction, Node};
+
+    #[test]
+    fn test_regions_of_a_symmetric_grid() {
+        let grid = vec![vec![0, 1, 0], vec![0, 1, 0], vec![1, 1, 0]];
+        let index = ComponentIndex::new(&Node::grid_to_nodes(&grid));
+
+        assert_eq!(index.region_count(), 2);
+        assert_eq!(index.region((0, 0)), index.region((0, 1)));
+        assert_ne!(index.region((0, 0)), index.region((2, 2)));
+        assert_eq!(index.region((1, 0)), None);
+        assert_eq!(index.region((9, 9)), None);
+        assert_eq!(index.regions().len(), 5);
+
+        assert!(index.is_reachable((2, 0), (2, 2)));
+        assert!(!index.is_reachable((0, 0), (2, 2)));
+        assert!(!index.is_reachable((0, 0), (1, 0)));
+    }
+
+    #[test]
+    fn test_one_way_edges() {
+        // A one-way link from the left column to the right one through a teleporter
+        let grid = vec![vec![0, 1, 0], vec![0, 1, 0]];
+        let mut nodes = Node::grid_to_nodes(&grid);
+        nodes
+            .get_mut(&(0, 0))
+            .unwrap()
+            .add_custom_edge((0, 2), 3, "teleporter");
+        let index = ComponentIndex::new(&nodes);
+
+        assert_eq!(index.region_count(), 2);
+        assert!(index.is_reachable((0, 1), (2, 1)));
+        assert!(!index.is_reachable((2, 1), (0, 1)));
+    }
+
+    #[test]
+    fn test_directed_chain() {
+        // Every cell of a row only links to the east, so no two cells share a component
+        let mut nodes = Node::grid_to_nodes(&[vec![0, 0, 0, 0]]);
+        for node in nodes.values_mut() {
+            node.remove_neighbor(Direction::North);
+        }
+        let index = ComponentIndex::new(&nodes);
+
+        assert_eq!(index.region_count(), 4);
+        assert!(index.is_reachable((0, 0), (3, 0)));
+        assert!(index.is_reachable((2, 0), (2, 0)));
+        assert!(!index.is_reachable((3, 0), (0, 0)));
+    }
+
+    #[test]
+    fn test_incremental_updates_match_a_rebuild() {
+        let grid = vec![
+            vec![0, 0, 0, 0, 0],
+            vec![0, 1, 1, 1, 0],
+            vec![0, 0, 0, 1, 0],
+            vec![1, 1, 0, 1, 0],
+            vec![0, 0, 0, 0, 0],
+        ];
+        let mut nodes = Node::grid_to_nodes(&grid);
+        nodes
+            .get_mut(&(4, 0))
+            .unwrap()
+            .remove_neighbor(Direction::South);
+        let mut index = ComponentIndex::new(&nodes);
+
+        let changes = [
+            ((4, 0), true),
+            ((0, 2), true),
+            ((2, 4), true),
+            ((4, 0), false),
+            ((2, 3), false),
+            ((0, 2), false),
+            ((2, 4), false),
+        ];
+        for (position, blocked) in changes {
+            index.set_blocked(&mut nodes, position, blocked);
+            let rebuilt = ComponentIndex::new(&nodes);
+            assert_eq!(index.region_count(), rebuilt.region_count());
+
+            for from in nodes.keys().map(|&(y, x)| (x, y)) {
+                for to in nodes.keys().map(|&(y, x)| (x, y)) {
+                    assert_eq!(
+                        index.is_reachable(from, to),
+                        rebuilt.is_reachable(from, to),
+                        "{:?} -> {:?} after {:?}",
+                        from,
+                        to,
+                        (position, blocked)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_incremental_updates_on_directed_components() {
+        // Rows only link eastwards and one teleporter leads back, so components chain up
+        let mut nodes = Node::grid_to_nodes(&vec![vec![0; 6]; 3]);
+        for node in nodes.values_mut() {
+            node.remove_neighbor(Direction::West);
+        }
+        nodes
+            .get_mut(&(2, 5))
+            .unwrap()
+            .add_custom_edge((0, 0), 1, "teleporter");
+        let mut index = ComponentIndex::new(&nodes);
+
+        let mut positions: Vec<_> = nodes.keys().map(|&(y, x)| (x, y)).collect();
+        positions.sort();
+        for blocked in [true, false] {
+            for &position in positions.iter().step_by(4) {
+                index.set_blocked(&mut nodes, position, blocked);
+                let rebuilt = ComponentIndex::new(&nodes);
+                assert_eq!(index.region_count(), rebuilt.region_count());
+                for &from in &positions {
+                    for &to in &positions {
+                        assert_eq!(
+                            index.is_reachable(from, to),
+                            rebuilt.is_reachable(from, to),
+                            "{:?} -> {:?} after {:?}",
+                            from,
+                            to,
+                            (position, blocked)
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_agrees_with_astar() {
+        let grid = vec![
+            vec![0, 0, 1, 0],
+            vec![1, 0, 1, 0],
+            vec![0, 0, 1, 0],
+            vec![0, 1, 0, 0],
+        ];
+        let mut nodes = Node::grid_to_nodes(&grid);
+        nodes
+            .get_mut(&(3, 0))
+            .unwrap()
+            .set_neighbor(Direction::NorthEast, None);
+        nodes
+            .get_mut(&(2, 1))
+            .unwrap()
+            .add_custom_edge((3, 2), 1, "ladder");
+        let index = ComponentIndex::new(&nodes);
+        let mut astar = AStar::new(nodes.clone());
+
+        for from in nodes.keys().map(|&(y, x)| (x, y)) {
+            for to in nodes.keys().map(|&(y, x)| (x, y)) {
+                let found = astar.find_shortest_path(from, to).is_ok();
+                assert_eq!(
+                    index.is_reachable(from, to),
+                    found,
+                    "{:?} -> {:?}",
+                    from,
+                    to
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+#[cfg(test)]
+mod tests {
+    use controlled_astar::{
+        AStar, AStarError, Direction, DocumentError, LandmarkStrategy, LandmarkTable, MapDocument,
+        Node, Path, PathDatabase, State, MAP_DOCUMENT_VERSION,
+    };
+    use std::collections::HashMap;
+
+    // Helper function to create a map with manual neighbor changes, as in the example
+    fn create_edited_map() -> HashMap<(usize, usize), Node> {
+        let mut nodes =
+            Node::grid_to_nodes(&[vec![0, 0, 0, 1], vec![0, 1, 0, 0], vec![0, 0, 0, 0]]);
+        let node = nodes.get_mut(&(0, 0)).unwrap();
+        node.remove_neighbor(Direction::South);
+        node.remove_neighbor(Direction::East);
+        node.set_neighbor(Direction::SouthEast, Some((1, 1)));
+        node.set_neighbor(Direction::NorthWest, None);
+        node.add_custom_edge((2, 3), 4, "teleporter");
+        nodes.get_mut(&(2, 2)).unwrap().set_cost(7);
+        nodes
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let nodes = create_edited_map();
+        let json = MapDocument::from_nodes(&nodes).to_json().unwrap();
+        let restored = MapDocument::from_json(&json).unwrap();
+
+        assert_eq!(restored.version, MAP_DOCUMENT_VERSION);
+        assert_eq!(restored.into_nodes(), nodes);
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let nodes = create_edited_map();
+        let bytes = MapDocument::from_nodes(&nodes).to_bytes().unwrap();
+        let restored = MapDocument::from_bytes(&bytes).unwrap();
+
+        assert_eq!(restored.into_nodes(), nodes);
+
+        // The binary form is smaller than the JSON form
+        let json = MapDocument::from_nodes(&nodes).to_json().unwrap();
+        assert!(bytes.len() < json.len());
+    }
+
+    #[test]
+    fn test_round_trip_keeps_mismatched_keys() {
+        // A node stored under a key that differs from its coordinates
+        let mut nodes = create_edited_map();
+        let moved = nodes.remove(&(2, 3)).unwrap();
+        nodes.insert((5, 5), moved);
+
+        let json = MapDocument::from_nodes(&nodes).to_json().unwrap();
+        let restored = MapDocument::from_json(&json).unwrap().into_nodes();
+        assert_eq!(restored, nodes);
+        assert_eq!((restored[&(5, 5)].x, restored[&(5, 5)].y), (2, 3));
+        assert!(!restored.contains_key(&(2, 3)));
+
+        let bytes = MapDocument::from_nodes(&nodes).to_bytes().unwrap();
+        assert_eq!(MapDocument::from_bytes(&bytes).unwrap().into_nodes(), nodes);
+    }
+
+    #[test]
+    fn test_output_is_stable() {
+        // The same map always produces the same document
+        let nodes = create_edited_map();
+        let first = MapDocument::from_nodes(&nodes).to_json().unwrap();
+        let second = MapDocument::from_nodes(&nodes.clone()).to_json().unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let mut document = MapDocument::from_nodes(&create_edited_map());
+        document.version = MAP_DOCUMENT_VERSION + 1;
+
+        let json = document.to_json().unwrap();
+        assert!(matches!(
+            MapDocument::from_json(&json),
+            Err(DocumentError::UnsupportedVersion(version)) if version == MAP_DOCUMENT_VERSION + 1
+        ));
+
+        let bytes = document.to_bytes().unwrap();
+        assert!(matches!(
+            MapDocument::from_bytes(&bytes),
+            Err(DocumentError::UnsupportedVersion(_))
+        ));
+
+        // A future version with a different structure is still reported as such
+        assert!(matches!(
+            MapDocument::from_json(r#"{"version": 2, "cells": []}"#),
+            Err(DocumentError::UnsupportedVersion(2))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(matches!(
+            MapDocument::from_bytes(b"not a map"),
+            Err(DocumentError::InvalidMagic)
+        ));
+        assert!(matches!(
+            MapDocument::from_json("{"),
+            Err(DocumentError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn test_restored_map_finds_same_path() {
+        let nodes = create_edited_map();
+        let restored = MapDocument::from_json(&MapDocument::from_nodes(&nodes).to_json().unwrap())
+            .unwrap()
+            .into_nodes();
+
+        let path = AStar::new(nodes)
+            .find_shortest_path((0, 0), (3, 2))
+            .unwrap();
+        let restored_path = AStar::new(restored)
+            .find_shortest_path((0, 0), (3, 2))
+            .unwrap();
+        assert_eq!(path, restored_path);
+
+        // Cached paths round trip with their costs and directions
+        let json = serde_json::to_string(&path).unwrap();
+        let cached: Path = serde_json::from_str(&json).unwrap();
+        assert_eq!(cached, path);
+        assert!(cached.contains((3, 2)));
+        assert!(serde_json::from_str::<Path>(
+            r#"{"positions":[[0,0],[1,0]],"step_costs":[],"directions":[]}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_other_types_serialize() {
+        let state = State {
+            cost: 3,
+            position: (1, 2),
+        };
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(serde_json::from_str::<State>(&json).unwrap(), state);
+
+        let error = AStarError::GoalNodeBlocked((4, 5));
+        let json = serde_json::to_string(&error).unwrap();
+        assert!(matches!(
+            serde_json::from_str::<AStarError>(&json).unwrap(),
+            AStarError::GoalNodeBlocked((4, 5))
+        ));
+    }
+
+    #[test]
+    fn test_landmark_table_round_trip() {
+        let nodes = create_edited_map();
+        let table = LandmarkTable::new(&nodes, 2, LandmarkStrategy::Avoid);
+
+        let json = serde_json::to_string(&table).unwrap();
+        let restored: LandmarkTable = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, table);
+        assert_eq!(
+            restored.estimate((0, 0), (3, 2)),
+            table.estimate((0, 0), (3, 2))
+        );
+
+        let bytes = bincode::serialize(&table).unwrap();
+        assert_eq!(
+            bincode::deserialize::<LandmarkTable>(&bytes).unwrap(),
+            table
+        );
+
+        // Distance lists must match the positions
+        assert!(serde_json::from_str::<LandmarkTable>(
+            r#"{"landmarks":[[0,0]],"positions":[[0,0],[0,1]],"from_landmarks":[[0]],"to_landmarks":[[0,1]]}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_path_database_round_trip() {
+        let nodes = create_edited_map();
+        let database = PathDatabase::new(&nodes).unwrap();
+
+        let restored = PathDatabase::from_bytes(&database.to_bytes().unwrap()).unwrap();
+        assert_eq!(restored, database);
+        assert_eq!(
+            restored.find_path(&nodes, (0, 0), (3, 2)).unwrap(),
+            database.find_path(&nodes, (0, 0), (3, 2)).unwrap()
+        );
+
+        let json = serde_json::to_string(&database).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PathDatabase>(&json).unwrap(),
+            database
+        );
+        // Runs must start at the first position
+        assert!(serde_json::from_str::<PathDatabase>(
+            r#"{"positions":[[0,0],[0,1]],"runs":[[[1,0]],[[0,0]]]}"#
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use controlled_astar::{AStar, Direction, Heuristic, HexCoord, HexDirection, HexLayout, Node};
+
+    #[test]
+    fn test_offset_round_trip() {
+        // Converting offset -> axial -> offset should give back the same cell
+        for layout in [HexLayout::OddR, HexLayout::EvenR] {
+            for row in 0..5 {
+                for col in 0..5 {
+                    let coord = HexCoord::from_offset(col, row, layout);
+                    assert_eq!(coord.to_offset(layout), (col, row));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cube_coordinates() {
+        // Cube coordinates always sum to zero
+        let coord = HexCoord::new(2, -5);
+        let (x, y, z) = coord.to_cube();
+        assert_eq!(x + y + z, 0);
+        assert_eq!(HexCoord::from_cube(x, y, z), Some(coord));
+    }
+
+    #[test]
+    fn test_hex_distance() {
+        let origin = HexCoord::new(0, 0);
+
+        // Every neighbor is exactly one step away
+        for direction in HexDirection::ALL {
+            assert_eq!(origin.distance(origin.neighbor(direction)), 1);
+        }
+
+        // Moving east three times and south-east twice takes five steps
+        assert_eq!(origin.distance(HexCoord::new(3, 2)), 5);
+    }
+
+    #[test]
+    fn test_hex_grid_to_nodes_neighbors() {
+        let grid = vec![vec![0; 3]; 3];
+        let nodes = Node::hex_grid_to_nodes(&grid, HexLayout::OddR);
+
+        // The center cell of an odd-r grid sits on a shoved row and has all six neighbors
+        let center = &nodes[&(1, 1)];
+        assert_eq!(center.neighbors.len(), 6);
+        assert_eq!(center.neighbors[&Direction::NorthEast], Some((0, 2)));
+        assert_eq!(center.neighbors[&Direction::NorthWest], Some((0, 1)));
+        assert_eq!(center.neighbors[&Direction::SouthEast], Some((2, 2)));
+        assert_eq!(center.neighbors[&Direction::SouthWest], Some((2, 1)));
+        assert_eq!(center.neighbors[&Direction::East], Some((1, 2)));
+        assert_eq!(center.neighbors[&Direction::West], Some((1, 0)));
+
+        // Hex cells never have straight north or south neighbors
+        assert!(!center.neighbors.contains_key(&Direction::North));
+        assert!(!center.neighbors.contains_key(&Direction::South));
+    }
+
+    #[test]
+    fn test_hex_heuristic_matches_distance() {
+        // Map keys are (row, column)
+        let heuristic = Heuristic::Hex(HexLayout::EvenR);
+        let a = HexCoord::from_offset(0, 0, HexLayout::EvenR);
+        let b = HexCoord::from_offset(3, 4, HexLayout::EvenR);
+        assert_eq!(heuristic.estimate((0, 0), (4, 3)), a.distance(b) as usize);
+    }
+
+    #[test]
+    fn test_astar_on_hex_grid() {
+        let grid = vec![vec![0; 5]; 5];
+        let mut astar = AStar::new(Node::hex_grid_to_nodes(&grid, HexLayout::OddR));
+        astar.set_heuristic(Heuristic::Hex(HexLayout::OddR));
+
+        // The path length must match the hex distance on an open grid
+        let path = astar.find_shortest_path((0, 0), (4, 4)).unwrap();
+        let distance = HexCoord::from_offset(0, 0, HexLayout::OddR)
+            .distance(HexCoord::from_offset(4, 4, HexLayout::OddR));
+        assert_eq!(path.len(), distance as usize);
+        assert_eq!(path.start(), (0, 0));
+        assert_eq!(path.goal(), (4, 4));
+    }
+}
@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use controlled_astar::{AStar, AStarError, HierarchicalMap, Node, Path};
+
+    /// Builds a square grid with a scattered obstacle pattern.
+    fn create_obstacle_grid(size: usize) -> Vec<Vec<i32>> {
+        (0..size)
+            .map(|y| {
+                (0..size)
+                    .map(|x| i32::from((x * 31 + y * 17) % 7 == 0 && (x, y) != (0, 0)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_hierarchy_open_grid_matches_astar() {
+        let nodes = Node::grid_to_nodes(&vec![vec![0; 20]; 20]);
+        let map = HierarchicalMap::new(&nodes, 5);
+        let mut astar = AStar::new(nodes.clone());
+
+        for &(start, goal) in &[((0, 0), (19, 19)), ((3, 17), (16, 2)), ((7, 7), (8, 9))] {
+            let path = map.find_path(&nodes, start, goal).unwrap();
+            let expected = astar.find_shortest_path(start, goal).unwrap();
+            assert_eq!(path.total_cost(), expected.total_cost());
+            assert_eq!((path.start(), path.goal()), (start, goal));
+        }
+    }
+
+    #[test]
+    fn test_hierarchy_obstacles_stay_close_to_astar() {
+        let grid = create_obstacle_grid(24);
+        let nodes = Node::grid_to_nodes(&grid);
+        let map = HierarchicalMap::new(&nodes, 6);
+        let mut astar = AStar::new(nodes.clone());
+
+        for goal in [(23, 23), (0, 23), (23, 0), (12, 11), (5, 18)] {
+            let expected = astar.find_shortest_path((0, 0), goal);
+            let path = map.find_path(&nodes, (0, 0), goal);
+            match (expected, path) {
+                (Ok(expected), Ok(path)) => {
+                    assert!(path.total_cost() >= expected.total_cost());
+                    assert!(path.total_cost() * 2 <= expected.total_cost() * 3);
+                    // The refined path follows the map step by step
+                    let positions = path.positions().to_vec();
+                    assert_eq!(Path::from_positions(&nodes, positions), Some(path));
+                }
+                (Err(expected), Err(error)) => assert_eq!(
+                    std::mem::discriminant(&expected),
+                    std::mem::discriminant(&error)
+                ),
+                (expected, path) => panic!("{goal:?}: A* gave {expected:?}, HPA* gave {path:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_hierarchy_set_blocked_matches_rebuild() {
+        let mut nodes = Node::grid_to_nodes(&create_obstacle_grid(16));
+        let mut map = HierarchicalMap::new(&nodes, 4);
+
+        let changes = [
+            ((4, 5), true),
+            ((7, 3), true),
+            ((8, 8), true),
+            ((7, 3), false),
+        ];
+        for (position, blocked) in changes {
+            map.set_blocked(&mut nodes, position, blocked);
+            let rebuilt = HierarchicalMap::new(&nodes, 4);
+            assert_eq!(map.entrances(), rebuilt.entrances());
+
+            for goal in [(15, 15), (15, 0), (8, 9)] {
+                let path = map.find_path(&nodes, (0, 0), goal).map(|p| p.total_cost());
+                let expected = rebuilt
+                    .find_path(&nodes, (0, 0), goal)
+                    .map(|p| p.total_cost());
+                assert_eq!(path.ok(), expected.ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_hierarchy_errors() {
+        let mut nodes = Node::grid_to_nodes(&vec![vec![0; 8]; 8]);
+        let mut map = HierarchicalMap::new(&nodes, 4);
+
+        assert!(matches!(
+            map.find_path(&nodes, (0, 0), (8, 0)),
+            Err(AStarError::NodeNotFound((8, 0)))
+        ));
+
+        map.set_blocked(&mut nodes, (0, 0), true);
+        assert!(matches!(
+            map.find_path(&nodes, (0, 0), (7, 7)),
+            Err(AStarError::StartNodeBlocked((0, 0)))
+        ));
+        assert!(matches!(
+            map.find_path(&nodes, (7, 7), (0, 0)),
+            Err(AStarError::GoalNodeBlocked((0, 0)))
+        ));
+
+        // Wall off the corner (7, 7)
+        map.set_blocked(&mut nodes, (6, 7), true);
+        map.set_blocked(&mut nodes, (7, 6), true);
+        match map.find_path(&nodes, (1, 1), (7, 7)) {
+            Err(AStarError::PathNotFound(unreachable)) => assert!(unreachable.explored > 0),
+            other => panic!("expected PathNotFound, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_hierarchy_custom_edges_cross_clusters() {
+        let mut grid = vec![vec![0; 12]; 4];
+        for row in grid.iter_mut() {
+            row[6] = 1;
+        }
+        let mut nodes = Node::grid_to_nodes(&grid);
+        nodes
+            .get_mut(&(0, 1))
+            .unwrap()
+            .add_custom_edge((3, 10), 2, "teleporter");
+        let map = HierarchicalMap::new(&nodes, 4);
+
+        let path = map.find_path(&nodes, (0, 0), (11, 3)).unwrap();
+        let expected = AStar::new(nodes.clone())
+            .find_shortest_path((0, 0), (11, 3))
+            .unwrap();
+        assert_eq!(path.total_cost(), expected.total_cost());
+        assert!(path.contains((10, 3)));
+    }
+}
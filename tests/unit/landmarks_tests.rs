@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use controlled_astar::{AStar, Direction, LandmarkStrategy, LandmarkTable, Node};
+    use std::collections::HashMap;
+
+    /// Builds a maze of walls with alternating gaps, so paths wind back and forth.
+    fn create_maze(size: usize) -> Vec<Vec<i32>> {
+        let mut grid = vec![vec![0; size]; size];
+        for (y, row) in grid.iter_mut().enumerate() {
+            if y % 2 == 1 {
+                row.fill(1);
+                let gap = if y % 4 == 1 { size - 1 } else { 0 };
+                row[gap] = 0;
+            }
+        }
+        grid
+    }
+
+    /// Builds the maze with terrain costs and a few one-way links.
+    fn create_directed_maze(size: usize) -> HashMap<(usize, usize), Node> {
+        let mut nodes = Node::grid_to_nodes(&create_maze(size));
+        for (&(a, b), node) in nodes.iter_mut() {
+            node.set_cost((a * 5 + b * 3) % 4 + 1);
+        }
+        // Row 0 can only be walked towards larger x
+        for b in 1..size {
+            nodes
+                .get_mut(&(0, b))
+                .unwrap()
+                .remove_neighbor(Direction::West);
+        }
+        nodes
+    }
+
+    #[test]
+    fn test_landmarks_estimates_are_admissible() {
+        let nodes = create_directed_maze(9);
+        let mut astar = AStar::new(nodes.clone());
+
+        for strategy in [LandmarkStrategy::Farthest, LandmarkStrategy::Avoid] {
+            let table = LandmarkTable::new(&nodes, 3, strategy);
+            assert_eq!(table.landmarks().len(), 3);
+            for from in [(0, 0), (8, 0), (4, 4), (0, 8)] {
+                for goal in [(8, 8), (0, 0), (3, 2), (7, 6)] {
+                    let estimate = table.estimate(from, goal);
+                    if let Ok(path) = astar.find_shortest_path(from, goal) {
+                        assert!(
+                            estimate <= path.total_cost(),
+                            "{strategy:?} {from:?} -> {goal:?}: {estimate} > {}",
+                            path.total_cost()
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_landmarks_reduce_expansions() {
+        // A cup open away from the goal traps searches led by the Manhattan distance
+        let mut grid = vec![vec![0; 21]; 21];
+        grid[13][3..=17].fill(1);
+        for row in grid.iter_mut().take(14).skip(5) {
+            row[3] = 1;
+            row[17] = 1;
+        }
+        let nodes = Node::grid_to_nodes(&grid);
+        let mut plain = AStar::new(nodes.clone());
+        let expected = plain.find_shortest_path((10, 10), (10, 20)).unwrap();
+
+        for strategy in [LandmarkStrategy::Farthest, LandmarkStrategy::Avoid] {
+            let mut astar = AStar::new(nodes.clone());
+            astar.set_landmarks(Some(LandmarkTable::new(&nodes, 8, strategy)));
+            let path = astar.find_shortest_path((10, 10), (10, 20)).unwrap();
+            assert_eq!(path.total_cost(), expected.total_cost());
+            assert!(astar.closed_positions().len() < plain.closed_positions().len());
+        }
+    }
+
+    #[test]
+    fn test_landmarks_stay_optimal_with_costs_and_one_way_links() {
+        let nodes = create_directed_maze(13);
+        let mut plain = AStar::new(nodes.clone());
+        let mut astar = AStar::new(nodes.clone());
+        astar.set_landmarks(Some(LandmarkTable::new(&nodes, 5, LandmarkStrategy::Avoid)));
+
+        for (start, goal) in [((12, 12), (0, 0)), ((0, 0), (12, 12)), ((5, 4), (11, 0))] {
+            let expected = plain
+                .find_shortest_path(start, goal)
+                .map(|p| p.total_cost());
+            let found = astar
+                .find_shortest_path(start, goal)
+                .map(|p| p.total_cost());
+            assert_eq!(found.ok(), expected.ok());
+        }
+    }
+
+    #[test]
+    fn test_landmarks_cover_every_component() {
+        let grid = vec![vec![0, 0, 1, 0, 0], vec![0, 0, 1, 0, 0]];
+        let nodes = Node::grid_to_nodes(&grid);
+        let table = LandmarkTable::new(&nodes, 2, LandmarkStrategy::Farthest);
+        let landmarks = table.landmarks();
+        assert!(landmarks.iter().any(|&(x, _)| x < 2));
+        assert!(landmarks.iter().any(|&(x, _)| x > 2));
+
+        // Never more landmarks than passable nodes
+        let table = LandmarkTable::new(&nodes, 20, LandmarkStrategy::Avoid);
+        assert_eq!(table.landmarks().len(), 8);
+        // Positions outside the table are not estimated
+        assert_eq!(table.estimate((0, 0), (9, 9)), 0);
+    }
+}
@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod tests {
+    use controlled_astar::{
+        AStarError, Direction, LinkKind, MultiLevelMap, Node, UnreachableReason,
+    };
+
+    // Helper function to create a map with the given number of open 5x5 levels
+    fn create_building(levels: usize) -> MultiLevelMap {
+        let mut map = MultiLevelMap::new();
+        for _ in 0..levels {
+            map.add_level(Node::grid_to_nodes(&vec![vec![0; 5]; 5]));
+        }
+        map
+    }
+
+    // Helper function to sum the cost of a path, counting links by their cost
+    fn path_cost(map: &MultiLevelMap, path: &[(usize, usize, usize)]) -> usize {
+        path.windows(2)
+            .map(|step| {
+                map.links(step[0])
+                    .iter()
+                    .find(|link| link.to == step[1])
+                    .map_or(1, |link| link.cost)
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_path_on_single_level() {
+        let map = create_building(1);
+        let path = map.find_shortest_path((0, 0, 0), (0, 4, 4)).unwrap();
+
+        // A path on one level behaves like a regular grid path
+        assert_eq!(path.len(), 9);
+        assert!(path.iter().all(|&(level, _, _)| level == 0));
+    }
+
+    #[test]
+    fn test_path_uses_stairs() {
+        let mut map = create_building(2);
+        map.add_two_way_link((0, 4, 0), (1, 4, 0), 2, LinkKind::Stairs);
+
+        let path = map.find_shortest_path((0, 0, 0), (1, 0, 0)).unwrap();
+
+        // The path walks to the stairs, climbs them and walks back
+        assert!(path.contains(&(0, 4, 0)));
+        assert!(path.contains(&(1, 4, 0)));
+        assert_eq!(path_cost(&map, &path), 4 + 2 + 4);
+    }
+
+    #[test]
+    fn test_path_prefers_cheaper_elevator() {
+        let mut map = create_building(3);
+        // Stairs right next to the start, but climbing costs a lot
+        map.add_two_way_link((0, 1, 0), (1, 1, 0), 10, LinkKind::Stairs);
+        map.add_two_way_link((1, 1, 0), (2, 1, 0), 10, LinkKind::Stairs);
+        // An elevator a few steps away
+        map.add_elevator(3, 0, &[0, 1, 2], 1);
+
+        let path = map.find_shortest_path((0, 0, 0), (2, 0, 0)).unwrap();
+
+        assert!(path.contains(&(0, 3, 0)));
+        assert!(path.contains(&(2, 3, 0)));
+        assert_eq!(path_cost(&map, &path), 3 + 2 + 3);
+    }
+
+    #[test]
+    fn test_one_way_portal() {
+        let mut map = create_building(2);
+        map.add_link((1, 2, 2), (0, 2, 2), 1, LinkKind::Portal);
+
+        // Going down works, going up does not
+        assert!(map.find_shortest_path((1, 0, 0), (0, 0, 0)).is_ok());
+        match map.find_shortest_path((0, 0, 0), (1, 0, 0)) {
+            Err(AStarError::PathNotFound(unreachable)) => {
+                assert_eq!(unreachable.reason, UnreachableReason::OneWayEdges);
+                assert_eq!(unreachable.level, Some(0));
+                assert_eq!(unreachable.closest, (0, 0));
+                assert_eq!(unreachable.explored, 25);
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_blocked_link_target_is_skipped() {
+        let mut map = create_building(2);
+        map.add_two_way_link((0, 4, 4), (1, 4, 4), 1, LinkKind::Stairs);
+        map.level_mut(1)
+            .unwrap()
+            .get_mut(&(4, 4))
+            .unwrap()
+            .set_blocked(true);
+
+        assert!(matches!(
+            map.find_shortest_path((0, 0, 0), (1, 0, 0)),
+            Err(AStarError::PathNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_estimate_is_admissible_with_portals() {
+        let mut map = create_building(2);
+        // A portal that jumps across the whole level for a cost of 1
+        map.add_link((0, 0, 0), (1, 4, 4), 1, LinkKind::Portal);
+
+        // The planar distance would overestimate, so only the level bound remains
+        assert_eq!(map.estimate((0, 0, 0), (1, 4, 4)), 1);
+
+        let path = map.find_shortest_path((0, 0, 0), (1, 4, 4)).unwrap();
+        assert_eq!(path, vec![(0, 0, 0), (1, 4, 4)]);
+    }
+
+    #[test]
+    fn test_diagonal_edges_keep_planar_estimate() {
+        let mut map = create_building(1);
+        // A single diagonal step of cost 1 in the middle of the level
+        map.level_mut(0)
+            .unwrap()
+            .get_mut(&(2, 2))
+            .unwrap()
+            .set_neighbor(Direction::SouthEast, Some((3, 3)));
+
+        // Diagonals allow Chebyshev moves, but the estimate must not drop to 0
+        assert_eq!(map.estimate((0, 0, 0), (0, 4, 4)), 4);
+        assert_eq!(map.estimate((0, 0, 0), (0, 4, 0)), 4);
+
+        let path = map.find_shortest_path((0, 2, 2), (0, 3, 3)).unwrap();
+        assert_eq!(path, vec![(0, 2, 2), (0, 3, 3)]);
+    }
+
+    #[test]
+    fn test_estimate_follows_map_changes() {
+        let mut map = create_building(2);
+        map.add_two_way_link((0, 0, 0), (1, 0, 0), 3, LinkKind::Stairs);
+        assert_eq!(map.estimate((0, 0, 0), (1, 0, 0)), 3);
+
+        // A cheaper elevator lowers the cached level bound
+        map.add_elevator(4, 4, &[0, 1], 1);
+        assert_eq!(map.estimate((0, 0, 0), (1, 0, 0)), 1);
+
+        // A portal across the level disables the planar bound
+        map.add_link((0, 0, 0), (1, 4, 4), 1, LinkKind::Portal);
+        assert_eq!(map.estimate((0, 0, 0), (0, 4, 4)), 0);
+    }
+
+    #[test]
+    fn test_missing_level_is_reported() {
+        let map = create_building(1);
+        assert!(matches!(
+            map.find_shortest_path((0, 0, 0), (3, 0, 0)),
+            Err(AStarError::NodeNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use controlled_astar::{octile_length, AStar, MovingAiErrorKind, MovingAiMap, Scenario};
+
+    const MAP: &str = "type octile
+height 4
+width 5
+map
+.....
+.@@..
+.@T..
+...WS
+";
+
+    const SCENARIOS: &str = "version 1
+0\ttest.map\t5\t4\t0\t0\t0\t0\t0
+0\ttest.map\t5\t4\t0\t0\t4\t0\t4
+1\ttest.map\t5\t4\t0\t0\t4\t2\t5.41421356
+1\ttest.map\t5\t4\t0\t0\t2\t3\t5
+";
+
+    #[test]
+    fn test_parse_map() {
+        let map = MovingAiMap::parse(MAP).unwrap();
+        assert_eq!((map.width, map.height), (5, 4));
+        assert_eq!(map.nodes.len(), 20);
+
+        // Nodes are keyed by (row, column)
+        assert!(map.nodes[&(1, 1)].is_blocked); // '@'
+        assert!(map.nodes[&(2, 2)].is_blocked); // 'T'
+        assert!(map.nodes[&(3, 3)].is_blocked); // 'W'
+        assert!(!map.nodes[&(3, 4)].is_blocked); // 'S'
+    }
+
+    #[test]
+    fn test_diagonals_do_not_cut_corners() {
+        let map = MovingAiMap::parse(MAP).unwrap();
+
+        // (0, 0) -> (1, 1) is blocked itself
+        assert!(map.nodes[&(0, 0)].custom_edges.is_empty());
+
+        // (0, 3) -> (1, 4) is fine, (0, 3) -> (1, 2) is blocked
+        let targets: Vec<_> = map.nodes[&(0, 3)]
+            .custom_edges
+            .iter()
+            .map(|edge| edge.target)
+            .collect();
+        assert_eq!(targets, vec![(1, 4)]);
+
+        // (2, 3) -> (1, 2) would cut past the tree at (2, 2)
+        assert!(!map.nodes[&(2, 3)]
+            .custom_edges
+            .iter()
+            .any(|edge| edge.target == (1, 2)));
+    }
+
+    #[test]
+    fn test_parse_scenarios() {
+        let scenarios = Scenario::parse_all(SCENARIOS).unwrap();
+        assert_eq!(scenarios.len(), 4);
+        assert_eq!(scenarios[2].bucket, 1);
+        assert_eq!(scenarios[2].map, "test.map");
+        assert_eq!((scenarios[2].map_width, scenarios[2].map_height), (5, 4));
+        assert_eq!(scenarios[2].start, (0, 0));
+        assert_eq!(scenarios[2].goal, (4, 2));
+        assert!((scenarios[2].optimal_length - 5.41421356).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_run_scenarios() {
+        let map = MovingAiMap::parse(MAP).unwrap();
+        let scenarios = Scenario::parse_all(SCENARIOS).unwrap();
+        let results = map.run_scenarios(&scenarios);
+
+        assert_eq!(results.len(), 4);
+        for result in &results {
+            assert!(
+                result.is_optimal(),
+                "expected {} but found {:?}",
+                result.scenario.optimal_length,
+                result.length
+            );
+        }
+    }
+
+    #[test]
+    fn test_run_scenarios_detects_wrong_reference() {
+        let map = MovingAiMap::parse(MAP).unwrap();
+        let mut scenarios = Scenario::parse_all(SCENARIOS).unwrap();
+        scenarios[1].optimal_length = 3.0;
+
+        let results = map.run_scenarios(&scenarios);
+        assert!(!results[1].is_optimal());
+        assert!(results[1].is_within(1.0));
+    }
+
+    #[test]
+    fn test_path_matches_octile_length() {
+        let map = MovingAiMap::parse(MAP).unwrap();
+        let mut astar = AStar::new(map.nodes);
+        let path = astar.find_shortest_path((0, 0), (4, 2)).unwrap();
+
+        assert!((octile_length(path.positions()) - (4.0 + std::f64::consts::SQRT_2)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_map_errors() {
+        // Unsupported map type
+        let error = MovingAiMap::parse("type hex\nheight 1\nwidth 1\nmap\n.\n").unwrap_err();
+        assert_eq!(error.line, 1);
+        assert_eq!(
+            error.kind,
+            MovingAiErrorKind::UnsupportedType("hex".to_string())
+        );
+
+        // Unknown terrain
+        let error = MovingAiMap::parse("type octile\nheight 1\nwidth 2\nmap\n.x\n").unwrap_err();
+        assert_eq!(error.line, 5);
+        assert_eq!(
+            error.kind,
+            MovingAiErrorKind::UnknownTerrain {
+                column: 2,
+                character: 'x'
+            }
+        );
+
+        // Fewer lines than announced
+        let error = MovingAiMap::parse("type octile\nheight 3\nwidth 1\nmap\n.\n").unwrap_err();
+        assert_eq!(
+            error.kind,
+            MovingAiErrorKind::MissingLines {
+                expected: 3,
+                found: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_scenario_errors() {
+        let error = Scenario::parse_all("0\tm.map\t1\t1\t0\t0\t0\t0\t0\n").unwrap_err();
+        assert_eq!(error.line, 1);
+
+        let error = Scenario::parse_all("version 1\n0\tm.map\t1\t1\t0\t0\n").unwrap_err();
+        assert_eq!(error.line, 2);
+    }
+}
@@ -10,7 +10,7 @@ mod tests {
         // Check the node's attributes
         assert_eq!(node.x, 2); // Node's x-coordinate
         assert_eq!(node.y, 3); // Node's y-coordinate
-        assert_eq!(node.is_blocked, false); // Node should not be blocked
+        assert!(!node.is_blocked); // Node should not be blocked
 
         // Check the node's neighbors
         assert_eq!(node.neighbors[&Direction::North], Some((2, 2))); // North neighbor
@@ -103,8 +103,57 @@ mod tests {
         assert_eq!(nodes.len(), 9); // There should be 9 nodes
 
         // Check specific nodes' blocked status
-        assert_eq!(nodes[&(0, 0)].is_blocked, false); // Top-left node should not be blocked
-        assert_eq!(nodes[&(0, 1)].is_blocked, true); // Node (0, 1) should be blocked
-        assert_eq!(nodes[&(1, 1)].is_blocked, false); // Node (1, 1) should not be blocked
+        assert!(!nodes[&(0, 0)].is_blocked); // Top-left node should not be blocked
+        assert!(nodes[&(0, 1)].is_blocked); // Node (0, 1) should be blocked
+        assert!(!nodes[&(1, 1)].is_blocked); // Node (1, 1) should not be blocked
+    }
+
+    #[test]
+    fn test_custom_edges() {
+        // Create a new Node instance
+        let mut node = Node::new(2, 2, false, 9, 9);
+        assert!(node.custom_edges.is_empty()); // No custom edges initially
+
+        // Add a teleporter and a jump pad
+        node.add_custom_edge((8, 8), 3, "teleporter");
+        node.add_custom_edge((2, 5), 2, "jump pad");
+        assert_eq!(node.custom_edges.len(), 2);
+        assert_eq!(node.custom_edges[0].target, (8, 8));
+        assert_eq!(node.custom_edges[0].cost, 3);
+        assert_eq!(node.custom_edges[0].label, "teleporter");
+
+        // Remove the teleporter again
+        node.remove_custom_edges("teleporter");
+        assert_eq!(node.custom_edges.len(), 1);
+        assert_eq!(node.custom_edges[0].label, "jump pad");
+    }
+
+    #[test]
+    fn test_edges() {
+        // Create a grid and give the node at (0, 0) a custom edge
+        let mut nodes = Node::grid_to_nodes(&vec![vec![0; 9]; 9]);
+        nodes.get_mut(&(1, 0)).unwrap().set_cost(3);
+        let mut node = nodes[&(0, 0)].clone();
+        node.add_custom_edge((5, 5), 4, "teleporter");
+        node.add_custom_edge((20, 20), 1, "broken teleporter");
+
+        // Neighbors cost their node's cost, custom edges keep their cost,
+        // targets outside the map are skipped
+        let edges = node.edges(&nodes);
+        assert_eq!(edges.len(), 3);
+        assert!(edges.contains(&((0, 1), 1)));
+        assert!(edges.contains(&((1, 0), 3)));
+        assert!(edges.contains(&((5, 5), 4)));
+    }
+
+    #[test]
+    fn test_set_cost() {
+        // Nodes cost 1 to enter by default
+        let mut node = Node::new(2, 2, false, 9, 9);
+        assert_eq!(node.cost, 1);
+
+        // Set a higher cost for rough terrain
+        node.set_cost(7);
+        assert_eq!(node.cost, 7);
     }
 }
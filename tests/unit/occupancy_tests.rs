@@ -0,0 +1,196 @@
+#[cfg(test)]
+mod tests {
+    use controlled_astar::{
+        AStar, MapMetadata, Occupancy, OccupancyError, OccupancyGrid, OccupancyOptions,
+    };
+    use std::fs;
+
+    const YAML: &str = "image: test.pgm
+resolution: 0.5
+origin: [-1.0, 2.0, 0.0]
+negate: 0
+occupied_thresh: 0.65
+free_thresh: 0.196
+mode: trinary
+";
+
+    #[test]
+    fn test_parse_yaml() {
+        let metadata = MapMetadata::parse_yaml(YAML).unwrap();
+        assert_eq!(metadata.image, "test.pgm");
+        assert_eq!(metadata.resolution, 0.5);
+        assert_eq!(metadata.origin, (-1.0, 2.0, 0.0));
+        assert!(!metadata.negate);
+
+        let error = MapMetadata::parse_yaml("image: a.pgm\nresolution: fast\n").unwrap_err();
+        assert!(matches!(
+            error,
+            OccupancyError::InvalidMetadata { line: 2, .. }
+        ));
+        assert!(MapMetadata::parse_yaml("resolution: 0.1\n").is_err());
+    }
+
+    #[test]
+    fn test_thresholds() {
+        let mut metadata = MapMetadata::new(1.0);
+        assert_eq!(metadata.classify(254), Occupancy::Free);
+        assert_eq!(metadata.classify(0), Occupancy::Occupied);
+        assert_eq!(metadata.classify(205), Occupancy::Unknown);
+
+        metadata.negate = true;
+        assert_eq!(metadata.classify(254), Occupancy::Occupied);
+        assert_eq!(metadata.classify(0), Occupancy::Free);
+    }
+
+    #[test]
+    fn test_binary_and_plain_pgm() {
+        let mut binary = b"P5\n# comment\n3 2\n255\n".to_vec();
+        binary.extend_from_slice(&[254, 0, 254, 205, 254, 254]);
+        let plain = b"P2\n3 2\n# comment\n15\n15 0 15\n12 15 15\n";
+
+        for bytes in [&binary[..], &plain[..]] {
+            let grid =
+                OccupancyGrid::from_pgm(bytes, MapMetadata::new(1.0), OccupancyOptions::default())
+                    .unwrap();
+            assert_eq!((grid.width, grid.height), (3, 2));
+            assert_eq!(grid.occupancy((1, 0)), Some(Occupancy::Occupied));
+            assert_eq!(grid.occupancy((0, 1)), Some(Occupancy::Unknown));
+            assert_eq!(grid.occupancy((2, 1)), Some(Occupancy::Free));
+            assert_eq!(grid.occupancy((3, 1)), None);
+        }
+
+        let truncated = b"P5\n3 2\n255\n\xfe\xfe";
+        assert!(matches!(
+            OccupancyGrid::from_pgm(
+                truncated,
+                MapMetadata::new(1.0),
+                OccupancyOptions::default()
+            ),
+            Err(OccupancyError::InvalidImage(_))
+        ));
+
+        // Huge headers are rejected before any memory is reserved
+        for huge in [
+            &b"P5\n100000 100000\n255\n\x00"[..],
+            b"P2\n100000 100000\n255\n0 0",
+            b"P5\n18446744073709551615 2\n255\n\x00",
+        ] {
+            assert!(matches!(
+                OccupancyGrid::from_pgm(huge, MapMetadata::new(1.0), OccupancyOptions::default()),
+                Err(OccupancyError::InvalidImage(_))
+            ));
+        }
+        assert!(matches!(
+            OccupancyGrid::from_gray(
+                usize::MAX,
+                2,
+                &[0],
+                MapMetadata::new(1.0),
+                OccupancyOptions::default()
+            ),
+            Err(OccupancyError::InvalidImage(_))
+        ));
+    }
+
+    #[test]
+    fn test_unknown_cells_and_costs() {
+        let pixels = [254, 205, 128, 0];
+        let options = OccupancyOptions {
+            unknown_is_blocked: false,
+            max_cost: Some(10),
+        };
+        let grid = OccupancyGrid::from_gray(4, 1, &pixels, MapMetadata::new(1.0), options).unwrap();
+
+        assert!(!grid.nodes[&(0, 1)].is_blocked);
+        assert!(grid.nodes[&(0, 3)].is_blocked);
+        assert_eq!(grid.nodes[&(0, 0)].cost, 1);
+
+        // Darker cells cost more
+        assert!(grid.nodes[&(0, 1)].cost > 1);
+        assert!(grid.nodes[&(0, 2)].cost > grid.nodes[&(0, 1)].cost);
+        assert!(grid.nodes[&(0, 2)].cost <= 10);
+
+        let strict =
+            OccupancyGrid::from_gray(4, 1, &pixels, MapMetadata::new(1.0), Default::default())
+                .unwrap();
+        assert!(strict.nodes[&(0, 1)].is_blocked);
+    }
+
+    #[test]
+    fn test_world_and_cell_conversion() {
+        let metadata = MapMetadata::parse_yaml(YAML).unwrap();
+        let grid = OccupancyGrid::from_gray(4, 2, &[254; 8], metadata, OccupancyOptions::default())
+            .unwrap();
+
+        // The origin is the lower-left corner of the image
+        assert_eq!(grid.world_to_cell((-1.0, 2.0)), Some((0, 1)));
+        assert_eq!(grid.world_to_cell((0.99, 2.99)), Some((3, 0)));
+        assert_eq!(grid.world_to_cell((-1.01, 2.0)), None);
+        assert_eq!(grid.world_to_cell((0.0, 3.0)), None);
+
+        for row in 0..2 {
+            for col in 0..4 {
+                let world = grid.cell_to_world((col, row)).unwrap();
+                assert_eq!(grid.world_to_cell(world), Some((col, row)));
+            }
+        }
+        assert_eq!(grid.cell_to_world((4, 0)), None);
+        assert_eq!(grid.cell_to_world((0, 2)), None);
+    }
+
+    #[test]
+    fn test_world_to_cell_rejects_non_finite_positions() {
+        let metadata = MapMetadata::parse_yaml(YAML).unwrap();
+        let grid = OccupancyGrid::from_gray(4, 2, &[254; 8], metadata, OccupancyOptions::default())
+            .unwrap();
+
+        assert_eq!(grid.world_to_cell((f64::NAN, 2.0)), None);
+        assert_eq!(grid.world_to_cell((-1.0, f64::NAN)), None);
+        assert_eq!(grid.world_to_cell((f64::INFINITY, 2.0)), None);
+        assert_eq!(grid.world_to_cell((-1.0, f64::NEG_INFINITY)), None);
+    }
+
+    #[test]
+    fn test_load_and_search() {
+        let dir = std::env::temp_dir().join(format!("occupancy_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("map.yaml"), YAML.replace("test.pgm", "map.pgm")).unwrap();
+        fs::write(
+            dir.join("map.pgm"),
+            b"P2\n3 3\n255\n254 254 254\n0 0 254\n254 254 254\n",
+        )
+        .unwrap();
+
+        let grid = OccupancyGrid::load(dir.join("map.yaml"), OccupancyOptions::default()).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let start = grid.world_to_cell((-0.75, 2.25)).unwrap();
+        let goal = grid.world_to_cell((-0.75, 3.25)).unwrap();
+        assert_eq!((start, goal), ((0, 2), (0, 0)));
+
+        let mut astar = AStar::new(grid.nodes.clone());
+        let path = astar.find_shortest_path(start, goal).unwrap();
+        assert_eq!(path.len(), 6);
+        assert!(path.contains((2, 1)));
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn test_png() {
+        // A 2x1 RGB image with a light and a dark pixel
+        let bytes: [u8; 72] = [
+            0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48,
+            0x44, 0x52, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x00, 0x00,
+            0x00, 0x7b, 0x40, 0xe8, 0xdd, 0x00, 0x00, 0x00, 0x0f, 0x49, 0x44, 0x41, 0x54, 0x78,
+            0x9c, 0x63, 0xf8, 0xf7, 0xef, 0x1f, 0x03, 0x03, 0x03, 0x00, 0x0e, 0xe9, 0x02, 0xfb,
+            0xbb, 0x99, 0x5c, 0x33, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42,
+            0x60, 0x82,
+        ];
+
+        let grid =
+            OccupancyGrid::from_png(&bytes, MapMetadata::new(1.0), OccupancyOptions::default())
+                .unwrap();
+        assert_eq!(grid.occupancy((0, 0)), Some(Occupancy::Free));
+        assert_eq!(grid.occupancy((1, 0)), Some(Occupancy::Occupied));
+    }
+}
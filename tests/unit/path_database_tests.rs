@@ -0,0 +1,137 @@
+#[cfg(test)]
+mod tests {
+    use controlled_astar::{
+        AStar, AStarError, Direction, Node, Path, PathDatabase, UnreachableReason,
+    };
+    use std::collections::HashMap;
+
+    /// Builds a small map with walls, terrain costs, a one-way link and a teleporter.
+    fn create_arena() -> HashMap<(usize, usize), Node> {
+        let grid = vec![
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 1, 1, 1, 1, 0],
+            vec![0, 0, 0, 0, 1, 0],
+            vec![1, 1, 1, 0, 1, 0],
+            vec![0, 0, 0, 0, 0, 0],
+        ];
+        let mut nodes = Node::grid_to_nodes(&grid);
+        for (&(a, b), node) in nodes.iter_mut() {
+            node.set_cost((a * 3 + b) % 3 + 1);
+        }
+        nodes
+            .get_mut(&(0, 2))
+            .unwrap()
+            .remove_neighbor(Direction::West);
+        nodes
+            .get_mut(&(2, 0))
+            .unwrap()
+            .add_custom_edge((4, 5), 2, "teleporter");
+        nodes
+    }
+
+    #[test]
+    fn test_path_database_matches_astar() {
+        let nodes = create_arena();
+        let database = PathDatabase::new(&nodes).unwrap();
+        let mut astar = AStar::new(nodes.clone());
+
+        for &(b, a) in nodes.keys() {
+            for &(y, x) in nodes.keys() {
+                let (start, goal) = ((a, b), (x, y));
+                let expected = astar.find_shortest_path(start, goal);
+                let found = database.find_path(&nodes, start, goal);
+                match (expected, found) {
+                    (Ok(expected), Ok(path)) => {
+                        assert_eq!(path.total_cost(), expected.total_cost());
+                        assert_eq!((path.start(), path.goal()), (start, goal));
+                        let positions = path.positions().to_vec();
+                        assert_eq!(Path::from_positions(&nodes, positions), Some(path));
+                    }
+                    (Err(expected), Err(error)) => assert_eq!(
+                        std::mem::discriminant(&expected),
+                        std::mem::discriminant(&error)
+                    ),
+                    (expected, found) => {
+                        panic!(
+                            "{start:?} -> {goal:?}: A* gave {expected:?}, database gave {found:?}"
+                        )
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_path_database_compresses_runs() {
+        let nodes = Node::grid_to_nodes(&vec![vec![0; 12]; 12]);
+        let database = PathDatabase::new(&nodes).unwrap();
+        let pairs = nodes.len() * nodes.len();
+        assert!(database.run_count() * 4 < pairs);
+
+        let path = database.find_path(&nodes, (0, 0), (11, 11)).unwrap();
+        assert_eq!(path.total_cost(), 22);
+        let path = database.find_path(&nodes, (5, 5), (5, 5)).unwrap();
+        assert!(path.is_empty());
+    }
+
+    #[test]
+    fn test_path_database_errors() {
+        let grid = vec![vec![0, 0, 1, 0], vec![0, 0, 1, 1]];
+        let mut nodes = Node::grid_to_nodes(&grid);
+        let database = PathDatabase::new(&nodes).unwrap();
+
+        assert!(matches!(
+            database.find_path(&nodes, (0, 0), (4, 0)),
+            Err(AStarError::NodeNotFound((4, 0)))
+        ));
+        assert!(matches!(
+            database.find_path(&nodes, (2, 0), (0, 0)),
+            Err(AStarError::StartNodeBlocked((2, 0)))
+        ));
+        assert!(matches!(
+            database.find_path(&nodes, (0, 0), (2, 1)),
+            Err(AStarError::GoalNodeBlocked((2, 1)))
+        ));
+        match database.find_path(&nodes, (0, 0), (3, 0)) {
+            Err(AStarError::PathNotFound(unreachable)) => {
+                assert_eq!(
+                    unreachable.reason,
+                    UnreachableReason::DisconnectedComponents
+                )
+            }
+            other => panic!("expected PathNotFound, got {other:?}"),
+        }
+
+        // Blocking a node on a stored path makes the database stale
+        nodes.get_mut(&(0, 1)).unwrap().set_blocked(true);
+        nodes.get_mut(&(1, 0)).unwrap().set_blocked(true);
+        assert!(matches!(
+            database.find_path(&nodes, (0, 0), (1, 1)),
+            Err(AStarError::InvalidConfiguration(_))
+        ));
+    }
+
+    #[test]
+    fn test_path_database_rejects_too_many_custom_edges() {
+        // Every custom edge needs a move code below the one for "no move"
+        let mut nodes = Node::grid_to_nodes(&[vec![0, 0]]);
+        let node = nodes.get_mut(&(0, 0)).unwrap();
+        for _ in 0..=usize::from(u16::MAX - 8) {
+            node.add_custom_edge((0, 1), 1, "ramp");
+        }
+        assert!(matches!(
+            PathDatabase::new(&nodes),
+            Err(AStarError::InvalidConfiguration(_))
+        ));
+
+        nodes.get_mut(&(0, 0)).unwrap().custom_edges.pop();
+        let database = PathDatabase::new(&nodes).unwrap();
+        assert_eq!(
+            database
+                .find_path(&nodes, (0, 0), (1, 0))
+                .unwrap()
+                .total_cost(),
+            1
+        );
+    }
+}
@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod tests {
+    use controlled_astar::{AStar, Direction, Node, Path, Segment};
+
+    #[test]
+    fn test_search_result() {
+        // A single row, so the path has to cross the expensive cell
+        let mut nodes = Node::grid_to_nodes(&[vec![0; 4]]);
+        nodes.get_mut(&(0, 1)).unwrap().set_cost(4);
+        let mut astar = AStar::new(nodes);
+
+        let path = astar.find_shortest_path((0, 0), (3, 0)).unwrap();
+        assert_eq!(path.positions(), [(0, 0), (1, 0), (2, 0), (3, 0)]);
+        assert_eq!(path.step_costs(), [4, 1, 1]);
+        assert_eq!(path.total_cost(), 6);
+        assert_eq!(path.len(), 3);
+        // Directions are the `Node::neighbors` keys, and `Node::new` labels steps along x as south
+        assert_eq!(
+            path.directions(),
+            [
+                Some(Direction::South),
+                Some(Direction::South),
+                Some(Direction::South)
+            ]
+        );
+        assert!(path.contains((2, 0)));
+        assert!(!path.contains((2, 1)));
+        assert!(!path.contains((0, 1)));
+        assert_eq!(path.iter().count(), 4);
+        assert_eq!((&path).into_iter().nth(1), Some(&(1, 0)));
+
+        let single = astar.find_shortest_path((2, 0), (2, 0)).unwrap();
+        assert!(single.is_empty());
+        assert_eq!(single.total_cost(), 0);
+        assert_eq!((single.start(), single.goal()), ((2, 0), (2, 0)));
+        assert!(single.segments().is_empty());
+    }
+
+    #[test]
+    fn test_custom_edges() {
+        let mut nodes = Node::grid_to_nodes(&vec![vec![0; 5]; 5]);
+        nodes
+            .get_mut(&(0, 0))
+            .unwrap()
+            .add_custom_edge((4, 4), 2, "teleporter");
+        let mut astar = AStar::new(nodes.clone());
+
+        let path = astar.find_shortest_path((0, 0), (4, 3)).unwrap();
+        assert_eq!(path.positions(), [(0, 0), (4, 4), (4, 3)]);
+        assert_eq!(path.directions(), [None, Some(Direction::West)]);
+        assert_eq!(path.step_costs(), [2, 1]);
+
+        // A custom edge cheaper than the neighbor in the same place is taken
+        nodes
+            .get_mut(&(0, 0))
+            .unwrap()
+            .add_custom_edge((1, 0), 0, "slide");
+        let path = Path::from_positions(&nodes, vec![(0, 0), (0, 1)]).unwrap();
+        assert_eq!(path.step_costs(), [0]);
+        assert_eq!(path.directions(), [None]);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let nodes = Node::grid_to_nodes(&vec![vec![0; 3]; 3]);
+        let path = Path::from_positions(&nodes, vec![(0, 0), (1, 0), (1, 1)]).unwrap();
+
+        let back = path.reverse();
+        assert_eq!(back.positions(), [(1, 1), (1, 0), (0, 0)]);
+        assert_eq!(
+            back.directions(),
+            [Some(Direction::West), Some(Direction::North)]
+        );
+        assert!(back.contains((1, 0)));
+        assert_eq!(back.reverse(), path);
+    }
+
+    #[test]
+    fn test_segments() {
+        let mut nodes = Node::grid_to_nodes(&vec![vec![0; 4]; 4]);
+        nodes
+            .get_mut(&(3, 3))
+            .unwrap()
+            .add_custom_edge((0, 0), 1, "teleporter");
+        nodes
+            .get_mut(&(0, 0))
+            .unwrap()
+            .add_custom_edge((0, 3), 1, "teleporter");
+        let path = Path::from_positions(
+            &nodes,
+            vec![
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (3, 0),
+                (3, 1),
+                (3, 2),
+                (3, 3),
+                (0, 0),
+                (3, 0),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            path.segments(),
+            vec![
+                Segment {
+                    start: (0, 0),
+                    end: (3, 0),
+                    direction: Some(Direction::South),
+                    steps: 3,
+                    cost: 3,
+                },
+                Segment {
+                    start: (3, 0),
+                    end: (3, 3),
+                    direction: Some(Direction::East),
+                    steps: 3,
+                    cost: 3,
+                },
+                Segment {
+                    start: (3, 3),
+                    end: (0, 0),
+                    direction: None,
+                    steps: 1,
+                    cost: 1,
+                },
+                Segment {
+                    start: (0, 0),
+                    end: (3, 0),
+                    direction: None,
+                    steps: 1,
+                    cost: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unlinked_positions() {
+        let nodes = Node::grid_to_nodes(&vec![vec![0; 3]; 3]);
+        assert!(Path::from_positions(&nodes, Vec::new()).is_none());
+        assert!(Path::from_positions(&nodes, vec![(0, 0), (1, 1)]).is_none());
+        assert!(Path::from_positions(&nodes, vec![(0, 0), (0, 9)]).is_none());
+        assert_eq!(
+            Path::from_positions(&nodes, vec![(2, 2)])
+                .unwrap()
+                .positions(),
+            [(2, 2)]
+        );
+    }
+}
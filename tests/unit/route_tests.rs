@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod tests {
+    use controlled_astar::{AStar, AStarError, Node, WaypointOrder};
+
+    #[test]
+    fn test_fixed_order() {
+        let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 5]; 5]));
+
+        let route = astar
+            .find_route((0, 0), &[(2, 0), (2, 2)], (0, 2), WaypointOrder::Fixed)
+            .unwrap();
+        assert_eq!(route.order, vec![0, 1]);
+        assert_eq!(route.leg_costs, vec![2, 2, 2]);
+        assert_eq!(route.path.total_cost(), 6);
+        assert_eq!(route.path.len(), 6);
+        assert_eq!(route.path.start(), (0, 0));
+        assert_eq!(route.path.goal(), (0, 2));
+        // Junctions appear once
+        let positions = route.path.positions();
+        assert_eq!(positions.iter().filter(|&&p| p == (2, 0)).count(), 1);
+        assert_eq!(positions.iter().filter(|&&p| p == (2, 2)).count(), 1);
+        assert!(positions.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn test_no_waypoints() {
+        let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 4]; 4]));
+        let direct = astar.find_shortest_path((0, 0), (3, 3)).unwrap();
+
+        for order in [WaypointOrder::Fixed, WaypointOrder::Optimized] {
+            let route = astar.find_route((0, 0), &[], (3, 3), order).unwrap();
+            assert_eq!(route.path, direct);
+            assert!(route.order.is_empty());
+            assert_eq!(route.leg_costs, vec![6]);
+        }
+    }
+
+    #[test]
+    fn test_failed_leg() {
+        let mut grid = vec![vec![0; 5]; 5];
+        // Wall off x = 3
+        for row in grid.iter_mut() {
+            row[3] = 1;
+        }
+        let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+
+        let error = astar
+            .find_route((0, 0), &[(2, 2), (4, 4)], (0, 4), WaypointOrder::Fixed)
+            .unwrap_err();
+        assert_eq!(error.leg, 1);
+        assert_eq!((error.from, error.to), ((2, 2), (4, 4)));
+        assert!(matches!(error.error, AStarError::PathNotFound(_)));
+        assert!(std::error::Error::source(&error).is_some());
+        assert!(error.to_string().starts_with("Leg 1 from (2, 2) to (4, 4)"));
+
+        // No order helps, so the given one is reported
+        let error = astar
+            .find_route((0, 0), &[(2, 2), (4, 4)], (0, 4), WaypointOrder::Optimized)
+            .unwrap_err();
+        assert_eq!(error.leg, 1);
+    }
+
+    #[test]
+    fn test_optimized_order() {
+        let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 8]; 8]));
+        let waypoints = [(7, 7), (1, 0), (7, 0), (4, 0), (7, 4)];
+
+        let fixed = astar
+            .find_route((0, 0), &waypoints, (0, 7), WaypointOrder::Fixed)
+            .unwrap();
+        let optimized = astar
+            .find_route((0, 0), &waypoints, (0, 7), WaypointOrder::Optimized)
+            .unwrap();
+        assert_eq!(optimized.order, vec![1, 3, 2, 4, 0]);
+        assert_eq!(optimized.path.total_cost(), 21);
+        assert!(optimized.path.total_cost() < fixed.path.total_cost());
+        assert_eq!(
+            optimized.leg_costs.iter().sum::<usize>(),
+            optimized.path.total_cost()
+        );
+        for waypoint in waypoints {
+            assert!(optimized.path.contains(waypoint));
+        }
+    }
+
+    #[test]
+    fn test_optimized_legs_are_shortest_paths() {
+        // Terrain costs, a one-way teleporter and a waypoint given twice
+        let mut nodes = Node::grid_to_nodes(&vec![vec![0; 6]; 6]);
+        for (&(a, b), node) in nodes.iter_mut() {
+            node.set_cost((a + 2 * b) % 3 + 1);
+        }
+        nodes
+            .get_mut(&(5, 0))
+            .unwrap()
+            .add_custom_edge((0, 5), 1, "teleporter");
+        let mut astar = AStar::new(nodes);
+        let waypoints = [(5, 5), (0, 5), (3, 2), (5, 0), (3, 2)];
+
+        let route = astar
+            .find_route((0, 0), &waypoints, (2, 4), WaypointOrder::Optimized)
+            .unwrap();
+        let mut stops = vec![(0, 0)];
+        stops.extend(route.order.iter().map(|&index| waypoints[index]));
+        stops.push((2, 4));
+        for (pair, &cost) in stops.windows(2).zip(&route.leg_costs) {
+            let expected = astar.find_shortest_path(pair[0], pair[1]).unwrap();
+            assert_eq!(cost, expected.total_cost());
+        }
+        assert_eq!(
+            route.leg_costs.iter().sum::<usize>(),
+            route.path.total_cost()
+        );
+    }
+
+    #[test]
+    fn test_many_waypoints() {
+        // More waypoints than the exact search handles, placed along a ring
+        let mut astar = AStar::new(Node::grid_to_nodes(&vec![vec![0; 10]; 10]));
+        let mut ring: Vec<(usize, usize)> = (0..10).map(|x| (x, 0)).collect();
+        ring.extend((1..10).map(|y| (9, y)));
+        ring.extend((0..9).rev().map(|x| (x, 9)));
+        let waypoints: Vec<_> = ring[1..ring.len() - 1]
+            .iter()
+            .rev()
+            .step_by(2)
+            .copied()
+            .collect();
+        assert!(waypoints.len() > 12);
+
+        let route = astar
+            .find_route((0, 0), &waypoints, (0, 9), WaypointOrder::Optimized)
+            .unwrap();
+        // Walking around the ring is optimal
+        assert_eq!(route.path.total_cost(), 27);
+        let mut order = route.order.clone();
+        order.sort_unstable();
+        assert_eq!(order, (0..waypoints.len()).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use controlled_astar::{AStar, Node, Path, PathSmoother};
+
+    #[test]
+    fn test_line_of_sight() {
+        let grid = vec![vec![0, 0, 0, 0], vec![0, 1, 0, 0], vec![1, 0, 0, 0]];
+        let nodes = Node::grid_to_nodes(&grid);
+        let smoother = PathSmoother::new(&nodes);
+
+        assert!(smoother.has_line_of_sight((0, 0), (3, 0)));
+        assert!(smoother.has_line_of_sight((2, 0), (3, 2)));
+        assert!(smoother.has_line_of_sight((1, 2), (1, 2)));
+        // Through the blocked cell at (1, 1)
+        assert!(!smoother.has_line_of_sight((0, 0), (2, 2)));
+        assert!(!smoother.has_line_of_sight((1, 0), (1, 2)));
+        // Squeezing between (1, 1) and (0, 2), which touch at a corner
+        assert!(!smoother.has_line_of_sight((0, 1), (1, 2)));
+        // Into a blocked or missing cell
+        assert!(!smoother.has_line_of_sight((0, 0), (0, 2)));
+        assert!(!smoother.has_line_of_sight((0, 0), (5, 0)));
+    }
+
+    #[test]
+    fn test_simplify() {
+        let mut nodes = Node::grid_to_nodes(&vec![vec![0; 4]; 4]);
+        nodes
+            .get_mut(&(0, 2))
+            .unwrap()
+            .add_custom_edge((3, 2), 1, "teleporter");
+        let path = Path::from_positions(
+            &nodes,
+            vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2), (2, 3), (3, 3)],
+        )
+        .unwrap();
+        let smoother = PathSmoother::new(&nodes);
+        assert_eq!(
+            smoother.simplify(&path),
+            vec![(0, 0), (2, 0), (2, 3), (3, 3)]
+        );
+
+        // The teleporter step is kept on its own
+        let path =
+            Path::from_positions(&nodes, vec![(0, 0), (1, 0), (2, 0), (2, 3), (3, 3)]).unwrap();
+        assert_eq!(
+            smoother.simplify(&path),
+            vec![(0, 0), (2, 0), (2, 3), (3, 3)]
+        );
+    }
+
+    #[test]
+    fn test_string_pull() {
+        let grid = vec![
+            vec![0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0],
+            vec![0, 0, 1, 0, 0],
+            vec![0, 0, 1, 0, 0],
+            vec![0, 0, 0, 0, 0],
+        ];
+        let mut astar = AStar::new(Node::grid_to_nodes(&grid));
+        let path = astar.find_shortest_path((0, 4), (4, 3)).unwrap();
+        let smoother = PathSmoother::new(astar.nodes());
+        let waypoints = smoother.string_pull(&path);
+
+        assert_eq!(waypoints.first(), Some(&(0, 4)));
+        assert_eq!(waypoints.last(), Some(&(4, 3)));
+        assert!(waypoints.len() < path.positions().len());
+        assert!(waypoints
+            .windows(2)
+            .all(|pair| smoother.has_line_of_sight(pair[0], pair[1])));
+        // The wall cannot be seen through, so a corner is kept
+        assert!(waypoints.len() > 2);
+    }
+
+    #[test]
+    fn test_string_pull_keeps_custom_edges() {
+        let mut nodes = Node::grid_to_nodes(&vec![vec![0; 6]; 6]);
+        nodes
+            .get_mut(&(0, 2))
+            .unwrap()
+            .add_custom_edge((5, 5), 1, "teleporter");
+        let path = Path::from_positions(
+            &nodes,
+            vec![(0, 0), (1, 0), (2, 0), (5, 5), (5, 4), (4, 4), (4, 3)],
+        )
+        .unwrap();
+
+        let waypoints = PathSmoother::new(&nodes).string_pull(&path);
+        assert_eq!(waypoints, vec![(0, 0), (2, 0), (5, 5), (4, 3)]);
+    }
+
+    #[test]
+    fn test_catmull_rom_avoids_obstacles() {
+        let grid = vec![
+            vec![0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0],
+            vec![0, 0, 0, 1, 0],
+            vec![0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0],
+        ];
+        let nodes = Node::grid_to_nodes(&grid);
+        let smoother = PathSmoother::new(&nodes);
+        let waypoints = [(1, 1), (4, 1), (4, 4)];
+        let curve = smoother.catmull_rom(&waypoints, 8);
+
+        assert_eq!(curve.len(), 17);
+        assert_eq!(curve[0], (1.0, 1.0));
+        assert_eq!(curve[8], (4.0, 1.0));
+        assert_eq!(curve[16], (4.0, 4.0));
+        // No point of the curve lies in the blocked cell at (3, 2) or outside the map
+        for &(x, y) in &curve {
+            let cell = ((x + 0.5).floor() as usize, (y + 0.5).floor() as usize);
+            assert_ne!(cell, (3, 2), "{:?}", (x, y));
+            assert!(cell.0 < 5 && cell.1 < 5);
+        }
+
+        // A long sharp turn overshoots into the last row, unless it is blocked
+        let mut grid = vec![vec![0; 11]; 10];
+        let waypoints = [(0, 8), (10, 8), (10, 0)];
+        let curve = PathSmoother::new(&Node::grid_to_nodes(&grid)).catmull_rom(&waypoints, 10);
+        assert!(curve[1..10].iter().any(|&(_, y)| y > 8.5));
+
+        grid[9] = vec![1; 11];
+        let curve = PathSmoother::new(&Node::grid_to_nodes(&grid)).catmull_rom(&waypoints, 10);
+        assert!(curve[1..10].iter().all(|&(_, y)| y == 8.0));
+        assert_eq!(curve[10], (10.0, 8.0));
+
+        assert!(smoother.catmull_rom(&[], 8).is_empty());
+        assert_eq!(smoother.catmull_rom(&[(2, 2)], 8), vec![(2.0, 2.0)]);
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use controlled_astar::{AStar, Direction, EdgeDisplay, Node, SvgOptions, SvgRenderer};
+
+    #[test]
+    fn test_cells_and_costs() {
+        let mut nodes = Node::grid_to_nodes(&[vec![0, 1, 0], vec![0, 0, 0]]);
+        nodes.get_mut(&(1, 2)).unwrap().set_cost(5);
+
+        let mut renderer = SvgRenderer::new(&nodes);
+        renderer.set_options(SvgOptions {
+            cell_size: 10,
+            edges: EdgeDisplay::None,
+            cost_labels: true,
+        });
+        let svg = renderer.render();
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains(r#"width="30" height="20""#));
+
+        // The blocked cell at row 0, column 1
+        assert!(svg.contains(r##"<rect x="10" y="0" width="10" height="10" fill="#333333""##));
+        // The expensive cell is shaded and labelled
+        assert!(svg.contains(r##"<rect x="20" y="10" width="10" height="10" fill="#a0522d""##));
+        assert!(svg.contains(">5</text>"));
+        assert!(!svg.contains("<line"));
+    }
+
+    #[test]
+    fn test_irregular_edges() {
+        let mut nodes = Node::grid_to_nodes(&vec![vec![0; 3]; 3]);
+        let node = nodes.get_mut(&(0, 0)).unwrap();
+        node.set_neighbor(Direction::SouthEast, Some((1, 1)));
+        node.add_custom_edge((2, 2), 3, "teleporter");
+        nodes
+            .get_mut(&(1, 1))
+            .unwrap()
+            .remove_neighbor(Direction::East);
+
+        let renderer = SvgRenderer::new(&nodes);
+        let svg = renderer.render();
+
+        // The diagonal, the teleporter and the one-way link left behind by the removed neighbor
+        assert_eq!(svg.matches("<line").count(), 3);
+        assert_eq!(svg.matches("stroke-dasharray").count(), 1);
+
+        let mut renderer = SvgRenderer::new(&nodes);
+        renderer.set_options(SvgOptions {
+            edges: EdgeDisplay::All,
+            ..SvgOptions::default()
+        });
+        let all_edges: usize = nodes
+            .values()
+            .map(|node| node.neighbors.len() + node.custom_edges.len())
+            .sum();
+        assert_eq!(renderer.render().matches("<line").count(), all_edges);
+    }
+
+    #[test]
+    fn test_search_and_path() {
+        let nodes = Node::grid_to_nodes(&vec![vec![0; 5]; 5]);
+        let mut astar = AStar::new(nodes.clone());
+        let path = astar.find_shortest_path((0, 0), (4, 0)).unwrap();
+
+        let closed = astar.closed_positions();
+        let open = astar.open_positions();
+        assert!(closed.contains(&(0, 0)) && closed.contains(&(4, 0)));
+        assert!(open.is_disjoint(&closed));
+        assert!(!open.is_empty());
+
+        let mut renderer = SvgRenderer::new(&nodes);
+        renderer.set_path(path.positions());
+        renderer.set_search(&astar);
+        let svg = renderer.render();
+
+        assert_eq!(svg.matches("#7ca7cf").count(), closed.len());
+        assert_eq!(svg.matches("#7ccf7c").count(), open.len());
+        assert!(svg.contains(r#"<polyline points="12,12 36,12 60,12 84,12 108,12""#));
+        assert_eq!(svg.matches("<circle").count(), 2);
+    }
+}
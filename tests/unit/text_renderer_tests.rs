@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use controlled_astar::{AStar, Glyphs, Node, TextOptions, TextRenderer};
+    use std::collections::HashMap;
+
+    fn render(renderer: &TextRenderer) -> String {
+        let mut text = String::new();
+        renderer.render(&mut text).unwrap();
+        text
+    }
+
+    #[test]
+    fn test_obstacles_without_path() {
+        let nodes = Node::grid_to_nodes(&[vec![0, 1, 0], vec![0, 0, 1]]);
+        let renderer = TextRenderer::new(&nodes);
+        assert_eq!(render(&renderer), ". # . \n. . # \n");
+        assert_eq!(renderer.to_string(), render(&renderer));
+    }
+
+    #[test]
+    fn test_path_and_glyphs() {
+        let nodes = Node::grid_to_nodes(&[vec![0, 0, 0], vec![1, 1, 0]]);
+        let mut renderer = TextRenderer::new(&nodes);
+        renderer.set_path(&[(0, 0), (1, 0), (2, 0), (2, 1)]);
+        assert_eq!(render(&renderer), "S o o \n# # G \n");
+
+        renderer.set_options(TextOptions {
+            glyphs: Glyphs {
+                free: ' ',
+                blocked: 'X',
+                path: '*',
+                ..Glyphs::default()
+            },
+            endpoints: false,
+            ..TextOptions::default()
+        });
+        assert_eq!(render(&renderer), "* * * \nX X * \n");
+    }
+
+    #[test]
+    fn test_arrows() {
+        let nodes = Node::grid_to_nodes(&[vec![0, 0, 0], vec![0, 0, 0]]);
+        let mut renderer = TextRenderer::new(&nodes);
+        renderer.set_path(&[(0, 1), (0, 0), (1, 0), (2, 1)]);
+        renderer.set_options(TextOptions {
+            arrows: true,
+            ..TextOptions::default()
+        });
+        assert_eq!(render(&renderer), "→ ↘ . \nS . G \n");
+    }
+
+    #[test]
+    fn test_missing_nodes() {
+        let mut nodes = Node::grid_to_nodes(&[vec![0, 0], vec![0, 0]]);
+        nodes.remove(&(0, 1));
+        let renderer = TextRenderer::new(&nodes);
+        assert_eq!(render(&renderer), ".   \n. . \n");
+    }
+
+    #[test]
+    fn test_heatmap_and_colors() {
+        let nodes = Node::grid_to_nodes(&[vec![0, 0, 0]]);
+        let mut renderer = TextRenderer::new(&nodes);
+        renderer.set_scores(HashMap::from([((0, 0), 0), ((1, 0), 5), ((2, 0), 10)]));
+        assert_eq!(render(&renderer), "0 4 9 \n");
+
+        renderer.set_options(TextOptions {
+            colors: true,
+            ..TextOptions::default()
+        });
+        let text = render(&renderer);
+        assert!(text.starts_with("\x1b[30;48;5;46m0\x1b[0m "));
+        assert!(text.contains("\x1b[30;48;5;202m9\x1b[0m"));
+    }
+
+    #[test]
+    fn test_search_scores_and_io_write() {
+        let nodes = Node::grid_to_nodes(&[vec![0, 0, 0, 0]]);
+        let mut astar = AStar::new(nodes.clone());
+        let path = astar.find_shortest_path((0, 0), (2, 0)).unwrap();
+        assert_eq!(astar.g_scores()[&(2, 0)], 2);
+
+        let mut renderer = TextRenderer::new(&nodes);
+        renderer.set_search(&astar);
+        renderer.set_path(path.positions());
+
+        let mut bytes = Vec::new();
+        renderer.write_to(&mut bytes).unwrap();
+        assert_eq!(String::from_utf8(bytes).unwrap(), "S o G . \n");
+    }
+}
@@ -0,0 +1,204 @@
+#[cfg(test)]
+mod tests {
+    use controlled_astar::{AStar, Direction, TiledError, TiledMap, TiledOptions};
+
+    // Tile 1 is floor, 2 is a wall, 3 is mud and 4 is a one-way drop that can only be left southwards
+    const TMX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" width="3" height="3" tilewidth="16" tileheight="16" infinite="0">
+ <tileset firstgid="1" name="tiles" tilewidth="16" tileheight="16" tilecount="4" columns="4">
+  <image source="tiles.png" width="64" height="16"/>
+  <tile id="1">
+   <properties>
+    <property name="blocked" type="bool" value="true"/>
+   </properties>
+  </tile>
+  <tile id="2">
+   <properties>
+    <property name="cost" type="int" value="5"/>
+   </properties>
+  </tile>
+  <tile id="3">
+   <properties>
+    <property name="directions" value="south"/>
+   </properties>
+  </tile>
+ </tileset>
+ <!-- The ground -->
+ <layer id="1" name="ground" width="3" height="3">
+  <data encoding="csv">
+1,4,1,
+1,4,3,
+1,1,1
+</data>
+ </layer>
+ <group id="3" name="objects">
+  <layer id="2" name="walls" width="3" height="3">
+   <data>
+    <tile/><tile/><tile/>
+    <tile gid="2"/><tile/><tile/>
+    <tile/><tile/><tile/>
+   </data>
+  </layer>
+ </group>
+</map>
+"#;
+
+    #[cfg(feature = "serde")]
+    const JSON: &str = r#"{
+  "width": 3, "height": 3, "infinite": false,
+  "tilesets": [{
+    "firstgid": 1,
+    "tiles": [
+      {"id": 1, "properties": [{"name": "blocked", "type": "bool", "value": true}]},
+      {"id": 2, "properties": [{"name": "cost", "type": "int", "value": 5}]},
+      {"id": 3, "properties": [{"name": "directions", "type": "string", "value": "south"}]}
+    ]
+  }],
+  "layers": [
+    {"type": "tilelayer", "name": "ground", "width": 3, "height": 3,
+     "data": [1, 4, 1, 1, 4, 3, 1, 1, 1]},
+    {"type": "group", "name": "objects", "layers": [
+      {"type": "tilelayer", "name": "walls", "width": 3, "height": 3,
+       "data": [0, 0, 0, 2, 0, 0, 0, 0, 0]},
+      {"type": "objectgroup", "name": "spawns", "objects": []}
+    ]}
+  ]
+}"#;
+
+    fn check_map(map: &TiledMap) {
+        assert_eq!((map.width, map.height), (3, 3));
+        assert!(map.nodes[&(1, 0)].is_blocked);
+        assert_eq!(map.nodes[&(1, 2)].cost, 5);
+        assert_eq!(map.nodes[&(0, 0)].cost, 1);
+
+        // The drop tiles can only be left southwards
+        assert_eq!(map.nodes[&(0, 1)].get_directions(), vec![Direction::South]);
+        assert_eq!(map.nodes[&(2, 1)].get_directions().len(), 3);
+        assert_eq!(
+            map.nodes[&(2, 1)].neighbors[&Direction::North],
+            Some((1, 1))
+        );
+    }
+
+    #[test]
+    fn test_tmx_properties() {
+        let map = TiledMap::from_tmx(TMX, &TiledOptions::default()).unwrap();
+        check_map(&map);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_properties() {
+        let map = TiledMap::from_json(JSON, &TiledOptions::default()).unwrap();
+        check_map(&map);
+    }
+
+    #[test]
+    fn test_one_way_drop() {
+        let map = TiledMap::from_tmx(TMX, &TiledOptions::default()).unwrap();
+
+        // Down through the drop is free, back up has to go around through the mud
+        let mut astar = AStar::new(map.nodes.clone());
+        let down = astar.find_shortest_path((1, 0), (1, 2)).unwrap();
+        assert_eq!(down.positions(), [(1, 0), (1, 1), (1, 2)]);
+
+        let mut astar = AStar::new(map.nodes);
+        let up = astar.find_shortest_path((1, 2), (1, 0)).unwrap();
+        assert_eq!(up.positions(), [(1, 2), (2, 2), (2, 1), (2, 0), (1, 0)]);
+    }
+
+    #[test]
+    fn test_chosen_and_collision_layers() {
+        // Only the ground layer: the wall tile on the walls layer is ignored
+        let options = TiledOptions {
+            layers: vec!["ground".to_string()],
+            collision_layer: None,
+        };
+        let map = TiledMap::from_tmx(TMX, &options).unwrap();
+        assert!(!map.nodes[&(1, 0)].is_blocked);
+
+        // Every tile on the collision layer blocks, whatever its properties
+        let options = TiledOptions {
+            layers: vec!["ground".to_string()],
+            collision_layer: Some("walls".to_string()),
+        };
+        let map = TiledMap::from_tmx(TMX, &options).unwrap();
+        assert!(map.nodes[&(1, 0)].is_blocked);
+
+        let options = TiledOptions {
+            layers: vec!["lava".to_string()],
+            collision_layer: None,
+        };
+        assert!(matches!(
+            TiledMap::from_tmx(TMX, &options),
+            Err(TiledError::UnknownLayer(name)) if name == "lava"
+        ));
+    }
+
+    #[test]
+    fn test_flipped_tiles() {
+        // A horizontally flipped wall is still a wall
+        let tmx = TMX.replace("<tile gid=\"2\"/>", "<tile gid=\"2147483650\"/>");
+        let map = TiledMap::from_tmx(&tmx, &TiledOptions::default()).unwrap();
+        assert!(map.nodes[&(1, 0)].is_blocked);
+    }
+
+    #[test]
+    fn test_unsupported_maps() {
+        let base64 = TMX.replace("<data encoding=\"csv\">", "<data encoding=\"base64\">");
+        assert!(matches!(
+            TiledMap::from_tmx(&base64, &TiledOptions::default()),
+            Err(TiledError::Unsupported(_))
+        ));
+
+        let external =
+            r#"<map width="1" height="1"><tileset firstgid="1" source="tiles.tsx"/></map>"#;
+        assert!(matches!(
+            TiledMap::from_tmx(external, &TiledOptions::default()),
+            Err(TiledError::Unsupported(_))
+        ));
+
+        assert!(matches!(
+            TiledMap::from_tmx(
+                "<map width=\"1\" height=\"1\"><layer>",
+                &TiledOptions::default()
+            ),
+            Err(TiledError::InvalidXml(_))
+        ));
+    }
+
+    #[test]
+    fn test_overflowing_sizes_and_ids() {
+        let huge = TMX.replacen(
+            "width=\"3\" height=\"3\"",
+            &format!("width=\"{}\" height=\"3\"", usize::MAX),
+            1,
+        );
+        assert!(matches!(
+            TiledMap::from_tmx(&huge, &TiledOptions::default()),
+            Err(TiledError::InvalidMap(_))
+        ));
+
+        let late_tileset = TMX.replace("firstgid=\"1\"", &format!("firstgid=\"{}\"", u32::MAX));
+        assert!(matches!(
+            TiledMap::from_tmx(&late_tileset, &TiledOptions::default()),
+            Err(TiledError::InvalidMap(_))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_overflowing_json_ids() {
+        let late_tileset = JSON.replace("\"firstgid\": 1", &format!("\"firstgid\": {}", u32::MAX));
+        assert!(matches!(
+            TiledMap::from_json(&late_tileset, &TiledOptions::default()),
+            Err(TiledError::InvalidMap(_))
+        ));
+
+        let huge_firstgid = JSON.replace("\"firstgid\": 1", "\"firstgid\": 4294967296");
+        assert!(matches!(
+            TiledMap::from_json(&huge_firstgid, &TiledOptions::default()),
+            Err(TiledError::InvalidMap(_))
+        ));
+    }
+}
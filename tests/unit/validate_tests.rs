@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod tests {
+    use controlled_astar::{
+        validate, Direction, DirectionConvention, HexLayout, Node, TiledMap, TiledOptions,
+        ValidationIssue, ValidationOptions,
+    };
+
+    #[test]
+    fn test_generated_maps_are_valid() {
+        let nodes = Node::grid_to_nodes(&[vec![0, 1, 0], vec![0, 0, 0]]);
+        assert!(validate(&nodes, &ValidationOptions::default()).is_empty());
+
+        let hex = Node::hex_grid_to_nodes(&vec![vec![0; 3]; 3], HexLayout::OddR);
+        let options = ValidationOptions {
+            directions: DirectionConvention::Unchecked,
+            ..ValidationOptions::default()
+        };
+        assert!(validate(&hex, &options).is_empty());
+
+        let tmx = r#"<map width="2" height="2"><layer name="ground" width="2" height="2"><data encoding="csv">1,1,1,1</data></layer></map>"#;
+        let map = TiledMap::from_tmx(tmx, &TiledOptions::default()).unwrap();
+        let options = ValidationOptions {
+            directions: DirectionConvention::Visual,
+            ..ValidationOptions::default()
+        };
+        assert!(validate(&map.nodes, &options).is_empty());
+        assert!(!validate(&map.nodes, &ValidationOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn test_dangling_and_mismatched_neighbors() {
+        let mut nodes = Node::grid_to_nodes(&vec![vec![0; 3]; 3]);
+        let node = nodes.get_mut(&(1, 1)).unwrap();
+        node.set_neighbor(Direction::NorthEast, Some((5, 5)));
+        node.set_neighbor(Direction::North, Some((1, 2)));
+        node.add_custom_edge((7, 7), 2, "teleporter");
+
+        let issues = validate(&nodes, &ValidationOptions::default());
+        assert!(issues.contains(&ValidationIssue::DanglingNeighbor {
+            position: (1, 1),
+            direction: Direction::NorthEast,
+            target: (5, 5),
+        }));
+        assert!(issues.contains(&ValidationIssue::DanglingCustomEdge {
+            position: (1, 1),
+            label: "teleporter".to_string(),
+            target: (7, 7),
+        }));
+        assert!(issues.contains(&ValidationIssue::DirectionMismatch {
+            position: (1, 1),
+            direction: Direction::North,
+            target: (1, 2),
+            expected: Some((1, 0)),
+        }));
+        // (1, 0) now has a South neighbor that does not link back
+        assert!(issues.contains(&ValidationIssue::AsymmetricEdge {
+            position: (1, 0),
+            direction: Direction::South,
+            target: (1, 1),
+        }));
+        assert_eq!(issues.len(), 4);
+        assert_eq!(
+            issues[0].to_string(),
+            "The South neighbor of (1, 0) is (1, 1), which does not link back"
+        );
+    }
+
+    #[test]
+    fn test_optional_checks() {
+        let mut nodes = Node::grid_to_nodes(&[vec![0, 1]]);
+        nodes
+            .get_mut(&(0, 0))
+            .unwrap()
+            .remove_neighbor(Direction::South);
+
+        assert!(validate(&nodes, &ValidationOptions::default())
+            .iter()
+            .all(|issue| matches!(
+                issue,
+                ValidationIssue::AsymmetricEdge { .. } | ValidationIssue::IsolatedNode(_)
+            )));
+
+        let options = ValidationOptions {
+            asymmetric_edges: false,
+            blocked_neighbors: true,
+            ..ValidationOptions::default()
+        };
+        assert_eq!(
+            validate(&nodes, &options),
+            vec![ValidationIssue::IsolatedNode((0, 0))]
+        );
+
+        nodes.get_mut(&(0, 1)).unwrap().set_blocked(false);
+        nodes.get_mut(&(0, 0)).unwrap().set_blocked(true);
+        assert_eq!(
+            validate(&nodes, &options),
+            vec![
+                ValidationIssue::BlockedNeighbor {
+                    position: (0, 1),
+                    direction: Direction::North,
+                    target: (0, 0),
+                },
+                ValidationIssue::IsolatedNode((0, 1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_key_mismatch() {
+        let mut nodes = Node::grid_to_nodes(&[vec![0, 0]]);
+        let node = nodes.remove(&(0, 1)).unwrap();
+        nodes.insert((0, 2), node);
+
+        let issues = validate(&nodes, &ValidationOptions::default());
+        assert!(issues.contains(&ValidationIssue::KeyMismatch {
+            key: (0, 2),
+            x: 0,
+            y: 1,
+        }));
+        assert!(issues.contains(&ValidationIssue::DanglingNeighbor {
+            position: (0, 0),
+            direction: Direction::South,
+            target: (0, 1),
+        }));
+    }
+}